@@ -0,0 +1,172 @@
+use super::{parse_sequence, require_max_length, require_nucleotides};
+use crate::dto::{AlignmentResultDto, DotplotDto, DotplotPointDto, ScoringParamsDto};
+use helix_core::alignment::{needleman_wunsch_global, ScoringParams};
+use helix_core::dotplot;
+
+/// Compare two versions of a sequence end to end with a global alignment.
+/// The first sequence is the query and the second the target, so the query
+/// and target ends are the two sequence lengths.
+#[tauri::command]
+pub fn compare_sequences(a_json: String, b_json: String) -> Result<AlignmentResultDto, String> {
+    let a = parse_sequence(&a_json)?;
+    let b = parse_sequence(&b_json)?;
+    require_nucleotides(a.sequence(), "First sequence")?;
+    require_nucleotides(b.sequence(), "Second sequence")?;
+    require_alignment_size(a.len(), b.len())?;
+
+    let result = needleman_wunsch_global(
+        a.sequence().as_bytes(),
+        b.sequence().as_bytes(),
+        &ScoringParams::default(),
+    );
+
+    Ok(AlignmentResultDto::from(&result))
+}
+
+/// Most cells of a global alignment matrix `compare_sequences` fills. The
+/// traceback takes one byte per cell, so this caps it at about 100 MB.
+const MAX_ALIGNMENT_CELLS: usize = 100_000_000;
+
+/// Reject a global alignment of `len_a` by `len_b` bases whose traceback
+/// would not fit under [`MAX_ALIGNMENT_CELLS`].
+fn require_alignment_size(len_a: usize, len_b: usize) -> Result<(), String> {
+    let cells = len_a.saturating_add(1).checked_mul(len_b.saturating_add(1));
+    if cells.is_none_or(|cells| cells > MAX_ALIGNMENT_CELLS) {
+        return Err(format!(
+            "Sequences are too large to compare ({} bp by {} bp). \
+             Compare a smaller region instead.",
+            len_a, len_b
+        ));
+    }
+    Ok(())
+}
+
+/// Most points a dot plot sends to the frontend.
+const MAX_DOTPLOT_POINTS: usize = 50_000;
+
+/// Dot plot of two sequences (pass the same one twice for a self-comparison),
+/// capped at `MAX_DOTPLOT_POINTS` points.
+#[tauri::command]
+pub fn dotplot(
+    seq_a_json: String,
+    seq_b_json: String,
+    word_size: usize,
+) -> Result<DotplotDto, String> {
+    let a = parse_sequence(&seq_a_json)?;
+    let b = parse_sequence(&seq_b_json)?;
+    require_nucleotides(a.sequence(), "First sequence")?;
+    require_nucleotides(b.sequence(), "Second sequence")?;
+    require_max_length(a.sequence(), "dot plots")?;
+    require_max_length(b.sequence(), "dot plots")?;
+    if word_size == 0 {
+        return Err("Word size must be at least 1".to_string());
+    }
+    Ok(capped_dotplot(a.sequence(), b.sequence(), word_size, MAX_DOTPLOT_POINTS))
+}
+
+fn capped_dotplot(a: &str, b: &str, word_size: usize, max_points: usize) -> DotplotDto {
+    let mut points = dotplot::dotplot(a, b, word_size);
+    // Flag both strands before truncating, which keeps only the lowest x
+    let has_forward = points.iter().any(|p| !p.is_reverse);
+    let has_reverse = points.iter().any(|p| p.is_reverse);
+    let truncated = points.len() > max_points;
+    points.truncate(max_points);
+
+    DotplotDto {
+        length_a: a.len(),
+        length_b: b.len(),
+        word_size,
+        has_forward,
+        has_reverse,
+        points: points
+            .iter()
+            .map(|p| DotplotPointDto {
+                x: p.x,
+                y: p.y,
+                is_reverse: p.is_reverse,
+            })
+            .collect(),
+        truncated,
+    }
+}
+
+/// Default alignment scoring parameters.
+#[tauri::command]
+pub fn scoring_defaults() -> ScoringParamsDto {
+    ScoringParamsDto::from(&ScoringParams::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::SequenceDto;
+    use helix_core::sequence::{Sequence, Topology};
+
+    #[test]
+    fn test_dotplot_self_comparison() {
+        let seq = Sequence::new("rep", "GATTACAGGCCTTGATTACA", Topology::Linear);
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+        let plot = dotplot(json.clone(), json, 7).unwrap();
+        assert!(!plot.truncated);
+        assert!(plot.has_forward);
+        assert!(plot.points.iter().any(|p| p.x == 0 && p.y == 13));
+
+        let capped = capped_dotplot(seq.sequence(), seq.sequence(), 3, 5);
+        assert!(capped.truncated);
+        assert_eq!(capped.points.len(), 5);
+
+        // An inverted repeat at the far end is still flagged once truncated
+        let a = format!("{}GATTACAGG", "AAAC".repeat(20));
+        let b = format!("{}CCTGTAATC", "AAAC".repeat(20));
+        let capped = capped_dotplot(&a, &b, 6, 5);
+        assert!(capped.truncated);
+        assert!(capped.points.iter().all(|p| !p.is_reverse));
+        assert!(capped.has_reverse);
+    }
+
+    #[test]
+    fn test_scoring_defaults() {
+        let defaults = ScoringParams::default();
+        let dto = scoring_defaults();
+        assert_eq!(dto.match_score, defaults.match_score);
+        assert_eq!(dto.mismatch_score, defaults.mismatch_score);
+        assert_eq!(dto.gap_open, defaults.gap_open);
+        assert_eq!(dto.gap_extend, defaults.gap_extend);
+    }
+
+    fn to_json(name: &str, bases: &str) -> String {
+        let seq = Sequence::new(name, bases, Topology::Circular);
+        serde_json::to_string(&SequenceDto::from(&seq)).unwrap()
+    }
+
+    #[test]
+    fn test_compare_sequences_insertion() {
+        let a = "ATGACCATGATTACGGATTCACTGGCCGTCGTTTTACAACGTCGTGACTGGGAAAACCCTGGCG";
+        let b = format!("{}TTT{}", &a[..30], &a[30..]);
+
+        let cmp = compare_sequences(to_json("v1", a), to_json("v2", &b)).unwrap();
+        assert_eq!((cmp.query_end, cmp.target_end), (a.len(), b.len()));
+        assert_eq!(cmp.gaps, 3);
+        assert_eq!(cmp.mismatches, 0);
+        assert!(cmp.percent_identity > 95.0);
+    }
+
+    #[test]
+    fn test_compare_sequences_rejects_large_inputs() {
+        let plasmid = "ACGT".repeat(12_500);
+        let err = compare_sequences(to_json("v1", &plasmid), to_json("v2", &plasmid)).unwrap_err();
+        assert!(err.starts_with("Sequences are too large to compare"), "{}", err);
+
+        assert!(require_alignment_size(10_000, 9_000).is_ok());
+        assert!(require_alignment_size(usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn test_compare_sequences_rejects_empty() {
+        let err = compare_sequences(to_json("v1", ""), to_json("v2", "ACGT")).unwrap_err();
+        assert_eq!(err, "First sequence is empty");
+
+        let err = compare_sequences(to_json("v1", "ACGT"), to_json("v2", "MKVL*")).unwrap_err();
+        assert!(err.starts_with("Second sequence is not a nucleotide sequence"));
+    }
+}
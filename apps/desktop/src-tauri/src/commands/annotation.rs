@@ -1,7 +1,13 @@
-use crate::dto::{AnnotationHitDto, ComponentDto};
-use helix_components::annotate::{AnnotationConfig, AnnotationHit};
+use super::{parse_sequence, require_max_length, require_nucleotides};
+use crate::dto::{
+    AnnotationConfigDto, AnnotationHitDto, BackboneGuessDto, ComponentDto, ScoringParamsDto,
+    SequenceDto,
+};
+use helix_components::annotate::{
+    apply_hits, AnnotationConfig, AnnotationHit, AnnotationMode, ConflictPolicy, OverlapStrategy,
+};
 use helix_components::component::Component;
-use helix_components::db;
+use helix_components::{backbone, db, export};
 use rusqlite::Connection;
 use std::sync::Mutex;
 use tauri::State;
@@ -13,6 +19,10 @@ pub struct ComponentDbState {
 
 /// Auto-annotate a sequence against the component database.
 /// Returns a list of hits for the user to review before applying.
+///
+/// `mode` selects "dna" (default), "protein" or "both" component kinds.
+/// `overlap_strategy` is "keep_best_score" (default), "keep_longest" or
+/// "keep_all".
 #[tauri::command]
 pub fn auto_annotate(
     state: State<'_, ComponentDbState>,
@@ -20,20 +30,93 @@ pub fn auto_annotate(
     is_circular: bool,
     min_identity: Option<f64>,
     min_coverage: Option<f64>,
+    mode: Option<String>,
+    overlap_strategy: Option<String>,
 ) -> Result<Vec<AnnotationHitDto>, String> {
+    require_nucleotides(&sequence, "Sequence")?;
+    require_max_length(&sequence, "auto-annotation")?;
     let conn = state.conn.lock().map_err(|e| e.to_string())?;
     let components = db::get_components(&conn, None).map_err(|e| e.to_string())?;
 
+    let defaults = AnnotationConfig::default();
     let config = AnnotationConfig {
-        min_identity: min_identity.unwrap_or(80.0),
-        min_coverage: min_coverage.unwrap_or(80.0),
-        ..Default::default()
+        min_identity: min_identity.unwrap_or(defaults.min_identity),
+        min_coverage: min_coverage.unwrap_or(defaults.min_coverage),
+        mode: parse_annotation_mode(mode.as_deref())?,
+        overlap_strategy: parse_overlap_strategy(overlap_strategy.as_deref())?,
+        ..defaults
     };
 
     let hits =
         helix_components::annotate::annotate(&sequence, is_circular, &components, &config);
 
-    Ok(hits.iter().map(|h| annotation_hit_to_dto(h)).collect())
+    Ok(hits.iter().map(annotation_hit_to_dto).collect())
+}
+
+/// The settings `auto_annotate` uses when no overrides are given.
+#[tauri::command]
+pub fn annotation_defaults() -> AnnotationConfigDto {
+    let config = AnnotationConfig::default();
+    AnnotationConfigDto {
+        min_identity: config.min_identity,
+        min_coverage: config.min_coverage,
+        scoring: ScoringParamsDto::from(&config.scoring),
+        band_width: config.band_width,
+        min_score: config.min_score,
+        mode: annotation_mode_name(config.mode).to_string(),
+        overlap_strategy: overlap_strategy_name(config.overlap_strategy).to_string(),
+        overlap_threshold: config.overlap_threshold,
+    }
+}
+
+/// Export reviewed annotation hits as a "gff3" or "bed" feature file body.
+/// `sequence_length` is the length of the annotated sequence, needed for
+/// hits that wrap the origin of a circular sequence.
+#[tauri::command]
+pub fn export_annotation_hits(
+    hits: Vec<AnnotationHitDto>,
+    seqid: String,
+    sequence_length: usize,
+    format: String,
+) -> Result<String, String> {
+    let hits: Vec<AnnotationHit> = hits.iter().map(dto_to_annotation_hit).collect();
+    match format.to_lowercase().as_str() {
+        "gff3" | "gff" => Ok(export::annotation_hits_to_gff3(&hits, &seqid, sequence_length)),
+        "bed" => Ok(export::annotation_hits_to_bed(&hits, &seqid, sequence_length)),
+        other => Err(format!(
+            "Unknown export format '{}'. Expected gff3 or bed.",
+            other
+        )),
+    }
+}
+
+/// Add reviewed annotation hits to a sequence as features.
+///
+/// `policy` decides what happens when a hit overlaps an existing feature:
+/// "skip_if_overlapping" (default), "replace" or "keep_both".
+#[tauri::command]
+pub fn apply_annotation_hits(
+    sequence_json: String,
+    hits: Vec<AnnotationHitDto>,
+    policy: Option<String>,
+) -> Result<SequenceDto, String> {
+    let mut seq = parse_sequence(&sequence_json)?;
+    let policy = parse_conflict_policy(policy.as_deref())?;
+    let hits: Vec<AnnotationHit> = hits.iter().map(dto_to_annotation_hit).collect();
+    apply_hits(&mut seq, &hits, policy);
+    Ok(SequenceDto::from(&seq))
+}
+
+/// Suggest the vector backbone (e.g. "pUC") from reviewed annotation hits.
+/// Returns `None` when the hits match no known backbone.
+#[tauri::command]
+pub fn identify_backbone(hits: Vec<AnnotationHitDto>) -> Result<Option<BackboneGuessDto>, String> {
+    let hits: Vec<AnnotationHit> = hits.iter().map(dto_to_annotation_hit).collect();
+    Ok(backbone::identify_backbone(&hits).map(|guess| BackboneGuessDto {
+        name: guess.name,
+        confidence: guess.confidence,
+        matched_components: guess.matched_components,
+    }))
 }
 
 /// List all components in the database, optionally filtered by category.
@@ -56,6 +139,7 @@ pub fn add_component(
     category: String,
     sequence: String,
     description: Option<String>,
+    author: Option<String>,
 ) -> Result<ComponentDto, String> {
     let conn = state.conn.lock().map_err(|e| e.to_string())?;
     let component = Component {
@@ -70,7 +154,7 @@ pub fn add_component(
         accession: None,
         color: None,
     };
-    let id = db::add_user_component(&conn, &component).map_err(|e| e.to_string())?;
+    let id = db::add_user_component(&conn, &component, author.as_deref()).map_err(|e| e.to_string())?;
     let saved = db::get_component(&conn, id)
         .map_err(|e| e.to_string())?
         .ok_or_else(|| "Failed to retrieve saved component".to_string())?;
@@ -82,9 +166,23 @@ pub fn add_component(
 pub fn delete_component(
     state: State<'_, ComponentDbState>,
     id: i64,
+    author: Option<String>,
 ) -> Result<bool, String> {
     let conn = state.conn.lock().map_err(|e| e.to_string())?;
-    db::delete_user_component(&conn, id).map_err(|e| e.to_string())
+    db::delete_user_component(&conn, id, author.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Delete all user-defined components in a category (built-ins are kept).
+/// Returns the number deleted.
+#[tauri::command]
+pub fn delete_components_by_category(
+    state: State<'_, ComponentDbState>,
+    category: String,
+    author: Option<String>,
+) -> Result<usize, String> {
+    let conn = state.conn.lock().map_err(|e| e.to_string())?;
+    db::delete_user_components_by_category(&conn, &category, author.as_deref())
+        .map_err(|e| e.to_string())
 }
 
 /// Search components by name.
@@ -98,8 +196,75 @@ pub fn search_components(
     Ok(results.iter().map(component_to_dto).collect())
 }
 
+fn parse_annotation_mode(mode: Option<&str>) -> Result<AnnotationMode, String> {
+    match mode.map(str::to_lowercase).as_deref() {
+        None | Some("dna") => Ok(AnnotationMode::DnaOnly),
+        Some("protein") => Ok(AnnotationMode::ProteinOnly),
+        Some("both") => Ok(AnnotationMode::Both),
+        Some(other) => Err(format!(
+            "Unknown annotation mode '{}'. Expected dna, protein or both.",
+            other
+        )),
+    }
+}
+
+fn annotation_mode_name(mode: AnnotationMode) -> &'static str {
+    match mode {
+        AnnotationMode::DnaOnly => "dna",
+        AnnotationMode::ProteinOnly => "protein",
+        AnnotationMode::Both => "both",
+    }
+}
+
+fn parse_overlap_strategy(strategy: Option<&str>) -> Result<OverlapStrategy, String> {
+    match strategy.map(str::to_lowercase).as_deref() {
+        None | Some("keep_best_score") => Ok(OverlapStrategy::KeepBestScore),
+        Some("keep_longest") => Ok(OverlapStrategy::KeepLongest),
+        Some("keep_all") => Ok(OverlapStrategy::KeepAll),
+        Some(other) => Err(format!(
+            "Unknown overlap strategy '{}'. Expected keep_best_score, keep_longest or keep_all.",
+            other
+        )),
+    }
+}
+
+fn overlap_strategy_name(strategy: OverlapStrategy) -> &'static str {
+    match strategy {
+        OverlapStrategy::KeepBestScore => "keep_best_score",
+        OverlapStrategy::KeepLongest => "keep_longest",
+        OverlapStrategy::KeepAll => "keep_all",
+    }
+}
+
+fn parse_conflict_policy(policy: Option<&str>) -> Result<ConflictPolicy, String> {
+    match policy.map(str::to_lowercase).as_deref() {
+        None | Some("skip_if_overlapping") => Ok(ConflictPolicy::SkipIfOverlapping),
+        Some("replace") => Ok(ConflictPolicy::Replace),
+        Some("keep_both") => Ok(ConflictPolicy::KeepBoth),
+        Some(other) => Err(format!(
+            "Unknown conflict policy '{}'. Expected skip_if_overlapping, replace or keep_both.",
+            other
+        )),
+    }
+}
+
 // ── DTO conversions ──
 
+fn dto_to_annotation_hit(dto: &AnnotationHitDto) -> AnnotationHit {
+    AnnotationHit {
+        component_name: dto.component_name.clone(),
+        component_id: dto.component_id,
+        category: dto.category.clone(),
+        target_start: dto.target_start,
+        target_end: dto.target_end,
+        is_reverse_complement: dto.strand < 0,
+        percent_identity: dto.percent_identity,
+        query_coverage: dto.query_coverage,
+        alignment_score: dto.alignment_score,
+        color: Some(dto.color.clone()),
+    }
+}
+
 fn annotation_hit_to_dto(hit: &AnnotationHit) -> AnnotationHitDto {
     AnnotationHitDto {
         component_name: hit.component_name.clone(),
@@ -126,3 +291,93 @@ fn component_to_dto(c: &Component) -> ComponentDto {
         is_builtin: c.is_builtin,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_defaults_match_config() {
+        let config = AnnotationConfig::default();
+        let dto = annotation_defaults();
+        assert_eq!(dto.min_identity, config.min_identity);
+        assert_eq!(dto.min_coverage, config.min_coverage);
+        assert_eq!(dto.band_width, config.band_width);
+        assert_eq!(dto.min_score, config.min_score);
+        assert_eq!(dto.scoring.match_score, config.scoring.match_score);
+        assert_eq!(dto.scoring.gap_extend, config.scoring.gap_extend);
+        assert_eq!(parse_annotation_mode(Some(&dto.mode)), Ok(config.mode));
+        assert_eq!(
+            parse_overlap_strategy(Some(&dto.overlap_strategy)),
+            Ok(config.overlap_strategy)
+        );
+        assert_eq!(dto.overlap_threshold, config.overlap_threshold);
+    }
+
+    #[test]
+    fn test_export_annotation_hits() {
+        let hits = vec![AnnotationHitDto {
+            component_name: "AmpR".to_string(),
+            component_id: 3,
+            category: "resistance".to_string(),
+            target_start: 100,
+            target_end: 961,
+            strand: -1,
+            percent_identity: 99.5,
+            query_coverage: 100.0,
+            alignment_score: 1700,
+            color: "#a78bfa".to_string(),
+        }];
+
+        let export = |format: &str| {
+            export_annotation_hits(hits.clone(), "pUC19".to_string(), 2686, format.to_string())
+        };
+        assert_eq!(export("bed").unwrap(), "pUC19\t100\t961\tAmpR\t0\t-\n");
+        assert!(export("GFF3").unwrap().contains("\t101\t961\t"));
+        assert!(export("embl").is_err());
+    }
+
+    #[test]
+    fn test_apply_annotation_hits() {
+        use helix_core::feature::{Feature, FeatureType, Strand};
+        use helix_core::sequence::{Sequence, Topology};
+
+        let mut seq = Sequence::new("p", "A".repeat(2000), Topology::Circular);
+        seq.add_feature(Feature::new("Manual", FeatureType::Cds, 90, 200, Strand::Forward));
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+        let hits = vec![AnnotationHitDto {
+            component_name: "AmpR".to_string(),
+            component_id: 3,
+            category: "resistance".to_string(),
+            target_start: 100,
+            target_end: 961,
+            strand: -1,
+            percent_identity: 99.5,
+            query_coverage: 100.0,
+            alignment_score: 1700,
+            color: "#a78bfa".to_string(),
+        }];
+
+        let skipped = apply_annotation_hits(json.clone(), hits.clone(), None).unwrap();
+        assert_eq!(skipped.features.len(), 1);
+
+        let replaced =
+            apply_annotation_hits(json.clone(), hits.clone(), Some("replace".to_string())).unwrap();
+        assert_eq!(replaced.features.len(), 1);
+        assert_eq!(replaced.features[0].name, "AmpR");
+        assert_eq!(replaced.features[0].strand, -1);
+
+        assert!(apply_annotation_hits(json, hits, Some("merge".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_parse_annotation_mode() {
+        assert_eq!(parse_annotation_mode(None).unwrap(), AnnotationMode::DnaOnly);
+        assert_eq!(
+            parse_annotation_mode(Some("Protein")).unwrap(),
+            AnnotationMode::ProteinOnly
+        );
+        assert_eq!(parse_annotation_mode(Some("both")).unwrap(), AnnotationMode::Both);
+        assert!(parse_annotation_mode(Some("rna")).is_err());
+    }
+}
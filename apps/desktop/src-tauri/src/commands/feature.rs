@@ -0,0 +1,327 @@
+use super::parse_sequence;
+use crate::dto::{FeatureEditOp, SequenceDto};
+use helix_core::feature::{Feature, FeatureType, Strand};
+use helix_core::sequence::Topology;
+
+/// Merge overlapping or nearby features (within `max_gap` bases) on the same
+/// strand, optionally only when they share a feature type.
+#[tauri::command]
+pub fn merge_features(
+    sequence_json: String,
+    same_type_only: bool,
+    max_gap: usize,
+) -> Result<SequenceDto, String> {
+    let mut seq = parse_sequence(&sequence_json)?;
+    seq.merge_features(same_type_only, max_gap);
+    Ok(SequenceDto::from(&seq))
+}
+
+/// Remove exact duplicate features, keeping the first of each.
+#[tauri::command]
+pub fn dedup_features(sequence_json: String) -> Result<SequenceDto, String> {
+    let mut seq = parse_sequence(&sequence_json)?;
+    seq.dedup_features();
+    Ok(SequenceDto::from(&seq))
+}
+
+/// Name features that only carry their type (or no name at all) from
+/// `scheme`, e.g. "{type}_{n}" for CDS_1, CDS_2, ... in position order.
+/// User-given names are left alone.
+#[tauri::command]
+pub fn relabel_features(sequence_json: String, scheme: String) -> Result<SequenceDto, String> {
+    if !scheme.contains("{n}") {
+        return Err("Naming scheme must contain {n} so the names are distinct".to_string());
+    }
+    let mut seq = parse_sequence(&sequence_json)?;
+    seq.relabel_features(&scheme);
+    Ok(SequenceDto::from(&seq))
+}
+
+/// Add a feature from 1-based inclusive coordinates, as typed by the user.
+/// `start_1based > end_1based` is only accepted on circular sequences, where
+/// the feature wraps through the origin. `strand` is 1, -1 or 0.
+#[tauri::command]
+pub fn add_feature(
+    sequence_json: String,
+    name: String,
+    feature_type: String,
+    start_1based: usize,
+    end_1based: usize,
+    strand: i8,
+) -> Result<SequenceDto, String> {
+    let mut seq = parse_sequence(&sequence_json)?;
+    let len = seq.len();
+    if start_1based == 0 || end_1based == 0 || start_1based > len || end_1based > len {
+        return Err(format!(
+            "Feature {}..{} is outside the sequence (1..{})",
+            start_1based, end_1based, len
+        ));
+    }
+    if start_1based > end_1based && !seq.is_circular() {
+        return Err("Start must not be after end on a linear sequence".to_string());
+    }
+
+    // 1-based inclusive -> 0-based half-open
+    seq.add_feature(Feature::new(
+        name,
+        FeatureType::from_genbank_key(&feature_type),
+        start_1based - 1,
+        end_1based,
+        Strand::from_i8(strand),
+    ));
+    Ok(SequenceDto::from(&seq))
+}
+
+/// Apply a batch of delete, recolor and rename edits, in order. The batch is
+/// all-or-nothing: if any op names a feature that does not exist (or was
+/// deleted earlier in the batch) nothing is applied and the error lists the
+/// offending ids.
+#[tauri::command]
+pub fn edit_features(
+    sequence_json: String,
+    ops: Vec<FeatureEditOp>,
+) -> Result<SequenceDto, String> {
+    let mut seq = parse_sequence(&sequence_json)?;
+    let mut unknown: Vec<&str> = Vec::new();
+
+    let features = seq.features_mut();
+    for op in &ops {
+        let Some(pos) = features.iter().position(|f| f.id.to_string() == op.id()) else {
+            unknown.push(op.id());
+            continue;
+        };
+        match op {
+            FeatureEditOp::Delete { .. } => {
+                features.remove(pos);
+            }
+            FeatureEditOp::Recolor { color, .. } => features[pos].color = Some(color.clone()),
+            FeatureEditOp::Rename { name, .. } => features[pos].name = name.clone(),
+        }
+    }
+
+    if !unknown.is_empty() {
+        return Err(format!("Unknown feature id(s): {}", unknown.join(", ")));
+    }
+    Ok(SequenceDto::from(&seq))
+}
+
+/// Overlay features from a GFF3 (`.gff`, `.gff3`) or BED (`.bed`) file onto
+/// the sequence, leaving its bases unchanged. Files with another extension are
+/// read as GFF3 if they start with a `##gff-version` header. Fails without
+/// adding anything if any feature lies outside the sequence, listing the
+/// offending line numbers.
+#[tauri::command]
+pub fn import_annotations(sequence_json: String, path: String) -> Result<SequenceDto, String> {
+    let mut seq = parse_sequence(&sequence_json)?;
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let lower = path.to_lowercase();
+    let features = if lower.ends_with(".bed") {
+        helix_formats::bed::parse_features_with_lines(&content)
+    } else if lower.ends_with(".gff")
+        || lower.ends_with(".gff3")
+        || content.trim_start().starts_with("##gff-version")
+    {
+        helix_formats::gff::parse_features_with_lines(&content)
+    } else {
+        return Err("Unsupported annotation file. Helix imports GFF3 (.gff, .gff3) and BED (.bed) files.".to_string());
+    }
+    .map_err(|e| format!("Failed to parse annotations: {}", e))?;
+
+    let len = seq.len();
+    let out_of_range: Vec<String> = features
+        .iter()
+        .filter(|(_, f)| f.location.ranges().iter().any(|&(_, end)| end > len))
+        .map(|(line, _)| line.to_string())
+        .collect();
+    if !out_of_range.is_empty() {
+        return Err(format!(
+            "Features on line(s) {} extend past the end of the sequence (length {})",
+            out_of_range.join(", "),
+            len
+        ));
+    }
+
+    for (_, feature) in features {
+        seq.add_feature(feature);
+    }
+    Ok(SequenceDto::from(&seq))
+}
+
+/// Extract `start..end` (wrapping through the origin when `is_circular` and
+/// `start > end`) as a new sequence, carrying over the features in that range.
+#[tauri::command]
+pub fn extract_region(
+    sequence_json: String,
+    start: usize,
+    end: usize,
+    is_circular: bool,
+) -> Result<SequenceDto, String> {
+    let mut seq = parse_sequence(&sequence_json)?;
+    seq.topology = if is_circular {
+        Topology::Circular
+    } else {
+        Topology::Linear
+    };
+    if start > seq.len() || end > seq.len() {
+        return Err(format!(
+            "Region {}..{} is outside the sequence (length {})",
+            start,
+            end,
+            seq.len()
+        ));
+    }
+    if start > end && !is_circular {
+        return Err("Start must not be after end on a linear sequence".to_string());
+    }
+    Ok(SequenceDto::from(&seq.extract_region(start, end)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_core::sequence::Sequence;
+
+    #[test]
+    fn test_merge_features_command() {
+        let mut seq = Sequence::new("test", "A".repeat(300), Topology::Linear);
+        seq.add_feature(Feature::new("CDS", FeatureType::Cds, 0, 100, Strand::Forward));
+        seq.add_feature(Feature::new("CDS", FeatureType::Cds, 50, 150, Strand::Forward));
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let merged = merge_features(json, true, 0).unwrap();
+        assert_eq!(merged.features.len(), 1);
+        assert_eq!((merged.features[0].start, merged.features[0].end), (0, 150));
+    }
+
+    #[test]
+    fn test_relabel_features_command() {
+        let mut seq = Sequence::new("test", "A".repeat(300), Topology::Linear);
+        seq.add_feature(Feature::new("CDS", FeatureType::Cds, 200, 250, Strand::Forward));
+        seq.add_feature(Feature::new("CDS", FeatureType::Cds, 0, 50, Strand::Forward));
+        seq.add_feature(Feature::new("lacZ", FeatureType::Cds, 60, 90, Strand::Forward));
+        seq.add_feature(Feature::new("CDS", FeatureType::Cds, 100, 150, Strand::Reverse));
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let relabeled = relabel_features(json.clone(), "{type}_{n}".to_string()).unwrap();
+        let names: Vec<&str> = relabeled.features.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["CDS_3", "CDS_1", "lacZ", "CDS_2"]);
+
+        assert!(relabel_features(json, "{type}".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_import_bed_annotations() {
+        let mut seq = Sequence::new("test", "A".repeat(200), Topology::Linear);
+        seq.add_feature(Feature::new("existing", FeatureType::Cds, 0, 10, Strand::Forward));
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let path = std::env::temp_dir().join(format!("helix-test-{}.bed", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "chr\t10\t50\tpromoter\t0\t+\nchr\t100\t180\tgene\t0\t-\n").unwrap();
+        let imported = import_annotations(json.clone(), path.to_str().unwrap().to_string()).unwrap();
+
+        assert_eq!(imported.sequence, seq.sequence());
+        assert_eq!(imported.features.len(), 3);
+        let promoter = &imported.features[1];
+        assert_eq!(promoter.name, "promoter");
+        assert_eq!((promoter.start, promoter.end, promoter.strand), (10, 50, 1));
+        let gene = &imported.features[2];
+        assert_eq!((gene.start, gene.end, gene.strand), (100, 180, -1));
+
+        std::fs::write(&path, "chr\t10\t50\tok\nchr\t150\t250\ttoo_long\n").unwrap();
+        let err = import_annotations(json, path.to_str().unwrap().to_string()).unwrap_err();
+        assert!(err.contains("line(s) 2 "), "{}", err);
+        std::fs::remove_file(&path).ok();
+    }
+
+    fn add(json: &str, feature_type: &str, start: usize, end: usize, strand: i8) -> Result<SequenceDto, String> {
+        add_feature(json.to_string(), "f".to_string(), feature_type.to_string(), start, end, strand)
+    }
+
+    #[test]
+    fn test_add_feature_one_based() {
+        let seq = Sequence::new("test", "A".repeat(100), Topology::Linear);
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let added = add(&json, "promoter", 1, 20, 1).unwrap();
+        let feature = &added.features[0];
+        assert_eq!((feature.start, feature.end), (0, 20));
+        assert_eq!(feature.feature_type, "promoter");
+        assert_eq!(feature.strand, 1);
+
+        let last = add(&json, "terminator", 100, 100, -1).unwrap();
+        assert_eq!((last.features[0].start, last.features[0].end), (99, 100));
+
+        for (start, end) in [(0, 10), (1, 101), (50, 10)] {
+            assert!(add(&json, "misc_feature", start, end, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn test_add_feature_wraps_on_circular() {
+        let seq = Sequence::new("test", "A".repeat(100), Topology::Circular);
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let added = add(&json, "misc_feature", 91, 10, 1).unwrap();
+        assert_eq!((added.features[0].start, added.features[0].end), (90, 10));
+    }
+
+    fn three_feature_json() -> (String, Vec<String>) {
+        let mut seq = Sequence::new("test", "A".repeat(300), Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Cds, 0, 100, Strand::Forward));
+        seq.add_feature(Feature::new("b", FeatureType::Promoter, 100, 150, Strand::Forward));
+        seq.add_feature(Feature::new("c", FeatureType::Terminator, 200, 250, Strand::Reverse));
+        let ids = seq.features().iter().map(|f| f.id.to_string()).collect();
+        (serde_json::to_string(&SequenceDto::from(&seq)).unwrap(), ids)
+    }
+
+    #[test]
+    fn test_edit_features_delete_and_rename() {
+        let (json, ids) = three_feature_json();
+        let ops = vec![
+            FeatureEditOp::Delete { id: ids[0].clone() },
+            FeatureEditOp::Rename { id: ids[1].clone(), name: "lac".to_string() },
+            FeatureEditOp::Recolor { id: ids[1].clone(), color: "#123456".to_string() },
+            FeatureEditOp::Delete { id: ids[2].clone() },
+        ];
+
+        let edited = edit_features(json, ops).unwrap();
+        assert_eq!(edited.features.len(), 1);
+        assert_eq!(edited.features[0].id, ids[1]);
+        assert_eq!(edited.features[0].name, "lac");
+        assert_eq!(edited.features[0].color, "#123456");
+    }
+
+    #[test]
+    fn test_edit_features_unknown_id_aborts() {
+        let (json, ids) = three_feature_json();
+        let ops = vec![
+            FeatureEditOp::Delete { id: ids[0].clone() },
+            FeatureEditOp::Delete { id: "missing".to_string() },
+            FeatureEditOp::Rename { id: ids[0].clone(), name: "gone".to_string() },
+        ];
+
+        let err = edit_features(json, ops).unwrap_err();
+        assert!(err.contains("missing"), "{}", err);
+        assert!(err.contains(&ids[0]), "{}", err);
+
+        let ops: Vec<FeatureEditOp> =
+            serde_json::from_str(r#"[{"op": "recolor", "id": "x", "color": "red"}]"#).unwrap();
+        assert!(matches!(&ops[0], FeatureEditOp::Recolor { color, .. } if color == "red"));
+    }
+
+    #[test]
+    fn test_extract_region_carries_features() {
+        let mut seq = Sequence::new("test", "A".repeat(300), Topology::Linear);
+        seq.add_feature(Feature::new("CDS", FeatureType::Cds, 120, 180, Strand::Forward));
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let region = extract_region(json.clone(), 100, 200, false).unwrap();
+        assert_eq!(region.length, 100);
+        assert_eq!(region.features.len(), 1);
+        assert_eq!((region.features[0].start, region.features[0].end), (20, 80));
+
+        assert!(extract_region(json, 200, 100, false).is_err());
+    }
+}
@@ -11,14 +11,18 @@ pub fn open_sequence_file(path: String) -> Result<OpenFileResult, String> {
     let format_str = match format {
         FileFormat::GenBank => "genbank",
         FileFormat::Fasta => "fasta",
+        FileFormat::Embl => "embl",
+        FileFormat::Fastq => "fastq",
         _ => {
             // Fallback: try extension-based detection
             let ext_format = helix_formats::detect::detect_format_from_extension(&path);
             match ext_format {
                 FileFormat::GenBank => "genbank",
                 FileFormat::Fasta => "fasta",
+                FileFormat::Embl => "embl",
+                FileFormat::Fastq => "fastq",
                 _ => return Err(
-                    "Unsupported file format. Helix supports GenBank (.gb, .gbk) and FASTA (.fasta, .fa) files.".to_string()
+                    "Unsupported file format. Helix supports GenBank (.gb, .gbk), EMBL (.embl), FASTA (.fasta, .fa) and FASTQ (.fastq, .fq) files.".to_string()
                 ),
             }
         }
@@ -54,24 +58,55 @@ pub fn detect_file_format(path: String) -> Result<String, String> {
 
 /// Save a sequence to a file. Format is determined by file extension.
 /// Accepts the sequence as a JSON string matching SequenceDto.
+///
+/// When `append` is true and the target is FASTA, the record is appended to
+/// the existing file instead of overwriting it (for building a multi-FASTA).
+/// Appending is rejected for GenBank and EMBL, which hold one record per file here.
 #[tauri::command]
-pub fn save_sequence_file(path: String, sequence_json: String) -> Result<(), String> {
+pub fn save_sequence_file(
+    path: String,
+    sequence_json: String,
+    append: Option<bool>,
+) -> Result<(), String> {
     let dto: SequenceDto =
         serde_json::from_str(&sequence_json).map_err(|e| format!("Invalid JSON: {}", e))?;
-    let seq = dto.to_core_sequence();
+    write_sequence(&path, &dto.to_core_sequence(), append.unwrap_or(false))
+}
 
-    let content = if path.to_lowercase().ends_with(".fasta")
-        || path.to_lowercase().ends_with(".fa")
-        || path.to_lowercase().ends_with(".fna")
-    {
-        helix_formats::fasta::serialize(&[seq])
-    } else {
+fn is_fasta_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower.ends_with(".fasta") || lower.ends_with(".fa") || lower.ends_with(".fna")
+}
+
+fn write_sequence(path: &str, seq: &helix_core::Sequence, append: bool) -> Result<(), String> {
+    if !is_fasta_path(path) {
+        if append {
+            return Err("Appending is only supported for FASTA files.".to_string());
+        }
         // Default to GenBank
-        helix_formats::genbank::serialize(&seq)
-    };
+        let content = if path.to_lowercase().ends_with(".embl") {
+            helix_formats::embl::serialize(seq)
+        } else {
+            helix_formats::genbank::serialize(seq)
+        };
+        return std::fs::write(path, &content)
+            .map_err(|e| format!("Failed to write file: {}", e));
+    }
 
-    std::fs::write(&path, &content).map_err(|e| format!("Failed to write file: {}", e))?;
-    Ok(())
+    let content = helix_formats::fasta::serialize(std::slice::from_ref(seq));
+    if append {
+        use std::io::Write;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open file: {}", e))?;
+        file.write_all(content.as_bytes())
+            .map_err(|e| format!("Failed to write file: {}", e))
+    } else {
+        std::fs::write(path, &content).map_err(|e| format!("Failed to write file: {}", e))
+    }
 }
 
 /// Export a sequence as a GenBank format string (for preview/clipboard)
@@ -82,3 +117,93 @@ pub fn export_genbank(sequence_json: String) -> Result<String, String> {
     let seq = dto.to_core_sequence();
     Ok(helix_formats::genbank::serialize(&seq))
 }
+
+/// Export the full core sequence, metadata included, as pretty-printed JSON
+/// for external tooling.
+#[tauri::command]
+pub fn export_json(sequence_json: String) -> Result<String, String> {
+    let seq = super::parse_sequence(&sequence_json)?;
+    serde_json::to_string_pretty(&seq).map_err(|e| format!("Failed to serialize sequence: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_core::sequence::{Sequence, Topology};
+
+    fn temp_path(ext: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("helix-test-{}.{}", uuid::Uuid::new_v4(), ext))
+    }
+
+    #[test]
+    fn test_append_fasta() {
+        let path = temp_path("fasta");
+        let path_str = path.to_str().unwrap();
+
+        let first = Sequence::new("seq1", "ATCG", Topology::Linear);
+        let second = Sequence::new("seq2", "GGCC", Topology::Linear);
+        write_sequence(path_str, &first, true).unwrap();
+        write_sequence(path_str, &second, true).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(content, ">seq1\nATCG\n>seq2\nGGCC\n");
+
+        let parsed = helix_formats::fasta::parse(&content).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].name, "seq1");
+        assert_eq!(parsed[1].name, "seq2");
+    }
+
+    #[test]
+    fn test_masked_fasta_survives_open_and_save() {
+        let input = temp_path("fa");
+        let output = temp_path("fa");
+        std::fs::write(&input, ">chr1 masked\nACGTacgtacGT\n").unwrap();
+
+        let opened = open_sequence_file(input.to_str().unwrap().to_string()).unwrap();
+        assert_eq!(opened.sequences[0].sequence, "ACGTacgtacGT");
+        let json = serde_json::to_string(&opened.sequences[0]).unwrap();
+        save_sequence_file(output.to_str().unwrap().to_string(), json, None).unwrap();
+
+        let saved = std::fs::read_to_string(&output).unwrap();
+        std::fs::remove_file(&input).unwrap();
+        std::fs::remove_file(&output).unwrap();
+        assert_eq!(saved, ">chr1 masked\nACGTacgtacGT\n");
+    }
+
+    #[test]
+    fn test_append_genbank_rejected() {
+        let path = temp_path("gb");
+        let seq = Sequence::new("seq1", "ATCG", Topology::Linear);
+        assert!(write_sequence(path.to_str().unwrap(), &seq, true).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_export_json_round_trip() {
+        let mut seq = Sequence::new("pTest", "ATGCATGCAA", Topology::Circular);
+        seq.description = "round trip".to_string();
+        seq.add_feature(helix_core::Feature::new(
+            "orf",
+            helix_core::FeatureType::Cds,
+            0,
+            9,
+            helix_core::Strand::Reverse,
+        ));
+        let dto_json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let exported = export_json(dto_json).unwrap();
+        assert!(exported.contains("\"metadata\""));
+        let back: Sequence = serde_json::from_str(&exported).unwrap();
+        assert_eq!(back.id, seq.id);
+        assert_eq!(back.name, seq.name);
+        assert_eq!(back.description, seq.description);
+        assert_eq!(back.topology, seq.topology);
+        assert_eq!(back.sequence(), seq.sequence());
+        assert_eq!(back.features().len(), 1);
+        assert_eq!(back.features()[0].id, seq.features()[0].id);
+        assert_eq!(back.features()[0].location, seq.features()[0].location);
+        assert_eq!(back.features()[0].strand, seq.features()[0].strand);
+    }
+}
@@ -0,0 +1,110 @@
+use super::parse_sequence;
+use crate::dto::SequenceDto;
+use helix_version::{Delta, EditHistory};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::State;
+use uuid::Uuid;
+
+/// Managed state holding an undo/redo history per open sequence, keyed by
+/// sequence id.
+#[derive(Default)]
+pub struct EditHistoryState {
+    pub histories: Mutex<HashMap<Uuid, EditHistory>>,
+}
+
+impl EditHistoryState {
+    /// Run `f` on the history for `sequence_json`. A new history is started if
+    /// there is none for this sequence or if the frontend's copy has diverged
+    /// from it (e.g. after an edit made outside the history).
+    fn with_history(
+        &self,
+        sequence_json: &str,
+        f: impl FnOnce(&mut EditHistory) -> Result<(), String>,
+    ) -> Result<SequenceDto, String> {
+        let seq = parse_sequence(sequence_json)?;
+        let mut histories = self.histories.lock().map_err(|e| e.to_string())?;
+        let history = histories
+            .entry(seq.id)
+            .or_insert_with(|| EditHistory::new(seq.clone()));
+        if !Delta::diff(history.sequence(), &seq).is_empty() {
+            *history = EditHistory::new(seq);
+        }
+        f(history)?;
+        Ok(SequenceDto::from(history.sequence()))
+    }
+}
+
+/// Apply a delta to the sequence, recording it for undo. Returns the new state.
+#[tauri::command]
+pub fn history_apply(
+    state: State<'_, EditHistoryState>,
+    sequence_json: String,
+    delta: Delta,
+) -> Result<SequenceDto, String> {
+    state.with_history(&sequence_json, |history| {
+        history.apply(delta).map_err(|e| e.to_string())
+    })
+}
+
+/// Undo the last applied delta. Returns the sequence unchanged if there is
+/// nothing to undo.
+#[tauri::command]
+pub fn history_undo(
+    state: State<'_, EditHistoryState>,
+    sequence_json: String,
+) -> Result<SequenceDto, String> {
+    state.with_history(&sequence_json, |history| {
+        history.undo().map(|_| ()).map_err(|e| e.to_string())
+    })
+}
+
+/// Redo the last undone delta. Returns the sequence unchanged if there is
+/// nothing to redo.
+#[tauri::command]
+pub fn history_redo(
+    state: State<'_, EditHistoryState>,
+    sequence_json: String,
+) -> Result<SequenceDto, String> {
+    state.with_history(&sequence_json, |history| {
+        history.redo().map(|_| ()).map_err(|e| e.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_json(dto: &SequenceDto) -> String {
+        serde_json::to_string(dto).unwrap()
+    }
+
+    #[test]
+    fn test_insert_undo_redo_roundtrip() {
+        let state = EditHistoryState::default();
+        let original = SequenceDto::from(&helix_core::Sequence::new(
+            "p",
+            "AAAATTTT",
+            helix_core::Topology::Linear,
+        ));
+        let delta: Delta = serde_json::from_str(
+            r#"{"sequence_ops": [{"op": "insert", "position": 4, "bases": "GGG"}]}"#,
+        )
+        .unwrap();
+
+        let edited = state
+            .with_history(&to_json(&original), |h| h.apply(delta).map_err(|e| e.to_string()))
+            .unwrap();
+        assert_eq!(edited.sequence, "AAAAGGGTTTT");
+
+        let undone = state
+            .with_history(&to_json(&edited), |h| h.undo().map(|_| ()).map_err(|e| e.to_string()))
+            .unwrap();
+        assert_eq!(undone.sequence, original.sequence);
+
+        let redone = state
+            .with_history(&to_json(&undone), |h| h.redo().map(|_| ()).map_err(|e| e.to_string()))
+            .unwrap();
+        assert_eq!(redone.sequence, "AAAAGGGTTTT");
+    }
+}
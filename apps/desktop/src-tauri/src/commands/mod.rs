@@ -1,3 +1,98 @@
+pub mod alignment;
 pub mod annotation;
+pub mod feature;
 pub mod file;
+pub mod history;
+pub mod restriction;
 pub mod sequence;
+
+use crate::dto::SequenceDto;
+use helix_core::operations::{self, MoleculeType};
+
+/// Parse a JSON-encoded `SequenceDto` passed from the frontend.
+pub(crate) fn parse_sequence(sequence_json: &str) -> Result<helix_core::Sequence, String> {
+    let dto: SequenceDto =
+        serde_json::from_str(sequence_json).map_err(|e| format!("Invalid JSON: {}", e))?;
+    Ok(dto.to_core_sequence())
+}
+
+/// Reject empty, whitespace-only or non-nucleotide input at the command
+/// boundary so callers get an error instead of a misleading empty result.
+/// `label` names the argument in the error message.
+pub(crate) fn require_nucleotides(sequence: &str, label: &str) -> Result<(), String> {
+    if sequence.trim().is_empty() {
+        return Err(format!("{} is empty", label));
+    }
+    if let Some(&pos) = operations::validate_alphabet(sequence, MoleculeType::Dna).first() {
+        let ch = sequence.chars().nth(pos).unwrap_or('?');
+        return Err(format!(
+            "{} is not a nucleotide sequence: invalid character '{}' at position {}",
+            label,
+            ch,
+            pos + 1
+        ));
+    }
+    Ok(())
+}
+
+/// Longest sequence the quadratic or six-frame commands (auto-annotation, dot
+/// plots, ORF finding, six-frame translation) accept unless
+/// `HELIX_MAX_SEQUENCE_LENGTH` overrides it. Well above any plasmid or BAC, well below a chromosome.
+pub(crate) const DEFAULT_MAX_SEQUENCE_LENGTH: usize = 2_000_000;
+
+/// The configured length limit: `HELIX_MAX_SEQUENCE_LENGTH` if set to a
+/// number, otherwise [`DEFAULT_MAX_SEQUENCE_LENGTH`].
+pub(crate) fn max_sequence_length() -> usize {
+    std::env::var("HELIX_MAX_SEQUENCE_LENGTH")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_SEQUENCE_LENGTH)
+}
+
+/// Reject sequences longer than the configured limit before starting an
+/// `operation` that would otherwise freeze the app on them.
+pub(crate) fn require_max_length(sequence: &str, operation: &str) -> Result<(), String> {
+    check_length(sequence.len(), max_sequence_length(), operation)
+}
+
+fn check_length(len: usize, max: usize, operation: &str) -> Result<(), String> {
+    if len > max {
+        return Err(format!(
+            "Sequence is too large for {} ({} bp, limit {} bp). \
+             Try a smaller region, or raise HELIX_MAX_SEQUENCE_LENGTH.",
+            operation, len, max
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_nucleotides() {
+        assert!(require_nucleotides("ACGTN\nacgt", "Sequence").is_ok());
+
+        let err = require_nucleotides("", "Sequence").unwrap_err();
+        assert_eq!(err, "Sequence is empty");
+        assert!(require_nucleotides("  \n ", "Sequence").is_err());
+
+        let err = require_nucleotides("ACGQ", "Sequence").unwrap_err();
+        assert!(err.contains("'Q' at position 4"), "{}", err);
+    }
+
+    #[test]
+    fn test_max_length_guard() {
+        let puc19 = include_str!("../../../../../crates/helix-formats/tests/fixtures/pUC19.gb");
+        let plasmid = helix_formats::genbank::parse(puc19).unwrap();
+        assert!(check_length(plasmid.len(), DEFAULT_MAX_SEQUENCE_LENGTH, "ORF finding").is_ok());
+        let max = DEFAULT_MAX_SEQUENCE_LENGTH;
+        let err = check_length(max + 1, max, "ORF finding").unwrap_err();
+        assert!(err.contains("too large for ORF finding"), "{}", err);
+
+        let chromosome = "A".repeat(max_sequence_length() + 1);
+        assert!(require_max_length(&chromosome, "auto-annotation").is_err());
+        assert!(require_max_length(plasmid.sequence(), "auto-annotation").is_ok());
+    }
+}
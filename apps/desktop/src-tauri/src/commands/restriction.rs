@@ -0,0 +1,97 @@
+use super::parse_sequence;
+use crate::dto::{EnzymeDto, EnzymeSummaryDto, RestrictionMapDto};
+use helix_core::restriction;
+
+/// Cut every built-in enzyme against the sequence and classify each as a
+/// unique, multiple or non-cutter.
+#[tauri::command]
+pub fn restriction_map(
+    sequence_json: String,
+    is_circular: bool,
+) -> Result<RestrictionMapDto, String> {
+    let seq = parse_sequence(&sequence_json)?;
+    let enzymes = restriction::builtin_enzymes();
+    let sites = restriction::digest(seq.sequence(), &enzymes, is_circular);
+
+    let mut summaries: Vec<EnzymeSummaryDto> = enzymes
+        .iter()
+        .map(|enzyme| {
+            let cut_positions: Vec<usize> = sites
+                .iter()
+                .filter(|site| site.enzyme == enzyme.name)
+                .map(|site| site.top_cut)
+                .collect();
+            let classification = match cut_positions.len() {
+                0 => "none",
+                1 => "unique",
+                _ => "multiple",
+            };
+            EnzymeSummaryDto {
+                name: enzyme.name.clone(),
+                recognition_site: enzyme.recognition_site.clone(),
+                cut_positions,
+                classification: classification.to_string(),
+            }
+        })
+        .collect();
+    summaries.sort_by_key(|s| {
+        let rank = match s.classification.as_str() {
+            "unique" => 0,
+            "multiple" => 1,
+            _ => 2,
+        };
+        (rank, s.name.to_lowercase())
+    });
+
+    Ok(RestrictionMapDto {
+        enzymes: summaries,
+        cuts: sites
+            .into_iter()
+            .map(|site| EnzymeDto {
+                name: site.enzyme,
+                position: site.top_cut,
+                overhang: site.overhang,
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::SequenceDto;
+
+    const PUC19_GB: &str =
+        include_str!("../../../../../crates/helix-formats/tests/fixtures/pUC19.gb");
+
+    #[test]
+    fn test_restriction_map_puc19() {
+        let seq = helix_formats::genbank::parse(PUC19_GB).unwrap();
+        let json = serde_json::to_string(&SequenceDto::from(&seq)).unwrap();
+
+        let map = restriction_map(json, true).unwrap();
+        let class_of = |name: &str| {
+            map.enzymes
+                .iter()
+                .find(|e| e.name == name)
+                .map(|e| e.classification.as_str())
+                .unwrap()
+        };
+        assert_eq!(class_of("EcoRI"), "unique");
+        let ecori = map.enzymes.iter().find(|e| e.name == "EcoRI").unwrap();
+        assert_eq!(ecori.cut_positions, vec![396]);
+
+        let non_cutters: Vec<&str> = map
+            .enzymes
+            .iter()
+            .filter(|e| e.classification == "none")
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(non_cutters, vec!["NotI", "XhoI"]);
+
+        // Unique cutters are listed first and every cut appears on the map
+        assert_eq!(map.enzymes[0].classification, "unique");
+        assert_eq!(map.cuts.len(), 3);
+        assert!(map.cuts.windows(2).all(|w| w[0].position <= w[1].position));
+    }
+}
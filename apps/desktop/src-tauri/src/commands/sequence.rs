@@ -1,28 +1,113 @@
-use crate::dto::{MatchDto, OrfDto};
+use crate::dto::{
+    feature_type_name, AnnealingDto, CodonTableDto, FrameTranslationDto, MatchDto, OrfDto,
+    SequenceSearchResult, SequenceStatsDto, ValidationDto,
+};
+use helix_core::operations::{MoleculeType, TranslationCache};
+use helix_core::primer::{self, TmParams};
+use helix_core::codon_usage::{self, CodonUsageTable};
 use helix_core::{codon::CodonTable, operations, search};
+use std::sync::{Mutex, OnceLock};
 
 #[tauri::command]
 pub fn reverse_complement(sequence: String) -> String {
     operations::reverse_complement(&sequence)
 }
 
+#[tauri::command]
+pub fn reverse_sequence(sequence: String) -> String {
+    operations::reverse(&sequence)
+}
+
 #[tauri::command]
 pub fn translate(sequence: String) -> String {
     let table = CodonTable::standard();
     operations::translate(&sequence, &table)
 }
 
+/// Frame translations shared across calls, so scrolling the protein track
+/// over an unchanged sequence does not re-translate it.
+fn translation_cache() -> &'static Mutex<TranslationCache> {
+    static CACHE: OnceLock<Mutex<TranslationCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(TranslationCache::new(TRANSLATION_CACHE_CAPACITY)))
+}
+
+/// Frame translations kept before the cache is cleared.
+const TRANSLATION_CACHE_CAPACITY: usize = 256;
+
+/// Translate all six reading frames with the given NCBI table (default 1).
+#[tauri::command]
+pub fn translate_all_frames(
+    sequence: String,
+    table_id: Option<u8>,
+) -> Result<Vec<FrameTranslationDto>, String> {
+    super::require_max_length(&sequence, "six-frame translation")?;
+    let id = table_id.unwrap_or(1);
+    let table = CodonTable::from_id(id)
+        .ok_or_else(|| format!("Unsupported translation table {}", id))?;
+    let mut cache = translation_cache().lock().map_err(|e| e.to_string())?;
+    Ok(cache
+        .translate_six_frames(&sequence, &table)
+        .iter()
+        .map(FrameTranslationDto::from)
+        .collect())
+}
+
+/// Built-in genetic codes accepted by the `table_id` parameters.
+#[tauri::command]
+pub fn codon_tables() -> Vec<CodonTableDto> {
+    CodonTable::BUILTIN_IDS
+        .iter()
+        .filter_map(|&id| CodonTable::from_id(id))
+        .map(|table| CodonTableDto {
+            id: table.id,
+            name: table.name,
+        })
+        .collect()
+}
+
+/// Protein preview from `start` to the next in-frame stop, using the given
+/// NCBI table (default 1).
+#[tauri::command]
+pub fn translate_preview(
+    sequence: String,
+    start: usize,
+    table_id: Option<u8>,
+) -> Result<String, String> {
+    let id = table_id.unwrap_or(1);
+    let table = CodonTable::from_id(id)
+        .ok_or_else(|| format!("Unsupported translation table {}", id))?;
+    Ok(operations::translate_from(&sequence, start, &table, true))
+}
+
 #[tauri::command]
 pub fn gc_content(sequence: String) -> f64 {
     operations::gc_content(&sequence)
 }
 
+/// Length, GC content and feature summary for the overview panel.
 #[tauri::command]
-pub fn find_orfs(sequence: String, min_length_aa: usize) -> Vec<OrfDto> {
-    operations::find_orfs(&sequence, min_length_aa)
+pub fn sequence_stats(sequence_json: String) -> Result<SequenceStatsDto, String> {
+    let seq = super::parse_sequence(&sequence_json)?;
+    Ok(SequenceStatsDto {
+        length: seq.len(),
+        gc_content: operations::gc_content(seq.sequence()),
+        feature_count: seq.features().len(),
+        feature_coverage: seq.coverage_stats().fraction,
+        feature_type_counts: seq
+            .feature_type_histogram()
+            .into_iter()
+            .map(|(feature_type, count)| (feature_type_name(feature_type), count))
+            .collect(),
+    })
+}
+
+#[tauri::command]
+pub fn find_orfs(sequence: String, min_length_aa: usize) -> Result<Vec<OrfDto>, String> {
+    super::require_max_length(&sequence, "ORF finding")?;
+    Ok(operations::find_orfs(&sequence, min_length_aa)
         .iter()
         .map(OrfDto::from)
-        .collect()
+        .collect())
 }
 
 #[tauri::command]
@@ -36,3 +121,309 @@ pub fn search_sequence(
         .map(MatchDto::from)
         .collect()
 }
+
+/// Search several sequences at once, e.g. every open tab. Each entry is
+/// `(id, sequence, is_circular)`; `mode` is "exact" (default, both strands)
+/// or "regex" (forward strand). Sequences without a match are left out.
+#[tauri::command]
+pub fn search_sequences(
+    sequences: Vec<(String, String, bool)>,
+    pattern: String,
+    mode: Option<String>,
+) -> Result<Vec<SequenceSearchResult>, String> {
+    let regex = match mode.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("exact") => false,
+        Some("regex") => true,
+        Some(other) => {
+            return Err(format!(
+                "Unknown search mode '{}'. Expected exact or regex.",
+                other
+            ))
+        }
+    };
+
+    let mut results = Vec::new();
+    for (id, sequence, is_circular) in sequences {
+        let matches = if regex {
+            search::find_regex(&sequence, &pattern, is_circular)
+                .map_err(|e| format!("Invalid regex: {}", e))?
+        } else {
+            search::find_pattern(&sequence, &pattern, is_circular)
+        };
+        if !matches.is_empty() {
+            results.push(SequenceSearchResult {
+                id,
+                matches: matches.iter().map(MatchDto::from).collect(),
+            });
+        }
+    }
+    Ok(results)
+}
+
+/// Degrees below the lower primer Tm to anneal at.
+const ANNEALING_OFFSET: f64 = 3.0;
+/// Largest Tm difference between primers before a warning is raised.
+const MAX_TM_DIFFERENCE: f64 = 5.0;
+
+/// Recommend an annealing temperature for a primer pair from their
+/// nearest-neighbor Tms: the lower Tm minus a few degrees.
+#[tauri::command]
+pub fn suggest_annealing_temp(fwd: String, rev: String) -> Result<AnnealingDto, String> {
+    let params = TmParams::default();
+    let tm_of = |oligo: &str, label: &str| {
+        let tm = primer::tm_nearest_neighbor(oligo.trim(), &params);
+        if tm.is_nan() {
+            Err(format!(
+                "{} primer must be at least 2 bases of A, C, G or T",
+                label
+            ))
+        } else {
+            Ok(tm)
+        }
+    };
+    let forward_tm = tm_of(&fwd, "Forward")?;
+    let reverse_tm = tm_of(&rev, "Reverse")?;
+
+    let difference = (forward_tm - reverse_tm).abs();
+    let warning = (difference > MAX_TM_DIFFERENCE).then(|| {
+        format!(
+            "Primer Tms differ by {:.1} °C; consider redesigning the lower-Tm primer",
+            difference
+        )
+    });
+
+    Ok(AnnealingDto {
+        forward_tm,
+        reverse_tm,
+        annealing_temp: forward_tm.min(reverse_tm) - ANNEALING_OFFSET,
+        warning,
+    })
+}
+
+/// Rough hairpin ΔG (kcal/mol, negative = more stable) for screening oligos.
+#[tauri::command]
+pub fn estimate_oligo_dg(sequence: String) -> Result<f64, String> {
+    super::require_nucleotides(&sequence, "Oligo")?;
+    Ok(operations::estimate_oligo_dg(sequence.trim()))
+}
+
+/// Codon adaptation index of a CDS against a built-in usage table.
+/// `organism` is "e_coli" or "s_cerevisiae".
+#[tauri::command]
+pub fn codon_adaptation_index(cds: String, organism: String) -> Result<f64, String> {
+    super::require_nucleotides(&cds, "CDS")?;
+    let usage = parse_codon_usage(&organism)?;
+    Ok(codon_usage::cai(cds.trim(), &usage))
+}
+
+/// Check pasted input against the expected molecule type before import.
+/// `expected_type` is "dna", "rna" or "protein".
+#[tauri::command]
+pub fn validate_sequence(sequence: String, expected_type: String) -> Result<ValidationDto, String> {
+    let expected = parse_molecule_type(&expected_type)?;
+    let invalid_positions = operations::validate_alphabet(&sequence, expected);
+    let (cleaned, removed_count) = operations::clean_sequence(&sequence, expected, false);
+
+    Ok(ValidationDto {
+        is_valid: invalid_positions.is_empty(),
+        expected_type: molecule_type_name(expected).to_string(),
+        inferred_type: molecule_type_name(operations::infer_molecule_type(&sequence)).to_string(),
+        invalid_positions,
+        cleaned,
+        removed_count,
+    })
+}
+
+fn parse_molecule_type(name: &str) -> Result<MoleculeType, String> {
+    match name.to_lowercase().as_str() {
+        "dna" => Ok(MoleculeType::Dna),
+        "rna" => Ok(MoleculeType::Rna),
+        "protein" => Ok(MoleculeType::Protein),
+        other => Err(format!(
+            "Unknown molecule type '{}'. Expected dna, rna or protein.",
+            other
+        )),
+    }
+}
+
+fn parse_codon_usage(name: &str) -> Result<CodonUsageTable, String> {
+    match name.to_lowercase().as_str() {
+        "e_coli" | "ecoli" => Ok(CodonUsageTable::e_coli()),
+        "s_cerevisiae" | "yeast" => Ok(CodonUsageTable::s_cerevisiae()),
+        other => Err(format!(
+            "Unknown organism '{}'. Expected e_coli or s_cerevisiae.",
+            other
+        )),
+    }
+}
+
+fn molecule_type_name(kind: MoleculeType) -> &'static str {
+    match kind {
+        MoleculeType::Dna => "dna",
+        MoleculeType::Rna => "rna",
+        MoleculeType::Protein => "protein",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_sequence() {
+        assert_eq!(reverse_sequence("ATCG".to_string()), "GCTA");
+    }
+
+    #[test]
+    fn test_translate_all_frames() {
+        let seq = "ATGGCTAAAGGTGAAGAACTGTTCACCGGTTAA";
+        let frames = translate_all_frames(seq.to_string(), None).unwrap();
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[0].label, "+1");
+        assert_eq!(frames[5].label, "-3");
+        assert_eq!(frames[0].protein, translate(seq.to_string()));
+
+        assert!(translate_all_frames(seq.to_string(), Some(99)).is_err());
+    }
+
+    #[test]
+    fn test_codon_tables() {
+        let tables = codon_tables();
+        assert_eq!(tables.len(), CodonTable::BUILTIN_IDS.len());
+        assert_eq!(tables[0].id, 1);
+        assert_eq!(tables[0].name, "Standard");
+    }
+
+    #[test]
+    fn test_sequence_stats() {
+        use helix_core::feature::{Feature, FeatureType, Strand};
+        use helix_core::sequence::{Sequence, Topology};
+
+        let mut seq = Sequence::new("p", "GGCCAATT", Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Cds, 0, 4, Strand::Forward));
+        seq.add_feature(Feature::new("b", FeatureType::Cds, 2, 6, Strand::Forward));
+        seq.add_feature(Feature::new("p", FeatureType::Promoter, 0, 2, Strand::Forward));
+        let json = serde_json::to_string(&crate::dto::SequenceDto::from(&seq)).unwrap();
+
+        let stats = sequence_stats(json).unwrap();
+        assert_eq!(stats.length, 8);
+        assert_eq!(stats.gc_content, 0.5);
+        assert_eq!(stats.feature_count, 3);
+        assert_eq!(stats.feature_coverage, 0.75);
+        assert_eq!(stats.feature_type_counts["cds"], 2);
+        assert_eq!(stats.feature_type_counts["promoter"], 1);
+    }
+
+    #[test]
+    fn test_translate_preview() {
+        let preview = translate_preview("CCATGGCTTAAGG".to_string(), 2, None).unwrap();
+        assert_eq!(preview, "MA*");
+        assert!(translate_preview("ATG".to_string(), 0, Some(99)).is_err());
+    }
+
+    #[test]
+    fn test_search_sequence_wrapped_match() {
+        let matches = search_sequence("CCTTTTGGAT".to_string(), "GGATCC".to_string(), true);
+        let wrapped = matches.iter().find(|m| m.start == 6).unwrap();
+        assert_eq!(wrapped.end, 2);
+        assert!(wrapped.is_wrapped);
+
+        let matches = search_sequence("AAAGGATCC".to_string(), "GGATCC".to_string(), true);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].end, 0);
+        assert!(!matches[0].is_wrapped);
+    }
+
+    #[test]
+    fn test_search_sequences_grouped_by_id() {
+        let sequences = vec![
+            ("a".to_string(), "TTGAATTCAAGAATTC".to_string(), false),
+            ("b".to_string(), "CCCCCCCCCC".to_string(), false),
+            ("c".to_string(), "AGAATTCT".to_string(), true),
+        ];
+        let results = search_sequences(sequences.clone(), "GAATTC".to_string(), None).unwrap();
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c"]);
+        assert_eq!(results[0].matches.len(), 2);
+        assert_eq!(results[1].matches[0].start, 1);
+
+        let results =
+            search_sequences(sequences.clone(), "GAAT+C".to_string(), Some("regex".to_string()))
+                .unwrap();
+        assert_eq!(results.len(), 2);
+
+        assert!(search_sequences(sequences.clone(), "(".to_string(), Some("regex".to_string())).is_err());
+        assert!(search_sequences(sequences, "GAATTC".to_string(), Some("fuzzy".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_suggest_annealing_temp_matched_pair() {
+        // M13 reverse against itself: both Tms agree
+        let primer = "AGCGGATAACAATTTCACACAGGA".to_string();
+        let result = suggest_annealing_temp(primer.clone(), primer).unwrap();
+        assert_eq!(result.forward_tm, result.reverse_tm);
+        let below = result.forward_tm - result.annealing_temp;
+        assert!((2.0..=5.0).contains(&below), "{}", below);
+        assert!(result.warning.is_none());
+    }
+
+    #[test]
+    fn test_suggest_annealing_temp_mismatched_pair() {
+        let result = suggest_annealing_temp(
+            "AGCGGATAACAATTTCACACAGGA".to_string(),
+            "AAAAAAAAAATTTTTTTTTT".to_string(),
+        )
+        .unwrap();
+        assert!(result.forward_tm - result.reverse_tm > 5.0);
+        assert!(result.annealing_temp < result.reverse_tm);
+        assert!(result.warning.unwrap().contains("differ"));
+
+        assert!(suggest_annealing_temp("ACGT".to_string(), "ACNNGT".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_estimate_oligo_dg() {
+        let hairpin = estimate_oligo_dg("GCGCGCGCAAAAGCGCGCGC".to_string()).unwrap();
+        assert!(hairpin < estimate_oligo_dg("ATGACTAGCTTAGCAGTCAA".to_string()).unwrap());
+        assert!(estimate_oligo_dg("".to_string()).is_err());
+        assert!(estimate_oligo_dg("QEF".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_codon_adaptation_index() {
+        let cai = |organism: &str| {
+            codon_adaptation_index("ATGCTGAAATAA".to_string(), organism.to_string()).unwrap()
+        };
+        assert!((cai("e_coli") - 1.0).abs() < 1e-9);
+        assert!(cai("yeast") < 1.0);
+
+        assert!(codon_adaptation_index("ATG".to_string(), "human".to_string()).is_err());
+        assert!(codon_adaptation_index("MKL".to_string(), "e_coli".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_validate_protein_as_dna() {
+        let result = validate_sequence("ATGQEF".to_string(), "dna".to_string()).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.invalid_positions, vec![3, 4, 5]);
+        assert_eq!(result.cleaned, "ATG");
+
+        let result = validate_sequence("MKVLSEGEWQLV".to_string(), "dna".to_string()).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.inferred_type, "protein");
+    }
+
+    #[test]
+    fn test_validate_clean_dna() {
+        let result = validate_sequence("atcg\nggcc".to_string(), "DNA".to_string()).unwrap();
+        assert!(result.is_valid);
+        assert_eq!(result.inferred_type, "dna");
+        assert_eq!(result.cleaned, "ATCGGGCC");
+        assert_eq!(result.removed_count, 1);
+    }
+
+    #[test]
+    fn test_validate_unknown_type() {
+        assert!(validate_sequence("ACGT".to_string(), "peptide".to_string()).is_err());
+    }
+}
@@ -26,6 +26,25 @@ pub struct FeatureDto {
     pub qualifiers: Vec<QualifierDto>,
 }
 
+/// One step of a batch feature edit, addressed by feature id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum FeatureEditOp {
+    Delete { id: String },
+    Recolor { id: String, color: String },
+    Rename { id: String, name: String },
+}
+
+impl FeatureEditOp {
+    pub fn id(&self) -> &str {
+        match self {
+            FeatureEditOp::Delete { id }
+            | FeatureEditOp::Recolor { id, .. }
+            | FeatureEditOp::Rename { id, .. } => id,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualifierDto {
     pub key: String,
@@ -33,11 +52,24 @@ pub struct QualifierDto {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MatchDto {
     pub start: usize,
     pub end: usize,
     pub matched: String,
     pub is_complement: bool,
+    /// True when the match runs through the origin of a circular sequence
+    /// (`end < start`); the match covers `start..length` then `0..end`.
+    pub is_wrapped: bool,
+}
+
+/// Matches in one of several sequences searched together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceSearchResult {
+    /// Caller-supplied sequence id
+    pub id: String,
+    pub matches: Vec<MatchDto>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +82,135 @@ pub struct OrfDto {
     pub protein: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameTranslationDto {
+    /// Display label: "+1", "+2", "+3", "-1", "-2" or "-3"
+    pub label: String,
+    pub frame: i8,
+    pub protein: String,
+}
+
+/// A built-in genetic code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodonTableDto {
+    pub id: u8,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationDto {
+    /// True when every non-whitespace character belongs to the expected alphabet.
+    pub is_valid: bool,
+    pub expected_type: String,
+    pub inferred_type: String,
+    pub invalid_positions: Vec<usize>,
+    /// The input with invalid characters and whitespace stripped.
+    pub cleaned: String,
+    pub removed_count: usize,
+}
+
+/// A pairwise alignment, shared by the commands that align two sequences.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlignmentResultDto {
+    pub score: i32,
+    pub target_start: usize,
+    pub target_end: usize,
+    pub query_start: usize,
+    pub query_end: usize,
+    pub matches: usize,
+    pub mismatches: usize,
+    /// Number of gap columns (inserted or deleted bases).
+    pub gaps: usize,
+    pub alignment_length: usize,
+    /// Matches as a percentage of the alignment length (gaps included).
+    pub percent_identity: f64,
+}
+
+/// Summary numbers for a sequence overview panel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequenceStatsDto {
+    pub length: usize,
+    /// GC fraction (0.0–1.0).
+    pub gc_content: f64,
+    pub feature_count: usize,
+    /// Fraction of bases covered by at least one feature (0.0–1.0).
+    pub feature_coverage: f64,
+    /// Feature counts keyed by feature type, as in `FeatureDto::feature_type`.
+    pub feature_type_counts: std::collections::BTreeMap<String, usize>,
+}
+
+/// A shared word in a dot plot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DotplotPointDto {
+    pub x: usize,
+    pub y: usize,
+    /// True when the word matches the reverse strand of the second sequence.
+    pub is_reverse: bool,
+}
+
+/// Dot plot of two sequences, capped for rendering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DotplotDto {
+    pub length_a: usize,
+    pub length_b: usize,
+    pub word_size: usize,
+    pub points: Vec<DotplotPointDto>,
+    pub has_forward: bool,
+    pub has_reverse: bool,
+    /// True when points were dropped to stay under the cap.
+    pub truncated: bool,
+}
+
+/// Recommended annealing temperature for a primer pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnealingDto {
+    pub forward_tm: f64,
+    pub reverse_tm: f64,
+    pub annealing_temp: f64,
+    /// Set when the primer Tms are too far apart to share one Ta.
+    pub warning: Option<String>,
+}
+
+/// One restriction cut, as drawn on the sequence maps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnzymeDto {
+    pub name: String,
+    /// Top-strand cut position (0-based, cut before this base)
+    pub position: usize,
+    pub overhang: String,
+}
+
+/// How often one enzyme cuts, for the restriction map summary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnzymeSummaryDto {
+    pub name: String,
+    pub recognition_site: String,
+    pub cut_positions: Vec<usize>,
+    /// "unique", "multiple" or "none"
+    pub classification: String,
+}
+
+/// Cuts of every built-in enzyme across a sequence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestrictionMapDto {
+    /// Unique cutters first, then multiple cutters, then non-cutters; by name
+    /// within each group.
+    pub enzymes: Vec<EnzymeSummaryDto>,
+    /// Every cut, sorted by position.
+    pub cuts: Vec<EnzymeDto>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenFileResult {
@@ -66,19 +227,24 @@ impl From<&helix_core::Sequence> for SequenceDto {
             name: seq.name.clone(),
             description: seq.description.clone(),
             topology: seq.topology.to_string(),
-            sequence: seq.sequence.clone(),
+            sequence: seq.sequence().to_string(),
             length: seq.len(),
-            features: seq.features.iter().map(FeatureDto::from).collect(),
+            features: seq.features().iter().map(FeatureDto::from).collect(),
         }
     }
 }
 
+/// Feature type name as sent to the frontend
+pub fn feature_type_name(feature_type: helix_core::FeatureType) -> String {
+    format!("{:?}", feature_type).to_lowercase()
+}
+
 impl From<&helix_core::Feature> for FeatureDto {
     fn from(f: &helix_core::Feature) -> Self {
         FeatureDto {
             id: f.id.to_string(),
-            name: f.name.clone(),
-            feature_type: format!("{:?}", f.feature_type).to_lowercase(),
+            name: f.display_name(),
+            feature_type: feature_type_name(f.feature_type),
             start: f.start(),
             end: f.end(),
             strand: f.strand.as_i8(),
@@ -102,6 +268,18 @@ impl From<&helix_core::search::SequenceMatch> for MatchDto {
             end: m.end,
             matched: m.matched.clone(),
             is_complement: m.is_complement,
+            // A match ending exactly at the sequence end also has `end == 0`
+            is_wrapped: m.end < m.start && m.end > 0,
+        }
+    }
+}
+
+impl From<&helix_core::operations::FrameTranslation> for FrameTranslationDto {
+    fn from(t: &helix_core::operations::FrameTranslation) -> Self {
+        FrameTranslationDto {
+            label: format!("{:+}", t.frame),
+            frame: t.frame,
+            protein: t.protein.clone(),
         }
     }
 }
@@ -118,6 +296,23 @@ impl From<&helix_core::operations::Orf> for OrfDto {
     }
 }
 
+impl From<&helix_core::alignment::AlignmentResult> for AlignmentResultDto {
+    fn from(r: &helix_core::alignment::AlignmentResult) -> Self {
+        AlignmentResultDto {
+            score: r.score,
+            target_start: r.target_start,
+            target_end: r.target_end,
+            query_start: r.query_start,
+            query_end: r.query_end,
+            matches: r.matches,
+            mismatches: r.mismatches,
+            gaps: r.gaps,
+            alignment_length: r.alignment_length,
+            percent_identity: r.percent_identity(),
+        }
+    }
+}
+
 // ── Annotation DTOs ──
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,6 +330,53 @@ pub struct AnnotationHitDto {
     pub color: String,
 }
 
+/// Backbone family suggested by a set of annotation hits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackboneGuessDto {
+    pub name: String,
+    /// Fraction (0.0 to 1.0) of the family's marker components found
+    pub confidence: f64,
+    pub matched_components: Vec<String>,
+}
+
+/// Alignment scoring parameters, as used by annotation and comparison.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoringParamsDto {
+    pub match_score: i32,
+    pub mismatch_score: i32,
+    pub gap_open: i32,
+    pub gap_extend: i32,
+}
+
+impl From<&helix_core::alignment::ScoringParams> for ScoringParamsDto {
+    fn from(p: &helix_core::alignment::ScoringParams) -> Self {
+        Self {
+            match_score: p.match_score,
+            mismatch_score: p.mismatch_score,
+            gap_open: p.gap_open,
+            gap_extend: p.gap_extend,
+        }
+    }
+}
+
+/// Auto-annotation settings, for displaying and pre-filling the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationConfigDto {
+    pub min_identity: f64,
+    pub min_coverage: f64,
+    pub scoring: ScoringParamsDto,
+    pub band_width: Option<usize>,
+    pub min_score: i32,
+    /// "dna", "protein" or "both"
+    pub mode: String,
+    /// "keep_best_score", "keep_longest" or "keep_all"
+    pub overlap_strategy: String,
+    pub overlap_threshold: f64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ComponentDto {
@@ -185,14 +427,44 @@ impl SequenceDto {
             }
         }).collect();
 
-        helix_core::Sequence {
-            id,
-            name: self.name.clone(),
-            description: self.description.clone(),
-            topology,
-            sequence: self.sequence.clone(),
-            features,
-            metadata: helix_core::sequence::SequenceMetadata::default(),
-        }
+        let mut seq = helix_core::Sequence::new(self.name.clone(), "", topology);
+        seq.id = id;
+        seq.description = self.description.clone();
+        // Keep the bases exactly as sent (`Sequence::new` would uppercase them)
+        seq.set_sequence(self.sequence.clone());
+        *seq.features_mut() = features;
+        seq
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_core::alignment::AlignmentResult;
+
+    #[test]
+    fn test_alignment_result_dto() {
+        let result = AlignmentResult {
+            score: 31,
+            target_start: 10,
+            target_end: 50,
+            query_start: 2,
+            query_end: 40,
+            matches: 36,
+            mismatches: 2,
+            gaps: 2,
+            alignment_length: 40,
+        };
+        let dto = AlignmentResultDto::from(&result);
+        assert_eq!(dto.score, 31);
+        assert_eq!((dto.target_start, dto.target_end), (10, 50));
+        assert_eq!((dto.query_start, dto.query_end), (2, 40));
+        assert_eq!((dto.matches, dto.mismatches, dto.gaps), (36, 2, 2));
+        assert_eq!(dto.alignment_length, 40);
+        assert!((dto.percent_identity - 90.0).abs() < 1e-9);
+
+        let json = serde_json::to_value(&dto).unwrap();
+        assert_eq!(json["percentIdentity"], 90.0);
+        assert_eq!(json["targetStart"], 10);
     }
 }
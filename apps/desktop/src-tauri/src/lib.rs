@@ -1,7 +1,7 @@
 pub mod commands;
 pub mod dto;
 
-use commands::{annotation, file, sequence};
+use commands::{alignment, annotation, feature, file, history, restriction, sequence};
 use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -25,7 +25,13 @@ pub fn run() {
 
             helix_components::db::init_db(&conn)
                 .map_err(|e| format!("Failed to init components DB: {}", e))?;
-            let seeded = helix_components::db::seed_builtins(&conn)
+            // HELIX_SEED_SET selects which built-ins to seed: full (default),
+            // minimal or none
+            let seed_set = match std::env::var("HELIX_SEED_SET") {
+                Ok(name) => name.parse()?,
+                Err(_) => helix_components::seed_data::SeedSet::default(),
+            };
+            let seeded = helix_components::db::seed_builtins_set(&conn, seed_set)
                 .map_err(|e| format!("Failed to seed components: {}", e))?;
             if seeded > 0 {
                 eprintln!("Seeded {} built-in components", seeded);
@@ -34,6 +40,7 @@ pub fn run() {
             app.manage(annotation::ComponentDbState {
                 conn: std::sync::Mutex::new(conn),
             });
+            app.manage(history::EditHistoryState::default());
 
             Ok(())
         })
@@ -42,15 +49,45 @@ pub fn run() {
             file::detect_file_format,
             file::save_sequence_file,
             file::export_genbank,
+            file::export_json,
+            history::history_apply,
+            history::history_undo,
+            history::history_redo,
             sequence::reverse_complement,
+            sequence::reverse_sequence,
             sequence::translate,
+            sequence::translate_all_frames,
+            sequence::translate_preview,
+            sequence::codon_tables,
             sequence::gc_content,
+            sequence::sequence_stats,
             sequence::find_orfs,
             sequence::search_sequence,
+            sequence::search_sequences,
+            sequence::validate_sequence,
+            sequence::suggest_annealing_temp,
+            sequence::estimate_oligo_dg,
+            sequence::codon_adaptation_index,
+            feature::add_feature,
+            feature::import_annotations,
+            feature::merge_features,
+            feature::dedup_features,
+            feature::relabel_features,
+            feature::edit_features,
+            feature::extract_region,
+            alignment::compare_sequences,
+            alignment::scoring_defaults,
+            alignment::dotplot,
+            restriction::restriction_map,
             annotation::auto_annotate,
+            annotation::annotation_defaults,
+            annotation::export_annotation_hits,
+            annotation::apply_annotation_hits,
+            annotation::identify_backbone,
             annotation::list_components,
             annotation::add_component,
             annotation::delete_component,
+            annotation::delete_components_by_category,
             annotation::search_components,
         ])
         .run(tauri::generate_context!())
@@ -1,7 +1,58 @@
-use helix_core::alignment::{align_both_strands, ScoringParams};
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+
+use helix_core::alignment::{
+    align_all_both_strands, align_both_strands, map_alignment_to_target, smith_waterman_protein,
+    AlignmentResult, ScoringParams,
+};
+use helix_core::codon::CodonTable;
+use helix_core::feature::{Feature, FeatureType, Location, Strand};
+use helix_core::operations::translate_six_frames;
+use helix_core::search::doubled_for_search;
+use helix_core::sequence::Sequence;
+use helix_core::substitution::SubstitutionMatrix;
 
 use crate::component::Component;
 
+/// Which components the annotation engine considers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationMode {
+    /// Only DNA components (the fast, default path).
+    #[default]
+    DnaOnly,
+    /// Only protein components.
+    ProteinOnly,
+    /// DNA and protein components.
+    Both,
+}
+
+impl AnnotationMode {
+    /// Whether a component with the given sequence is annotated in this mode.
+    pub fn includes(&self, component_sequence: &str) -> bool {
+        let is_dna = is_dna_sequence(component_sequence);
+        match self {
+            AnnotationMode::DnaOnly => is_dna,
+            AnnotationMode::ProteinOnly => !is_dna,
+            AnnotationMode::Both => true,
+        }
+    }
+}
+
+/// How overlapping annotation hits are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapStrategy {
+    /// Keep the higher-scoring of two overlapping hits.
+    #[default]
+    KeepBestScore,
+    /// Keep the wider of two overlapping hits, e.g. a CDS over a tag it
+    /// contains.
+    KeepLongest,
+    /// Keep every hit, for constructs with legitimately nested features.
+    KeepAll,
+}
+
 /// Configuration for the auto-annotation engine.
 #[derive(Debug, Clone)]
 pub struct AnnotationConfig {
@@ -15,6 +66,13 @@ pub struct AnnotationConfig {
     pub band_width: Option<usize>,
     /// Minimum alignment score to even consider a hit.
     pub min_score: i32,
+    /// Which component kinds (DNA, protein, or both) to annotate.
+    pub mode: AnnotationMode,
+    /// Which of two overlapping hits survives.
+    pub overlap_strategy: OverlapStrategy,
+    /// A hit is dropped when more than this fraction (0.0 to 1.0) of it lies
+    /// within a hit already kept. Ignored by [`OverlapStrategy::KeepAll`].
+    pub overlap_threshold: f64,
 }
 
 impl Default for AnnotationConfig {
@@ -25,6 +83,9 @@ impl Default for AnnotationConfig {
             scoring: ScoringParams::default(),
             band_width: Some(50),
             min_score: 20,
+            mode: AnnotationMode::default(),
+            overlap_strategy: OverlapStrategy::default(),
+            overlap_threshold: 0.5,
         }
     }
 }
@@ -40,7 +101,8 @@ pub struct AnnotationHit {
     pub category: String,
     /// Start position in the target (0-based, inclusive).
     pub target_start: usize,
-    /// End position in the target (0-based, exclusive).
+    /// End position in the target (0-based, exclusive). Less than
+    /// `target_start` when the hit wraps the origin of a circular target.
     pub target_end: usize,
     /// Whether the hit is on the reverse complement strand.
     pub is_reverse_complement: bool,
@@ -54,72 +116,371 @@ pub struct AnnotationHit {
     pub color: Option<String>,
 }
 
+impl AnnotationHit {
+    /// Whether the hit runs through the origin of a circular target.
+    pub fn wraps_origin(&self) -> bool {
+        self.target_start > self.target_end
+    }
+
+    /// The spans of a target of length `target_len` the hit covers: one, or
+    /// two for a hit that wraps the origin.
+    pub fn ranges(&self, target_len: usize) -> Vec<(usize, usize)> {
+        if self.wraps_origin() {
+            vec![(self.target_start, target_len), (0, self.target_end)]
+        } else {
+            vec![(self.target_start, self.target_end)]
+        }
+    }
+}
+
+/// Strategy for locating a single component within a target sequence.
+///
+/// A scorer returns its best hit for the component, or `None` if it finds
+/// nothing worth reporting. Identity/coverage thresholds and overlap
+/// resolution are applied afterwards by [`annotate_with_scorer`], so scorers
+/// only need to report what they found.
+pub trait AnnotationScorer {
+    fn score(&self, component: &Component, target: &str) -> Option<AnnotationHit>;
+
+    /// Every hit for the component, for scorers that can find more than one
+    /// occurrence (e.g. two identical terminators). Defaults to the single
+    /// hit from [`AnnotationScorer::score`].
+    fn score_all(&self, component: &Component, target: &str) -> Vec<AnnotationHit> {
+        self.score(component, target).into_iter().collect()
+    }
+}
+
+/// Most occurrences of one DNA component reported per strand.
+const MAX_HITS_PER_STRAND: usize = 10;
+
+/// Gap penalties for protein components, as in BLASTP (open 11, extend 1).
+const PROTEIN_GAP_OPEN: i32 = -11;
+const PROTEIN_GAP_EXTEND: i32 = -1;
+
+/// The default scorer: banded Smith-Waterman alignment against both strands.
+///
+/// Protein components are aligned with BLOSUM62 against all six reading-frame
+/// translations of the target instead, so a coding sequence is found from its
+/// amino acids even when its codons have diverged.
+#[derive(Debug, Clone)]
+pub struct SmithWatermanScorer {
+    /// Smith-Waterman scoring parameters.
+    pub scoring: ScoringParams,
+    /// Band width for banded alignment (None = full matrix).
+    pub band_width: Option<usize>,
+    /// Minimum alignment score to even consider a hit.
+    pub min_score: i32,
+}
+
+impl SmithWatermanScorer {
+    /// Build a scorer using the alignment settings of an [`AnnotationConfig`].
+    pub fn from_config(config: &AnnotationConfig) -> Self {
+        Self {
+            scoring: config.scoring.clone(),
+            band_width: config.band_width,
+            min_score: config.min_score,
+        }
+    }
+
+    /// Best hit of a protein component across the six frames of `target`,
+    /// mapped back to nucleotide coordinates on the forward strand.
+    fn score_protein(&self, component: &Component, target: &str) -> Option<AnnotationHit> {
+        let matrix = SubstitutionMatrix::blosum62();
+        let query = component.sequence.as_bytes();
+
+        let mut best: Option<(AlignmentResult, i8)> = None;
+        for translation in translate_six_frames(target, &CodonTable::standard()) {
+            let Some(alignment) = smith_waterman_protein(
+                query,
+                translation.protein.as_bytes(),
+                &matrix,
+                PROTEIN_GAP_OPEN,
+                PROTEIN_GAP_EXTEND,
+                self.min_score,
+            ) else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|(b, _)| alignment.score > b.score) {
+                best = Some((alignment, translation.frame));
+            }
+        }
+        let (alignment, frame) = best?;
+
+        // Residue i of frame ±k starts k-1+3i bases into its strand
+        let offset = frame.unsigned_abs() as usize - 1;
+        let start = offset + 3 * alignment.target_start;
+        let end = offset + 3 * alignment.target_end;
+        let is_rc = frame < 0;
+        let (start, end) = if is_rc {
+            (target.len() - end, target.len() - start)
+        } else {
+            (start, end)
+        };
+
+        Some(AnnotationHit {
+            component_name: component.name.clone(),
+            component_id: component.id,
+            category: component.category.clone(),
+            target_start: start,
+            target_end: end,
+            is_reverse_complement: is_rc,
+            percent_identity: alignment.percent_identity(),
+            query_coverage: alignment.query_coverage(query.len()),
+            alignment_score: alignment.score,
+            color: component.color.clone(),
+        })
+    }
+
+    /// Hit for an alignment of a DNA component against `target` or, if
+    /// `is_rc`, its reverse complement, in forward-strand coordinates.
+    fn dna_hit(
+        &self,
+        component: &Component,
+        alignment: &AlignmentResult,
+        is_rc: bool,
+        target_len: usize,
+    ) -> AnnotationHit {
+        let (start, end) = map_alignment_to_target(alignment, target_len, is_rc);
+        AnnotationHit {
+            component_name: component.name.clone(),
+            component_id: component.id,
+            category: component.category.clone(),
+            target_start: start,
+            target_end: end,
+            is_reverse_complement: is_rc,
+            percent_identity: alignment.percent_identity(),
+            query_coverage: alignment.query_coverage(component.sequence.len()),
+            alignment_score: alignment.score,
+            color: component.color.clone(),
+        }
+    }
+}
+
+impl Default for SmithWatermanScorer {
+    fn default() -> Self {
+        Self::from_config(&AnnotationConfig::default())
+    }
+}
+
+impl AnnotationScorer for SmithWatermanScorer {
+    fn score(&self, component: &Component, target: &str) -> Option<AnnotationHit> {
+        if !is_dna_sequence(&component.sequence) {
+            return self.score_protein(component, target);
+        }
+
+        let (alignment, is_rc) = align_both_strands(
+            component.sequence.as_bytes(),
+            target.as_bytes(),
+            &self.scoring,
+            self.band_width,
+            self.min_score,
+        )?;
+        Some(self.dna_hit(component, &alignment, is_rc, target.len()))
+    }
+
+    /// Each non-overlapping occurrence of a DNA component on either strand;
+    /// protein components still give their single best hit.
+    fn score_all(&self, component: &Component, target: &str) -> Vec<AnnotationHit> {
+        if !is_dna_sequence(&component.sequence) {
+            return self.score_protein(component, target).into_iter().collect();
+        }
+
+        align_all_both_strands(
+            component.sequence.as_bytes(),
+            target.as_bytes(),
+            &self.scoring,
+            self.band_width,
+            self.min_score,
+            MAX_HITS_PER_STRAND,
+        )
+        .iter()
+        .map(|(alignment, is_rc)| self.dna_hit(component, alignment, *is_rc, target.len()))
+        .collect()
+    }
+}
+
 /// Annotate a target sequence against a set of known components.
 ///
 /// Runs Smith-Waterman alignment of each component's sequence against the
 /// target (both strands), filters by identity/coverage thresholds, and
 /// resolves overlapping hits (keeping the best score per region).
 ///
-/// Only DNA components are aligned (protein components are skipped).
+/// Which components are aligned is controlled by `config.mode` (DNA only by
+/// default). On a circular target, components are also found across the
+/// origin; such hits have `target_start > target_end`.
 pub fn annotate(
     target: &str,
-    _is_circular: bool,
+    is_circular: bool,
     components: &[Component],
     config: &AnnotationConfig,
 ) -> Vec<AnnotationHit> {
-    let target_bytes = target.as_bytes();
-    let mut hits = Vec::new();
+    let scorer = SmithWatermanScorer::from_config(config);
+    annotate_with_scorer(target, is_circular, components, config, &scorer)
+}
 
+/// Annotate a target sequence using a custom [`AnnotationScorer`].
+///
+/// Each component selected by `config.mode` is handed to `scorer`; the
+/// resulting hits are filtered by the identity/coverage thresholds in `config`
+/// and overlapping hits are resolved exactly as in [`annotate`].
+pub fn annotate_with_scorer(
+    target: &str,
+    is_circular: bool,
+    components: &[Component],
+    config: &AnnotationConfig,
+    scorer: &dyn AnnotationScorer,
+) -> Vec<AnnotationHit> {
+    let components: Vec<&Component> = components
+        .iter()
+        .filter(|component| config.mode.includes(&component.sequence))
+        .collect();
+    let search_target = search_target(target, is_circular, &components);
+
+    let mut hits = Vec::new();
     for component in components {
-        // Skip protein sequences — we only do DNA alignment here
-        if !is_dna_sequence(&component.sequence) {
-            continue;
-        }
+        hits.extend(
+            scorer
+                .score_all(component, &search_target)
+                .into_iter()
+                .filter_map(|hit| fold_to_target(hit, target.len()))
+                .filter(|hit| threshold_failure(hit, config).is_none()),
+        );
+    }
 
-        let query = component.sequence.as_bytes();
+    resolve_overlaps(hits, config.overlap_strategy, config.overlap_threshold, target.len())
+}
 
-        let result = align_both_strands(
-            query,
-            target_bytes,
-            &config.scoring,
-            config.band_width,
-            config.min_score,
-        );
+/// The sequence to align `components` against: `target` itself, or for a
+/// circular target, `target` extended by enough of its start that a
+/// component running through the origin lies in one piece.
+fn search_target<'a>(
+    target: &'a str,
+    is_circular: bool,
+    components: &[&Component],
+) -> Cow<'a, str> {
+    if !is_circular {
+        return Cow::Borrowed(target);
+    }
+    let longest = components
+        .iter()
+        .map(|c| nucleotide_span(&c.sequence))
+        .max()
+        .unwrap_or(0);
+    Cow::Owned(doubled_for_search(target, longest.saturating_sub(1)))
+}
 
-        if let Some((alignment, is_rc)) = result {
-            let identity = alignment.percent_identity();
-            let coverage = alignment.query_coverage(query.len());
-
-            if identity >= config.min_identity && coverage >= config.min_coverage {
-                let (start, end) = if is_rc {
-                    // For reverse complement hits, convert coordinates back
-                    let target_len = target_bytes.len();
-                    let rc_start = target_len - alignment.target_end;
-                    let rc_end = target_len - alignment.target_start;
-                    (rc_start, rc_end)
-                } else {
-                    (alignment.target_start, alignment.target_end)
-                };
-
-                hits.push(AnnotationHit {
-                    component_name: component.name.clone(),
-                    component_id: component.id,
-                    category: component.category.clone(),
-                    target_start: start,
-                    target_end: end,
-                    is_reverse_complement: is_rc,
-                    percent_identity: identity,
-                    query_coverage: coverage,
-                    alignment_score: alignment.score,
-                    color: component.color.clone(),
-                });
-            }
-        }
+/// Bases of target a component covers: its length, or three per residue for
+/// a protein.
+fn nucleotide_span(component_sequence: &str) -> usize {
+    if is_dna_sequence(component_sequence) {
+        component_sequence.len()
+    } else {
+        component_sequence.len() * 3
+    }
+}
+
+/// Map a hit on a circular target extended past its origin back onto the
+/// target of length `target_len`. Hits that start in the extension repeat
+/// one found before the origin and are dropped, as are hits longer than the
+/// target itself.
+fn fold_to_target(mut hit: AnnotationHit, target_len: usize) -> Option<AnnotationHit> {
+    if hit.target_end <= target_len {
+        return Some(hit);
+    }
+    if hit.target_start >= target_len || hit.target_end - hit.target_start > target_len {
+        return None;
     }
+    hit.target_end -= target_len;
+    Some(hit)
+}
+
+/// Diagnostic result for one component from [`annotate_debug`].
+#[derive(Debug, Clone)]
+pub struct ComponentScore {
+    /// Name of the component.
+    pub component_name: String,
+    /// Database ID of the component.
+    pub component_id: i64,
+    /// Best alignment score, or `None` if nothing aligned above `min_score`.
+    pub best_score: Option<i32>,
+    /// Percent identity of the best alignment (0 if none).
+    pub percent_identity: f64,
+    /// Query coverage of the best alignment (0 if none).
+    pub query_coverage: f64,
+    /// Whether the component would be reported by [`annotate`] (before
+    /// overlap resolution).
+    pub passed: bool,
+    /// "pass", or why the component was rejected: "excluded by mode",
+    /// "no alignment", "below identity" or "below coverage".
+    pub reason: String,
+    /// Time spent scoring this component.
+    pub elapsed: Duration,
+}
 
-    // Sort by score descending, then resolve overlaps
-    hits.sort_by(|a, b| b.alignment_score.cmp(&a.alignment_score));
-    resolve_overlaps(hits)
+/// Dry-run annotation for tuning thresholds.
+///
+/// Scores every component the same way [`annotate`] does but reports all of
+/// them, passing or not, with the reason each one was rejected. Overlap
+/// resolution is not applied.
+pub fn annotate_debug(
+    target: &str,
+    is_circular: bool,
+    components: &[Component],
+    config: &AnnotationConfig,
+) -> Vec<ComponentScore> {
+    let scorer = SmithWatermanScorer::from_config(config);
+    let included: Vec<&Component> = components
+        .iter()
+        .filter(|component| config.mode.includes(&component.sequence))
+        .collect();
+    let search_target = search_target(target, is_circular, &included);
+
+    components
+        .iter()
+        .map(|component| {
+            let started = Instant::now();
+            let hit = if config.mode.includes(&component.sequence) {
+                let best = scorer
+                    .score_all(component, &search_target)
+                    .into_iter()
+                    .filter_map(|hit| fold_to_target(hit, target.len()))
+                    .max_by_key(|hit| hit.alignment_score);
+                Some(best)
+            } else {
+                None
+            };
+            let elapsed = started.elapsed();
+
+            let reason = match &hit {
+                None => "excluded by mode",
+                Some(None) => "no alignment",
+                Some(Some(hit)) => threshold_failure(hit, config).unwrap_or("pass"),
+            };
+            let hit = hit.flatten();
+
+            ComponentScore {
+                component_name: component.name.clone(),
+                component_id: component.id,
+                best_score: hit.as_ref().map(|h| h.alignment_score),
+                percent_identity: hit.as_ref().map_or(0.0, |h| h.percent_identity),
+                query_coverage: hit.as_ref().map_or(0.0, |h| h.query_coverage),
+                passed: reason == "pass",
+                reason: reason.to_string(),
+                elapsed,
+            }
+        })
+        .collect()
+}
+
+/// Which threshold in `config` a hit fails, if any.
+fn threshold_failure(hit: &AnnotationHit, config: &AnnotationConfig) -> Option<&'static str> {
+    if hit.percent_identity < config.min_identity {
+        Some("below identity")
+    } else if hit.query_coverage < config.min_coverage {
+        Some("below coverage")
+    } else {
+        None
+    }
 }
 
 /// Check if a sequence is DNA (contains only ACGT characters).
@@ -128,24 +489,42 @@ fn is_dna_sequence(seq: &str) -> bool {
         .all(|c| matches!(c.to_ascii_uppercase(), 'A' | 'C' | 'G' | 'T'))
 }
 
-/// Resolve overlapping hits by keeping the best-scoring hit for each region.
+/// Resolve overlapping hits according to `strategy`.
 ///
-/// Uses a greedy interval scheduling approach: iterate hits by descending
-/// score, and only keep a hit if it doesn't overlap significantly (>50%)
-/// with any already-accepted hit.
-fn resolve_overlaps(hits: Vec<AnnotationHit>) -> Vec<AnnotationHit> {
-    let mut accepted: Vec<AnnotationHit> = Vec::new();
-
-    for hit in hits {
-        let dominated = accepted.iter().any(|existing| {
-            let overlap = overlap_fraction(&hit, existing);
-            overlap > 0.5
-        });
-
-        if !dominated {
-            accepted.push(hit);
+/// Uses a greedy interval scheduling approach: iterate hits from most to least
+/// preferred (by score, or by length for [`OverlapStrategy::KeepLongest`]),
+/// and only keep a hit if no more than `threshold` of it overlaps an
+/// already-accepted hit. [`OverlapStrategy::KeepAll`] keeps every hit.
+fn resolve_overlaps(
+    mut hits: Vec<AnnotationHit>,
+    strategy: OverlapStrategy,
+    threshold: f64,
+    target_len: usize,
+) -> Vec<AnnotationHit> {
+    let mut accepted: Vec<AnnotationHit> = match strategy {
+        OverlapStrategy::KeepAll => hits,
+        OverlapStrategy::KeepBestScore | OverlapStrategy::KeepLongest => {
+            if strategy == OverlapStrategy::KeepLongest {
+                hits.sort_by_key(|h| {
+                    let len = span_len(&h.ranges(target_len));
+                    (std::cmp::Reverse(len), std::cmp::Reverse(h.alignment_score))
+                });
+            } else {
+                hits.sort_by_key(|h| std::cmp::Reverse(h.alignment_score));
+            }
+
+            let mut accepted: Vec<AnnotationHit> = Vec::new();
+            for hit in hits {
+                let dominated = accepted
+                    .iter()
+                    .any(|existing| overlap_fraction(&hit, existing, target_len) > threshold);
+                if !dominated {
+                    accepted.push(hit);
+                }
+            }
+            accepted
         }
-    }
+    };
 
     // Sort final result by position
     accepted.sort_by_key(|h| h.target_start);
@@ -153,42 +532,142 @@ fn resolve_overlaps(hits: Vec<AnnotationHit>) -> Vec<AnnotationHit> {
 }
 
 /// Calculate the fraction of `a` that overlaps with `b`.
-fn overlap_fraction(a: &AnnotationHit, b: &AnnotationHit) -> f64 {
-    let start = a.target_start.max(b.target_start);
-    let end = a.target_end.min(b.target_end);
-
-    if start >= end {
-        return 0.0;
-    }
-
-    let overlap_len = end - start;
-    let a_len = a.target_end - a.target_start;
+fn overlap_fraction(a: &AnnotationHit, b: &AnnotationHit, target_len: usize) -> f64 {
+    let a_ranges = a.ranges(target_len);
+    let b_ranges = b.ranges(target_len);
 
+    let a_len = span_len(&a_ranges);
     if a_len == 0 {
         return 0.0;
     }
 
+    let overlap_len: usize = a_ranges
+        .iter()
+        .flat_map(|&(a_start, a_end)| {
+            b_ranges.iter().map(move |&(b_start, b_end)| {
+                a_end.min(b_end).saturating_sub(a_start.max(b_start))
+            })
+        })
+        .sum();
+
     overlap_len as f64 / a_len as f64
 }
 
+fn span_len(ranges: &[(usize, usize)]) -> usize {
+    ranges.iter().map(|(start, end)| end - start).sum()
+}
+
 /// Convert annotation hits to helix-core Features for integration into a Sequence.
-pub fn hits_to_features(
-    hits: &[AnnotationHit],
-) -> Vec<(String, String, usize, usize, bool, Option<String>)> {
+///
+/// `target_len` is the length of the annotated sequence; a hit that wraps its
+/// origin becomes a join of the span up to the end and the span from the start.
+pub fn hits_to_features(hits: &[AnnotationHit], target_len: usize) -> Vec<Feature> {
     hits.iter()
         .map(|h| {
-            (
+            let strand = if h.is_reverse_complement {
+                Strand::Reverse
+            } else {
+                Strand::Forward
+            };
+            let mut feature = Feature::new(
                 h.component_name.clone(),
-                h.category.clone(),
+                category_feature_type(&h.category),
                 h.target_start,
                 h.target_end,
-                h.is_reverse_complement,
-                h.color.clone(),
-            )
+                strand,
+            );
+            if h.wraps_origin() {
+                feature.location = Location::join(h.ranges(target_len));
+            }
+            feature.color = h.color.clone();
+            feature
         })
         .collect()
 }
 
+/// Feature type used for hits of a component category.
+pub fn category_feature_type(category: &str) -> FeatureType {
+    match category {
+        "resistance" => FeatureType::Resistance,
+        "tag" => FeatureType::Tag,
+        "recombination" | "misc" => FeatureType::Misc,
+        "signal_peptide" => FeatureType::Signal,
+        other => FeatureType::from_genbank_key(other),
+    }
+}
+
+/// How auto-annotation hits interact with features already on a sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Drop a hit that overlaps any existing feature (curated work wins).
+    #[default]
+    SkipIfOverlapping,
+    /// Remove existing features that a hit overlaps, then add the hit.
+    Replace,
+    /// Add every hit alongside the existing features.
+    KeepBoth,
+}
+
+/// Add hits to `seq` as features, resolving overlaps with the features it
+/// already has according to `policy`. Returns the number of features added.
+pub fn apply_hits(seq: &mut Sequence, hits: &[AnnotationHit], policy: ConflictPolicy) -> usize {
+    let mut added = 0;
+    for feature in hits_to_features(hits, seq.len()) {
+        let spans = feature.location.ranges();
+        let overlaps = |existing: &Feature| {
+            existing
+                .location
+                .ranges()
+                .iter()
+                .any(|&(s, e)| spans.iter().any(|&(start, end)| s < end && start < e))
+        };
+
+        match policy {
+            ConflictPolicy::SkipIfOverlapping => {
+                if seq.features().iter().any(overlaps) {
+                    continue;
+                }
+            }
+            ConflictPolicy::Replace => seq.features_mut().retain(|f| !overlaps(f)),
+            ConflictPolicy::KeepBoth => {}
+        }
+        seq.add_feature(feature);
+        added += 1;
+    }
+    added
+}
+
+/// Annotate `seq` against `components` and add the resulting hits as
+/// features using `policy`. Returns the number of features added.
+pub fn annotate_and_apply(
+    seq: &mut Sequence,
+    components: &[Component],
+    config: &AnnotationConfig,
+    policy: ConflictPolicy,
+) -> usize {
+    let hits = annotate(seq.sequence(), seq.is_circular(), components, config);
+    apply_hits(seq, &hits, policy)
+}
+
+/// Forward-strand, full-identity hit over `start..end`, shared by the
+/// crate's tests.
+#[cfg(test)]
+pub(crate) fn hit_at(name: &str, start: usize, end: usize, score: i32) -> AnnotationHit {
+    AnnotationHit {
+        component_name: name.to_string(),
+        component_id: 1,
+        category: "cds".to_string(),
+        target_start: start,
+        target_end: end,
+        is_reverse_complement: false,
+        percent_identity: 100.0,
+        query_coverage: 100.0,
+        alignment_score: score,
+        color: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,6 +749,225 @@ mod tests {
         assert!(hits.is_empty(), "Protein components should be skipped");
     }
 
+    #[test]
+    fn test_annotate_protein_component_across_codon_changes() {
+        use helix_core::codon_usage::{codon_optimize, CodonUsageTable};
+        use helix_core::operations::reverse_complement;
+
+        // The component is the protein; the target carries it with yeast
+        // codons, so the DNA differs from an E. coli-optimized copy
+        let protein = "MSKGEELFTGVVPILVELDGDVNGHKFSVSG";
+        let cds = codon_optimize(protein, &CodonUsageTable::s_cerevisiae());
+        assert_ne!(cds, codon_optimize(protein, &CodonUsageTable::e_coli()));
+        let flank = "CGCGCGCGCGCGCGC";
+        let components = vec![make_component("GFP fragment", "cds", protein)];
+        let config = AnnotationConfig {
+            mode: AnnotationMode::Both,
+            ..Default::default()
+        };
+
+        let target = format!("{}A{}{}", flank, cds, flank);
+        let hits = annotate(&target, false, &components, &config);
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].target_start, hits[0].target_end), (16, 16 + cds.len()));
+        assert!(!hits[0].is_reverse_complement);
+        assert_eq!(hits[0].percent_identity, 100.0);
+
+        let target = format!("{}{}AA{}", flank, reverse_complement(&cds), flank);
+        let hits = annotate(&target, false, &components, &config);
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].target_start, hits[0].target_end), (15, 15 + cds.len()));
+        assert!(hits[0].is_reverse_complement);
+
+        // DNA-only mode still skips protein components
+        assert!(annotate(&target, false, &components, &AnnotationConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_annotate_finds_repeated_component() {
+        let terminator = "CTAGCATAACCCCTTGGGGCCTCTAAACGGGTCTTGAGGGG";
+        let spacer = "ATGAGTAAAGGAGAAGAACTTTTCACTGGAGTTGTCCCAATTCTTGTTGAATTAGATGGT";
+        let target = format!("{}{}{}{}{}", spacer, terminator, spacer, terminator, spacer);
+        let components = vec![make_component("T1", "terminator", terminator)];
+        let config = AnnotationConfig {
+            band_width: None,
+            ..Default::default()
+        };
+
+        let hits = annotate(&target, false, &components, &config);
+        let spans: Vec<(usize, usize)> =
+            hits.iter().map(|h| (h.target_start, h.target_end)).collect();
+        let second = 2 * spacer.len() + terminator.len();
+        assert_eq!(
+            spans,
+            vec![
+                (spacer.len(), spacer.len() + terminator.len()),
+                (second, second + terminator.len()),
+            ]
+        );
+        assert!(hits.iter().all(|h| h.percent_identity == 100.0));
+    }
+
+    #[test]
+    fn test_annotate_debug_reports_failures() {
+        let passing = "ACGTACGTACGTACGTACGT";
+        let near = "GGCATCGATTGCAAGCTTAGCCATGGAC";
+        let mismatched = "GGCATCGATTGCAAGGTTAGCCATGGAC";
+        let target = format!("TTTTTTTTTT{}TTTTTTTTTT{}TTTTTTTTTT", passing, near);
+
+        let components = vec![
+            make_component("Pass", "cds", passing),
+            make_component("OneMismatch", "cds", mismatched),
+            make_component("Protein", "cds", "MFCTFFEKHHRKWDIL"),
+        ];
+        let config = AnnotationConfig {
+            min_identity: 99.0,
+            min_coverage: 90.0,
+            ..Default::default()
+        };
+
+        let scores = annotate_debug(&target, false, &components, &config);
+        assert_eq!(scores.len(), 3);
+
+        assert!(scores[0].passed);
+        assert_eq!(scores[0].reason, "pass");
+
+        assert!(!scores[1].passed);
+        assert_eq!(scores[1].reason, "below identity");
+        assert!(scores[1].percent_identity < 99.0);
+        assert!(scores[1].percent_identity > 90.0);
+        assert!(scores[1].best_score.is_some());
+
+        assert_eq!(scores[2].reason, "excluded by mode");
+        assert!(scores[2].best_score.is_none());
+    }
+
+    #[test]
+    fn test_annotate_debug_across_origin() {
+        let component_seq = "ATGGCTAGCAAAGGAGAAGAACTTTTCACTGG";
+        let backbone = "GATCCTTGACAGCTAGCTCAGTCCTAGGTATAATGCTAGCG";
+        let target = format!("{}{}{}", &component_seq[12..], backbone, &component_seq[..12]);
+        let components = vec![make_component("GFP start", "cds", component_seq)];
+        let config = AnnotationConfig {
+            min_identity: 95.0,
+            min_coverage: 95.0,
+            band_width: None,
+            ..Default::default()
+        };
+
+        // The dry run agrees with annotate on either topology
+        let linear = annotate_debug(&target, false, &components, &config);
+        assert!(!linear[0].passed);
+        assert!(annotate(&target, false, &components, &config).is_empty());
+
+        let circular = annotate_debug(&target, true, &components, &config);
+        assert!(circular[0].passed, "{}", circular[0].reason);
+        assert_eq!(circular[0].query_coverage, 100.0);
+        assert_eq!(annotate(&target, true, &components, &config).len(), 1);
+    }
+
+    fn curated_sequence() -> Sequence {
+        use helix_core::sequence::Topology;
+        let mut seq = Sequence::new("curated", "A".repeat(1000), Topology::Linear);
+        seq.add_feature(Feature::new("Manual", FeatureType::Cds, 100, 300, Strand::Forward));
+        seq
+    }
+
+    #[test]
+    fn test_apply_hits_conflict_policies() {
+        let hits = vec![hit_at("Overlap", 250, 400, 100), hit_at("Clear", 600, 700, 100)];
+        let names = |seq: &Sequence| {
+            let mut n: Vec<String> = seq.features().iter().map(|f| f.name.clone()).collect();
+            n.sort();
+            n
+        };
+
+        let mut seq = curated_sequence();
+        assert_eq!(apply_hits(&mut seq, &hits, ConflictPolicy::default()), 1);
+        assert_eq!(names(&seq), vec!["Clear", "Manual"]);
+
+        let mut seq = curated_sequence();
+        assert_eq!(apply_hits(&mut seq, &hits, ConflictPolicy::Replace), 2);
+        assert_eq!(names(&seq), vec!["Clear", "Overlap"]);
+
+        let mut seq = curated_sequence();
+        assert_eq!(apply_hits(&mut seq, &hits, ConflictPolicy::KeepBoth), 2);
+        assert_eq!(names(&seq), vec!["Clear", "Manual", "Overlap"]);
+    }
+
+    /// Trivial scorer that only reports exact forward-strand matches.
+    struct ExactMatchScorer;
+
+    impl AnnotationScorer for ExactMatchScorer {
+        fn score(&self, component: &Component, target: &str) -> Option<AnnotationHit> {
+            let start = target.find(&component.sequence)?;
+            Some(AnnotationHit {
+                component_name: component.name.clone(),
+                component_id: component.id,
+                category: component.category.clone(),
+                target_start: start,
+                target_end: start + component.sequence.len(),
+                is_reverse_complement: false,
+                percent_identity: 100.0,
+                query_coverage: 100.0,
+                alignment_score: component.sequence.len() as i32,
+                color: component.color.clone(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_annotate_with_custom_scorer() {
+        let component_seq = "GATTACAGATTACA";
+        let target = format!("CCCCCCCC{}CCCCCCCC", component_seq);
+        let components = vec![
+            make_component("Embedded", "misc", component_seq),
+            make_component("Absent", "misc", "TTTTTTTTTTTT"),
+        ];
+
+        let hits = annotate_with_scorer(
+            &target,
+            false,
+            &components,
+            &AnnotationConfig::default(),
+            &ExactMatchScorer,
+        );
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].component_name, "Embedded");
+        assert_eq!(hits[0].target_start, 8);
+        assert_eq!(hits[0].target_end, 22);
+    }
+
+    #[test]
+    fn test_protein_only_mode_skips_dna() {
+        let component_seq = "GATTACAGATTACA";
+        let target = format!("CCCCCCCC{}CCCCCCCC", component_seq);
+        let components = vec![make_component("Embedded", "misc", component_seq)];
+
+        let config = AnnotationConfig {
+            mode: AnnotationMode::ProteinOnly,
+            ..Default::default()
+        };
+        let hits = annotate_with_scorer(&target, false, &components, &config, &ExactMatchScorer);
+        assert!(hits.is_empty(), "DNA components should be skipped in protein-only mode");
+
+        let config = AnnotationConfig {
+            mode: AnnotationMode::Both,
+            ..Default::default()
+        };
+        let hits = annotate_with_scorer(&target, false, &components, &config, &ExactMatchScorer);
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[test]
+    fn test_annotation_mode_includes() {
+        assert!(AnnotationMode::DnaOnly.includes("ACGT"));
+        assert!(!AnnotationMode::DnaOnly.includes("MKV"));
+        assert!(AnnotationMode::ProteinOnly.includes("MKV"));
+        assert!(!AnnotationMode::ProteinOnly.includes("ACGT"));
+        assert!(AnnotationMode::Both.includes("ACGT"));
+    }
+
     #[test]
     fn test_is_dna_sequence() {
         assert!(is_dna_sequence("ACGTACGT"));
@@ -306,7 +1004,8 @@ mod tests {
             color: None,
         };
 
-        let resolved = resolve_overlaps(vec![hit_a, hit_b]);
+        let resolved =
+            resolve_overlaps(vec![hit_a, hit_b], OverlapStrategy::KeepBestScore, 0.5, 1000);
         assert_eq!(resolved.len(), 1);
         assert_eq!(resolved[0].component_name, "PartA");
     }
@@ -338,7 +1037,99 @@ mod tests {
             color: None,
         };
 
-        let resolved = resolve_overlaps(vec![hit_b, hit_a]);
+        let resolved =
+            resolve_overlaps(vec![hit_b, hit_a], OverlapStrategy::KeepBestScore, 0.5, 1000);
         assert_eq!(resolved.len(), 2);
     }
+
+    #[test]
+    fn test_nested_tag_kept_only_under_keep_all() {
+        let cds = || hit_at("CDS", 100, 700, 900);
+        let tag = || hit_at("His tag", 100, 118, 36);
+
+        let config = AnnotationConfig::default();
+        let resolved = resolve_overlaps(
+            vec![tag(), cds()],
+            config.overlap_strategy,
+            config.overlap_threshold,
+            1000,
+        );
+        let names: Vec<&str> = resolved.iter().map(|h| h.component_name.as_str()).collect();
+        assert_eq!(names, vec!["CDS"]);
+
+        let resolved = resolve_overlaps(vec![tag(), cds()], OverlapStrategy::KeepAll, 0.5, 1000);
+        assert_eq!(resolved.len(), 2);
+    }
+
+    #[test]
+    fn test_keep_longest_prefers_wider_hit() {
+        // The 15 bp hit scores higher but lies inside the 40 bp one
+        let hits = vec![hit_at("short", 20, 35, 30), hit_at("long", 10, 50, 25)];
+        let longest = resolve_overlaps(hits, OverlapStrategy::KeepLongest, 0.5, 1000);
+        assert_eq!(longest.len(), 1);
+        assert_eq!(longest[0].component_name, "long");
+        assert_eq!(longest[0].target_end - longest[0].target_start, 40);
+    }
+
+    #[test]
+    fn test_annotate_across_origin_of_circular_target() {
+        let component_seq = "ATGGCTAGCAAAGGAGAAGAACTTTTCACTGG";
+        let backbone = "GATCCTTGACAGCTAGCTCAGTCCTAGGTATAATGCTAGCG".repeat(3);
+        // The last 12 bases of the component open the sequence; the rest ends it
+        let target = format!("{}{}{}", &component_seq[12..], backbone, &component_seq[..12]);
+        let components = vec![make_component("GFP start", "cds", component_seq)];
+        let config = AnnotationConfig {
+            min_identity: 95.0,
+            min_coverage: 95.0,
+            band_width: None,
+            ..Default::default()
+        };
+
+        // Split in two, it is not found on a linear sequence
+        assert!(annotate(&target, false, &components, &config).is_empty());
+
+        let hits = annotate(&target, true, &components, &config);
+        assert_eq!(hits.len(), 1);
+        let hit = &hits[0];
+        assert!(hit.wraps_origin());
+        assert_eq!((hit.target_start, hit.target_end), (target.len() - 12, 20));
+        assert_eq!(hit.ranges(target.len()), vec![(target.len() - 12, target.len()), (0, 20)]);
+
+        let features = hits_to_features(&hits, target.len());
+        assert_eq!(features[0].location, Location::join(hit.ranges(target.len())));
+        assert_eq!(features[0].location.len(), component_seq.len());
+
+        // The reverse complement wraps the same way
+        let rc_target = helix_core::operations::reverse_complement(&target);
+        let hits = annotate(&rc_target, true, &components, &config);
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].is_reverse_complement);
+        assert_eq!((hits[0].target_start, hits[0].target_end), (target.len() - 20, 12));
+    }
+
+    #[test]
+    fn test_circular_target_does_not_duplicate_hits() {
+        let component_seq = "ACGTACGTACGTACGTACGT";
+        let target = format!("{}TTTTTTTTTTTTTTTTTTTT", component_seq);
+        let components = vec![make_component("TestPart", "cds", component_seq)];
+
+        let hits = annotate(&target, true, &components, &AnnotationConfig::default());
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].target_start, hits[0].target_end), (0, 20));
+        assert!(!hits[0].wraps_origin());
+    }
+
+    #[test]
+    fn test_overlap_with_wrapped_hit() {
+        // 95..5 wraps a 100 bp target and overlaps 0..10 by 5 bases
+        let wrapped = hit_at("wrapped", 95, 5, 50);
+        let start = hit_at("start", 0, 10, 40);
+        assert_eq!(overlap_fraction(&start, &wrapped, 100), 0.5);
+        assert_eq!(overlap_fraction(&wrapped, &start, 100), 0.5);
+
+        let resolved =
+            resolve_overlaps(vec![wrapped, start], OverlapStrategy::KeepBestScore, 0.4, 100);
+        let names: Vec<&str> = resolved.iter().map(|h| h.component_name.as_str()).collect();
+        assert_eq!(names, vec!["wrapped"]);
+    }
 }
@@ -0,0 +1,165 @@
+//! Guess the vector backbone from the components found by annotation.
+
+use serde::{Deserialize, Serialize};
+
+use crate::annotate::AnnotationHit;
+
+/// A known backbone family and the components that identify it, by name
+/// in the component database.
+struct BackboneRule {
+    name: &'static str,
+    /// All of these must be present for the rule to match.
+    required: &'static [&'static str],
+    /// Each of these that is present raises the confidence.
+    optional: &'static [&'static str],
+}
+
+/// When several rules match, the one with the most required components wins,
+/// then the most confident, then the earliest listed.
+const BACKBONE_RULES: &[BackboneRule] = &[
+    BackboneRule {
+        name: "pET",
+        required: &["ColE1 origin", "KanR", "T7 promoter"],
+        optional: &["lacI", "Lac operator", "T7 terminator"],
+    },
+    BackboneRule {
+        name: "pcDNA",
+        required: &["ColE1 origin", "AmpR", "CMV promoter"],
+        optional: &["SV40 early promoter", "NeoR/KanR"],
+    },
+    BackboneRule {
+        name: "pBR322",
+        required: &["ColE1 origin", "AmpR", "rop"],
+        optional: &["Tet(C)", "AmpR promoter"],
+    },
+    BackboneRule {
+        name: "pUC",
+        required: &["ColE1 origin", "AmpR"],
+        optional: &["AmpR promoter", "lac promoter", "LacZ-alpha", "Lac operator"],
+    },
+    BackboneRule {
+        name: "pCDF",
+        required: &["CloDF13 ori"],
+        optional: &["T7 promoter", "lacI"],
+    },
+    BackboneRule {
+        name: "pRSF",
+        required: &["RSF ori"],
+        optional: &["KanR", "T7 promoter", "lacI"],
+    },
+    BackboneRule {
+        name: "pSC101",
+        required: &["pSC101 origin"],
+        optional: &["Rep101"],
+    },
+];
+
+/// A backbone family suggested by the annotated components.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackboneGuess {
+    /// Backbone family, e.g. "pUC".
+    pub name: String,
+    /// Fraction (0.0 to 1.0) of the family's marker components that were found.
+    pub confidence: f64,
+    /// Marker components found among the hits.
+    pub matched_components: Vec<String>,
+}
+
+/// Match the components in `hits` against a small table of known backbones.
+///
+/// A backbone matches when all its required components were found (names
+/// compared case-insensitively); confidence is the fraction of its required
+/// and optional components present. Returns the most specific match (the one
+/// requiring the most components), or `None` if no backbone's required
+/// components are all present.
+pub fn identify_backbone(hits: &[AnnotationHit]) -> Option<BackboneGuess> {
+    let found = |name: &str| {
+        hits.iter()
+            .any(|hit| hit.component_name.eq_ignore_ascii_case(name))
+    };
+
+    let mut best: Option<(usize, BackboneGuess)> = None;
+    for rule in BACKBONE_RULES {
+        if !rule.required.iter().all(|name| found(name)) {
+            continue;
+        }
+        let matched_components: Vec<String> = rule
+            .required
+            .iter()
+            .chain(rule.optional)
+            .filter(|name| found(name))
+            .map(|name| name.to_string())
+            .collect();
+        let confidence =
+            matched_components.len() as f64 / (rule.required.len() + rule.optional.len()) as f64;
+        let specificity = rule.required.len();
+        let better = best.as_ref().is_none_or(|(best_specificity, guess)| {
+            (specificity, confidence) > (*best_specificity, guess.confidence)
+        });
+        if better {
+            let guess = BackboneGuess {
+                name: rule.name.to_string(),
+                confidence,
+                matched_components,
+            };
+            best = Some((specificity, guess));
+        }
+    }
+    best.map(|(_, guess)| guess)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotate::hit_at;
+
+    fn hit(name: &str) -> AnnotationHit {
+        hit_at(name, 0, 100, 100)
+    }
+
+    #[test]
+    fn test_puc_from_ori_and_ampr() {
+        let guess = identify_backbone(&[hit("ColE1 origin"), hit("AmpR")]).unwrap();
+        assert_eq!(guess.name, "pUC");
+        assert!(guess.confidence > 0.0 && guess.confidence < 1.0);
+
+        let more = identify_backbone(&[
+            hit("ColE1 origin"),
+            hit("AmpR"),
+            hit("lac promoter"),
+            hit("LacZ-alpha"),
+        ])
+        .unwrap();
+        assert_eq!(more.name, "pUC");
+        assert!(more.confidence > guess.confidence);
+        assert_eq!(more.matched_components.len(), 4);
+    }
+
+    #[test]
+    fn test_more_specific_backbone_wins() {
+        let hits = [
+            hit("colE1 origin"),
+            hit("KanR"),
+            hit("T7 promoter"),
+            hit("lacI"),
+            hit("T7 terminator"),
+        ];
+        assert_eq!(identify_backbone(&hits).unwrap().name, "pET");
+
+        // pcDNA needs the CMV promoter on top of the pUC markers
+        let hits = [
+            hit("ColE1 origin"),
+            hit("AmpR"),
+            hit("AmpR promoter"),
+            hit("lac promoter"),
+            hit("CMV promoter"),
+        ];
+        assert_eq!(identify_backbone(&hits).unwrap().name, "pcDNA");
+    }
+
+    #[test]
+    fn test_no_backbone() {
+        assert!(identify_backbone(&[]).is_none());
+        assert!(identify_backbone(&[hit("AmpR"), hit("EGFP")]).is_none());
+    }
+}
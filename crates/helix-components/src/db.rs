@@ -1,7 +1,50 @@
 use rusqlite::{params, Connection, Result as SqlResult};
+use serde::{Deserialize, Serialize};
 
 use crate::component::Component;
-use crate::seed_data::builtin_components;
+use crate::seed_data::{builtin_components_for, SeedSet};
+
+/// Kind of change recorded in the component audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Add,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Add => "add",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "add" => Some(AuditAction::Add),
+            "update" => Some(AuditAction::Update),
+            "delete" => Some(AuditAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// One entry in the append-only component audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Monotonically increasing entry ID (insertion order).
+    pub id: i64,
+    pub action: AuditAction,
+    /// ID of the component that was changed.
+    pub component_id: i64,
+    /// UTC timestamp as written by SQLite (`YYYY-MM-DD HH:MM:SS`).
+    pub timestamp: String,
+    /// Who made the change, if known.
+    pub author: Option<String>,
+}
 
 /// Create the components table if it does not exist.
 pub fn init_db(conn: &Connection) -> SqlResult<()> {
@@ -21,14 +64,27 @@ pub fn init_db(conn: &Connection) -> SqlResult<()> {
             UNIQUE(name, sequence)
         );
         CREATE INDEX IF NOT EXISTS idx_components_category ON components(category);
-        CREATE INDEX IF NOT EXISTS idx_components_length ON components(length);",
+        CREATE INDEX IF NOT EXISTS idx_components_length ON components(length);
+        CREATE TABLE IF NOT EXISTS component_audit (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            action       TEXT NOT NULL,
+            component_id INTEGER NOT NULL,
+            timestamp    TEXT NOT NULL DEFAULT (datetime('now')),
+            author       TEXT
+        );",
     )
 }
 
-/// Seed built-in components (idempotent via INSERT OR IGNORE).
+/// Seed all built-in components (idempotent via INSERT OR IGNORE).
 /// Returns the number of newly inserted rows.
 pub fn seed_builtins(conn: &Connection) -> SqlResult<usize> {
-    let components = builtin_components();
+    seed_builtins_set(conn, SeedSet::Full)
+}
+
+/// Seed the built-in components in `set` (idempotent via INSERT OR IGNORE).
+/// Returns the number of newly inserted rows.
+pub fn seed_builtins_set(conn: &Connection, set: SeedSet) -> SqlResult<usize> {
+    let components = builtin_components_for(set);
     let mut count = 0usize;
     for c in &components {
         let changed = conn.execute(
@@ -96,8 +152,14 @@ pub fn get_component(conn: &Connection, id: i64) -> SqlResult<Option<Component>>
 }
 
 /// Insert a user-defined component. Returns the new row ID.
-pub fn add_user_component(conn: &Connection, component: &Component) -> SqlResult<i64> {
-    conn.execute(
+/// The insert is recorded in the audit log under `author`.
+pub fn add_user_component(
+    conn: &Connection,
+    component: &Component,
+    author: Option<&str>,
+) -> SqlResult<i64> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
         "INSERT INTO components
             (name, category, sequence, length, description, organism, is_builtin, accession, color)
          VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?8)",
@@ -112,17 +174,126 @@ pub fn add_user_component(conn: &Connection, component: &Component) -> SqlResult
             component.color,
         ],
     )?;
-    Ok(conn.last_insert_rowid())
+    let id = tx.last_insert_rowid();
+    record_audit(&tx, AuditAction::Add, id, author)?;
+    tx.commit()?;
+    Ok(id)
+}
+
+/// Replace the fields of a user-defined component. Built-ins cannot be updated.
+/// Returns true if a row was updated (and an audit entry recorded).
+pub fn update_user_component(
+    conn: &Connection,
+    id: i64,
+    component: &Component,
+    author: Option<&str>,
+) -> SqlResult<bool> {
+    let tx = conn.unchecked_transaction()?;
+    let changed = tx.execute(
+        "UPDATE components
+         SET name = ?2, category = ?3, sequence = ?4, length = ?5, description = ?6,
+             organism = ?7, accession = ?8, color = ?9
+         WHERE id = ?1 AND is_builtin = 0",
+        params![
+            id,
+            component.name,
+            component.category,
+            component.sequence,
+            component.length,
+            component.description,
+            component.organism,
+            component.accession,
+            component.color,
+        ],
+    )?;
+    if changed > 0 {
+        record_audit(&tx, AuditAction::Update, id, author)?;
+    }
+    tx.commit()?;
+    Ok(changed > 0)
 }
 
 /// Delete a user-defined component. Built-ins cannot be deleted.
-/// Returns true if a row was deleted.
-pub fn delete_user_component(conn: &Connection, id: i64) -> SqlResult<bool> {
+/// Returns true if a row was deleted (and an audit entry recorded).
+pub fn delete_user_component(conn: &Connection, id: i64, author: Option<&str>) -> SqlResult<bool> {
+    let tx = conn.unchecked_transaction()?;
     let changed =
-        conn.execute("DELETE FROM components WHERE id = ?1 AND is_builtin = 0", params![id])?;
+        tx.execute("DELETE FROM components WHERE id = ?1 AND is_builtin = 0", params![id])?;
+    if changed > 0 {
+        record_audit(&tx, AuditAction::Delete, id, author)?;
+    }
+    tx.commit()?;
     Ok(changed > 0)
 }
 
+/// Delete every user-defined component in `category`. Built-ins in the same
+/// category are kept. Each deletion is recorded in the audit log under `author`.
+/// Returns the number of components deleted.
+pub fn delete_user_components_by_category(
+    conn: &Connection,
+    category: &str,
+    author: Option<&str>,
+) -> SqlResult<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let ids: Vec<i64> = {
+        let mut stmt =
+            tx.prepare("SELECT id FROM components WHERE category = ?1 AND is_builtin = 0")?;
+        let rows = stmt.query_map(params![category], |row| row.get(0))?;
+        rows.collect::<SqlResult<_>>()?
+    };
+    tx.execute(
+        "DELETE FROM components WHERE category = ?1 AND is_builtin = 0",
+        params![category],
+    )?;
+    for &id in &ids {
+        record_audit(&tx, AuditAction::Delete, id, author)?;
+    }
+    tx.commit()?;
+    Ok(ids.len())
+}
+
+/// Retrieve the full audit log, oldest entry first.
+pub fn get_audit_log(conn: &Connection) -> SqlResult<Vec<AuditEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, action, component_id, timestamp, author
+         FROM component_audit ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        let action: String = row.get(1)?;
+        Ok(AuditEntry {
+            id: row.get(0)?,
+            action: AuditAction::from_str(&action).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    1,
+                    rusqlite::types::Type::Text,
+                    format!("unknown audit action '{}'", action).into(),
+                )
+            })?,
+            component_id: row.get(2)?,
+            timestamp: row.get(3)?,
+            author: row.get(4)?,
+        })
+    })?;
+    let mut entries = Vec::new();
+    for row in rows {
+        entries.push(row?);
+    }
+    Ok(entries)
+}
+
+fn record_audit(
+    conn: &Connection,
+    action: AuditAction,
+    component_id: i64,
+    author: Option<&str>,
+) -> SqlResult<()> {
+    conn.execute(
+        "INSERT INTO component_audit (action, component_id, author) VALUES (?1, ?2, ?3)",
+        params![action.as_str(), component_id, author],
+    )?;
+    Ok(())
+}
+
 /// Search components by name (case-insensitive LIKE).
 pub fn search_components(conn: &Connection, query: &str) -> SqlResult<Vec<Component>> {
     let pattern = format!("%{}%", query);
@@ -139,6 +310,62 @@ pub fn search_components(conn: &Connection, query: &str) -> SqlResult<Vec<Compon
     Ok(results)
 }
 
+/// Differences between two component databases, from [`diff_components`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ComponentDiff {
+    /// Components only in the second database.
+    pub added: Vec<Component>,
+    /// Components only in the first database.
+    pub removed: Vec<Component>,
+    /// Components in both whose other fields differ, as (first, second).
+    pub modified: Vec<(Component, Component)>,
+}
+
+impl ComponentDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Compare the components of two databases, e.g. two releases of a part
+/// library.
+///
+/// Components are matched by name and sequence, so a changed sequence shows
+/// up as one removal and one addition. A matched pair counts as modified when
+/// its category, description, organism, accession, color or built-in flag
+/// differ; database IDs are ignored. Each list is sorted by name.
+pub fn diff_components(conn_a: &Connection, conn_b: &Connection) -> SqlResult<ComponentDiff> {
+    let key = |c: &Component| (c.name.clone(), c.sequence.clone());
+    let mut remaining: std::collections::HashMap<(String, String), Component> =
+        get_components(conn_a, None)?.into_iter().map(|c| (key(&c), c)).collect();
+
+    let mut diff = ComponentDiff::default();
+    for new in get_components(conn_b, None)? {
+        match remaining.remove(&key(&new)) {
+            None => diff.added.push(new),
+            Some(old) => {
+                let same = old.category == new.category
+                    && old.description == new.description
+                    && old.organism == new.organism
+                    && old.accession == new.accession
+                    && old.color == new.color
+                    && old.is_builtin == new.is_builtin;
+                if !same {
+                    diff.modified.push((old, new));
+                }
+            }
+        }
+    }
+    diff.removed = remaining.into_values().collect();
+
+    let order =
+        |a: &Component, b: &Component| (&a.name, &a.sequence).cmp(&(&b.name, &b.sequence));
+    diff.added.sort_by(order);
+    diff.removed.sort_by(order);
+    diff.modified.sort_by(|(a, _), (b, _)| order(a, b));
+    Ok(diff)
+}
+
 fn row_to_component(row: &rusqlite::Row) -> SqlResult<Component> {
     Ok(Component {
         id: row.get(0)?,
@@ -175,6 +402,22 @@ mod tests {
         assert_eq!(count2, 0, "Second seed should insert nothing");
     }
 
+    #[test]
+    fn test_seed_sets() {
+        let full_conn = test_db();
+        let full = seed_builtins_set(&full_conn, SeedSet::Full).unwrap();
+        assert_eq!(seed_builtins_set(&full_conn, SeedSet::Full).unwrap(), 0);
+
+        let minimal_conn = test_db();
+        let minimal = seed_builtins_set(&minimal_conn, SeedSet::Minimal).unwrap();
+        assert_eq!(seed_builtins_set(&minimal_conn, SeedSet::Minimal).unwrap(), 0);
+        assert!(minimal > 0 && minimal < full, "{} vs {}", minimal, full);
+
+        let empty_conn = test_db();
+        assert_eq!(seed_builtins_set(&empty_conn, SeedSet::None).unwrap(), 0);
+        assert!(get_components(&empty_conn, None).unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_components() {
         let conn = test_db();
@@ -190,24 +433,93 @@ mod tests {
     fn test_user_component_crud() {
         let conn = test_db();
         let comp = Component::new_builtin("MyPart", "cds", "ATGATGATG", Some("Test"), None, None, None);
-        let id = add_user_component(&conn, &comp).unwrap();
+        let id = add_user_component(&conn, &comp, None).unwrap();
         assert!(id > 0);
 
         let fetched = get_component(&conn, id).unwrap().unwrap();
         assert_eq!(fetched.name, "MyPart");
         assert!(!fetched.is_builtin);
 
-        assert!(delete_user_component(&conn, id).unwrap());
+        assert!(delete_user_component(&conn, id, None).unwrap());
         assert!(get_component(&conn, id).unwrap().is_none());
     }
 
+    #[test]
+    fn test_audit_log_records_add_and_delete() {
+        let conn = test_db();
+        let comp = Component::new_builtin("MyPart", "cds", "ATGATGATG", None, None, None, None);
+        let id = add_user_component(&conn, &comp, Some("alice")).unwrap();
+        assert!(delete_user_component(&conn, id, None).unwrap());
+
+        let log = get_audit_log(&conn).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].action, AuditAction::Add);
+        assert_eq!(log[0].component_id, id);
+        assert_eq!(log[0].author.as_deref(), Some("alice"));
+        assert_eq!(log[1].action, AuditAction::Delete);
+        assert_eq!(log[1].component_id, id);
+        assert!(log[1].author.is_none());
+        assert!(log[0].id < log[1].id);
+    }
+
+    #[test]
+    fn test_failed_audit_rolls_back_write() {
+        let conn = test_db();
+        let comp = Component::new_builtin("MyPart", "cds", "ATGATGATG", None, None, None, None);
+        let before = get_components(&conn, None).unwrap().len();
+        conn.execute("DROP TABLE component_audit", []).unwrap();
+
+        assert!(add_user_component(&conn, &comp, None).is_err());
+        assert_eq!(get_components(&conn, None).unwrap().len(), before);
+    }
+
+    #[test]
+    fn test_update_user_component() {
+        let conn = test_db();
+        let comp = Component::new_builtin("MyPart", "cds", "ATGATGATG", None, None, None, None);
+        let id = add_user_component(&conn, &comp, None).unwrap();
+
+        let renamed = Component::new_builtin("Renamed", "cds", "ATGATGATG", None, None, None, None);
+        assert!(update_user_component(&conn, id, &renamed, Some("bob")).unwrap());
+        assert_eq!(get_component(&conn, id).unwrap().unwrap().name, "Renamed");
+
+        let log = get_audit_log(&conn).unwrap();
+        assert_eq!(log.last().unwrap().action, AuditAction::Update);
+    }
+
     #[test]
     fn test_cannot_delete_builtin() {
         let conn = test_db();
         seed_builtins(&conn).unwrap();
         let all = get_components(&conn, None).unwrap();
         let builtin = all.iter().find(|c| c.is_builtin).unwrap();
-        assert!(!delete_user_component(&conn, builtin.id).unwrap());
+        assert!(!delete_user_component(&conn, builtin.id, None).unwrap());
+        assert!(get_audit_log(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_user_components_by_category() {
+        let conn = test_db();
+        seed_builtins(&conn).unwrap();
+        let builtins = get_components(&conn, Some("promoter")).unwrap().len();
+        assert!(builtins > 0);
+
+        for name in ["PartA", "PartB", "PartC"] {
+            let comp = Component::new_builtin(name, "promoter", "ATGATGATG", None, None, None, None);
+            add_user_component(&conn, &comp, None).unwrap();
+        }
+        let other = Component::new_builtin("PartD", "cds", "ATGATGATG", None, None, None, None);
+        add_user_component(&conn, &other, None).unwrap();
+
+        assert_eq!(delete_user_components_by_category(&conn, "promoter", Some("alice")).unwrap(), 3);
+        let remaining = get_components(&conn, Some("promoter")).unwrap();
+        assert_eq!(remaining.len(), builtins);
+        assert!(remaining.iter().all(|c| c.is_builtin));
+        assert_eq!(get_components(&conn, Some("cds")).unwrap().iter().filter(|c| !c.is_builtin).count(), 1);
+
+        let deletes = get_audit_log(&conn).unwrap().into_iter().filter(|e| e.action == AuditAction::Delete).count();
+        assert_eq!(deletes, 3);
+        assert_eq!(delete_user_components_by_category(&conn, "promoter", None).unwrap(), 0);
     }
 
     #[test]
@@ -217,4 +529,65 @@ mod tests {
         let results = search_components(&conn, "Amp").unwrap();
         assert!(results.iter().any(|c| c.name.contains("Amp")));
     }
+
+    #[test]
+    fn test_diff_components() {
+        let (a, b) = (test_db(), test_db());
+        seed_builtins_set(&a, SeedSet::Minimal).unwrap();
+        seed_builtins_set(&b, SeedSet::Minimal).unwrap();
+        assert!(diff_components(&a, &b).unwrap().is_empty());
+
+        let part = Component::new_builtin("MyPart", "cds", "ATGATGATG", None, None, None, None);
+        add_user_component(&b, &part, None).unwrap();
+        let diff = diff_components(&a, &b).unwrap();
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "MyPart");
+        assert!(diff.removed.is_empty() && diff.modified.is_empty());
+
+        // The same change seen the other way round is a removal
+        let diff = diff_components(&b, &a).unwrap();
+        assert_eq!(diff.removed.len(), 1);
+        assert!(diff.added.is_empty());
+
+        add_user_component(&a, &Component { color: Some("#ff0000".into()), ..part }, None)
+            .unwrap();
+        let diff = diff_components(&a, &b).unwrap();
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].0.color.as_deref(), Some("#ff0000"));
+        assert_eq!(diff.modified[0].1.color, None);
+    }
+
+    #[test]
+    fn test_diff_components_sorted_by_name() {
+        let (a, b) = (test_db(), test_db());
+        let part = |name: &str, sequence: &str| {
+            Component::new_builtin(name, "cds", sequence, None, None, None, None)
+        };
+        for name in ["Zeta", "Beta"] {
+            add_user_component(&a, &part(name, "ATGATGATG"), None).unwrap();
+            let recolored = Component {
+                color: Some("#00ff00".into()),
+                ..part(name, "ATGATGATG")
+            };
+            add_user_component(&b, &recolored, None).unwrap();
+        }
+        for (name, sequence) in [("Omega", "ATGCCC"), ("Alpha", "ATGAAA"), ("Alpha", "ATGAAC")] {
+            add_user_component(&a, &part(name, &format!("{}TAA", sequence)), None).unwrap();
+            add_user_component(&b, &part(name, &format!("{}TGA", sequence)), None).unwrap();
+        }
+
+        let diff = diff_components(&a, &b).unwrap();
+        let key = |c: &Component| (c.name.clone(), c.sequence.clone());
+        for list in [&diff.added, &diff.removed] {
+            let keys: Vec<_> = list.iter().map(key).collect();
+            let mut sorted = keys.clone();
+            sorted.sort();
+            assert_eq!(keys, sorted);
+            assert_eq!(keys[0].0, "Alpha");
+            assert_eq!(keys.len(), 3);
+        }
+        let modified: Vec<&str> = diff.modified.iter().map(|(c, _)| c.name.as_str()).collect();
+        assert_eq!(modified, vec!["Beta", "Zeta"]);
+    }
 }
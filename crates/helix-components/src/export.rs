@@ -0,0 +1,45 @@
+//! Export annotation hits to genome-browser feature formats.
+
+use helix_formats::{bed, gff};
+
+use crate::annotate::{hits_to_features, AnnotationHit};
+
+/// Render annotation hits as a GFF3 document on sequence `seqid`, which is
+/// `target_len` bases long.
+pub fn annotation_hits_to_gff3(hits: &[AnnotationHit], seqid: &str, target_len: usize) -> String {
+    gff::serialize_features(&hits_to_features(hits, target_len), seqid)
+}
+
+/// Render annotation hits as BED6 rows on chromosome `chrom`, which is
+/// `target_len` bases long.
+pub fn annotation_hits_to_bed(hits: &[AnnotationHit], chrom: &str, target_len: usize) -> String {
+    bed::serialize_features(&hits_to_features(hits, target_len), chrom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotate::hit_at;
+
+    fn ampr() -> AnnotationHit {
+        AnnotationHit { is_reverse_complement: true, ..hit_at("AmpR", 900, 1761, 100) }
+    }
+
+    #[test]
+    fn test_hits_to_bed() {
+        let hits = vec![hit_at("GFP", 10, 730, 100), ampr()];
+        let bed = annotation_hits_to_bed(&hits, "pTest", 2000);
+        let lines: Vec<&str> = bed.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "pTest\t10\t730\tGFP\t0\t+");
+        assert_eq!(lines[1], "pTest\t900\t1761\tAmpR\t0\t-");
+    }
+
+    #[test]
+    fn test_hits_to_gff3() {
+        let hits = vec![ampr()];
+        let gff = annotation_hits_to_gff3(&hits, "pTest", 2000);
+        assert!(gff.starts_with("##gff-version 3\n"));
+        assert!(gff.contains("pTest\tHelix\tCDS\t901\t1761\t.\t-\t0\tID=AmpR;Name=AmpR"));
+    }
+}
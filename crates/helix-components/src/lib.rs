@@ -1,4 +1,6 @@
 pub mod annotate;
+pub mod backbone;
 pub mod component;
 pub mod db;
+pub mod export;
 pub mod seed_data;
@@ -55,6 +55,73 @@ pub fn builtin_components() -> Vec<Component> {
     components
 }
 
+/// Which built-in components to seed into a new database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedSet {
+    /// Every ApE default feature
+    #[default]
+    Full,
+    /// A curated set of common parts (see [`MINIMAL_COMPONENT_NAMES`])
+    Minimal,
+    None,
+}
+
+impl std::str::FromStr for SeedSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "full" => Ok(SeedSet::Full),
+            "minimal" => Ok(SeedSet::Minimal),
+            "none" => Ok(SeedSet::None),
+            other => Err(format!(
+                "Unknown seed set '{}'. Expected full, minimal or none.",
+                other
+            )),
+        }
+    }
+}
+
+/// Names of the parts seeded by [`SeedSet::Minimal`]: common promoters,
+/// origins, terminators, markers, reporters and tags.
+pub const MINIMAL_COMPONENT_NAMES: &[&str] = &[
+    "T7 promoter",
+    "lac promoter",
+    "tac promoter",
+    "araBAD promoter",
+    "CMV promoter",
+    "SV40 early promoter",
+    "AmpR promoter",
+    "Lac operator",
+    "ColE1 origin",
+    "pSC101 origin",
+    "M13 origin",
+    "rrnB T1 terminator",
+    "T7 terminator",
+    "AmpR",
+    "KanR",
+    "CAT/CamR",
+    "lacI",
+    "EGFP",
+    "mCherry",
+    "6xHis",
+    "FLAG tag",
+    "HA tag",
+];
+
+/// Built-in components belonging to `set`.
+pub fn builtin_components_for(set: SeedSet) -> Vec<Component> {
+    match set {
+        SeedSet::Full => builtin_components(),
+        SeedSet::Minimal => builtin_components()
+            .into_iter()
+            .filter(|c| MINIMAL_COMPONENT_NAMES.contains(&c.name.as_str()))
+            .collect(),
+        SeedSet::None => Vec::new(),
+    }
+}
+
 /// Return only DNA components (for sequence alignment).
 pub fn builtin_dna_components() -> Vec<Component> {
     let mut components = Vec::new();
@@ -208,6 +275,15 @@ mod tests {
         assert!(names.contains(&"EGFP"), "Missing EGFP");
     }
 
+    #[test]
+    fn test_minimal_set_names_exist() {
+        let minimal = builtin_components_for(SeedSet::Minimal);
+        assert_eq!(minimal.len(), MINIMAL_COMPONENT_NAMES.len());
+        assert!(builtin_components_for(SeedSet::None).is_empty());
+        assert_eq!("Minimal".parse::<SeedSet>(), Ok(SeedSet::Minimal));
+        assert!("most".parse::<SeedSet>().is_err());
+    }
+
     #[test]
     fn test_csv_parser() {
         let line = r#"hello,world,"quoted,field",simple"#;
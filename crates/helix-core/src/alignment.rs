@@ -1,4 +1,6 @@
-use crate::operations::reverse_complement;
+use crate::codon::CodonTable;
+use crate::operations::{reverse_complement, translate};
+use crate::substitution::SubstitutionMatrix;
 
 /// Scoring parameters for Smith-Waterman alignment with affine gap penalties.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -65,6 +67,24 @@ impl AlignmentResult {
     }
 }
 
+/// Map an alignment's target span back onto the forward strand of the target.
+///
+/// `align_both_strands` aligns reverse-complement hits against the reverse
+/// complement of the target, so their coordinates are measured from the other
+/// end. For those (`is_rc = true`) the span is mirrored through `target_len`;
+/// forward hits are returned unchanged. The result is 0-based, half-open.
+pub fn map_alignment_to_target(
+    result: &AlignmentResult,
+    target_len: usize,
+    is_rc: bool,
+) -> (usize, usize) {
+    if is_rc {
+        (target_len - result.target_end, target_len - result.target_start)
+    } else {
+        (result.target_start, result.target_end)
+    }
+}
+
 /// Traceback direction stored per cell.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TraceOp {
@@ -96,6 +116,72 @@ pub fn smith_waterman_local(
     band_width: Option<usize>,
     min_score: i32,
 ) -> Option<AlignmentResult> {
+    local_alignment(query, target, params, None, band_width, min_score, false)
+        .map(|(result, _)| result)
+}
+
+/// Smith-Waterman local alignment of two proteins, scoring residue pairs from
+/// `matrix` (e.g. [`SubstitutionMatrix::blosum62`]) with affine gap penalties
+/// (`gap_open` and `gap_extend` should be negative, as in [`ScoringParams`]).
+///
+/// Returns `None` if the best score is below `min_score`.
+pub fn smith_waterman_protein(
+    query: &[u8],
+    target: &[u8],
+    matrix: &SubstitutionMatrix,
+    gap_open: i32,
+    gap_extend: i32,
+    min_score: i32,
+) -> Option<AlignmentResult> {
+    // Match and mismatch scores come from the matrix instead
+    let params = ScoringParams {
+        match_score: 0,
+        mismatch_score: 0,
+        gap_open,
+        gap_extend,
+    };
+    local_alignment(query, target, &params, Some(matrix), None, min_score, false)
+        .map(|(result, _)| result)
+}
+
+/// Aligned sequences and CIGAR string for a local alignment.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AlignmentTrace {
+    /// Aligned part of the query, with `-` where the target has an extra base.
+    pub aligned_query: String,
+    /// Aligned part of the target, with `-` where the query has an extra base.
+    pub aligned_target: String,
+    /// CIGAR string with the target as the reference: `M` for an aligned pair
+    /// (match or mismatch), `I` for a query base missing from the target and
+    /// `D` for a target base missing from the query.
+    pub cigar: String,
+}
+
+/// Like [`smith_waterman_local`], but also returns the aligned strings and
+/// CIGAR string for rendering a pairwise view.
+pub fn smith_waterman_local_traced(
+    query: &[u8],
+    target: &[u8],
+    params: &ScoringParams,
+    band_width: Option<usize>,
+    min_score: i32,
+) -> Option<(AlignmentResult, AlignmentTrace)> {
+    local_alignment(query, target, params, None, band_width, min_score, true)
+        .map(|(result, trace)| (result, trace.expect("traced alignment builds a trace")))
+}
+
+/// Smith-Waterman alignment; the trace strings are only built when `traced`
+/// is set, so the count-only path does no string work. Pairs are scored from
+/// `matrix` when given, otherwise from the match/mismatch scores in `params`.
+fn local_alignment(
+    query: &[u8],
+    target: &[u8],
+    params: &ScoringParams,
+    matrix: Option<&SubstitutionMatrix>,
+    band_width: Option<usize>,
+    min_score: i32,
+    traced: bool,
+) -> Option<(AlignmentResult, Option<AlignmentTrace>)> {
     let n = query.len();  // rows
     let m = target.len(); // columns
 
@@ -159,10 +245,10 @@ pub fn smith_waterman_local(
             let q_base = query[i - 1];
             let t_base = target[j - 1];
 
-            let match_mismatch = if q_base.to_ascii_uppercase() == t_base.to_ascii_uppercase() {
-                params.match_score
-            } else {
-                params.mismatch_score
+            let match_mismatch = match matrix {
+                Some(matrix) => matrix.score(q_base, t_base),
+                None if q_base.eq_ignore_ascii_case(&t_base) => params.match_score,
+                None => params.mismatch_score,
             };
 
             // E: gap in query (extends along target, horizontal move)
@@ -211,27 +297,39 @@ pub fn smith_waterman_local(
     let mut ci = max_i;
     let mut cj = max_j;
 
+    // Columns collected end first as (query char, target char, CIGAR op)
+    let mut columns: Vec<(u8, u8, u8)> = Vec::new();
+
     while ci > 0 && cj > 0 && h[idx(ci, cj)] > 0 {
         match trace[idx(ci, cj)] {
             TraceOp::Match => {
                 let q_base = query[ci - 1];
                 let t_base = target[cj - 1];
-                if q_base.to_ascii_uppercase() == t_base.to_ascii_uppercase() {
+                if q_base.eq_ignore_ascii_case(&t_base) {
                     matches += 1;
                 } else {
                     mismatches += 1;
                 }
+                if traced {
+                    columns.push((q_base, t_base, b'M'));
+                }
                 ci -= 1;
                 cj -= 1;
             }
             TraceOp::GapInTarget => {
                 // consuming query, gap in target
                 gaps += 1;
+                if traced {
+                    columns.push((query[ci - 1], b'-', b'I'));
+                }
                 ci -= 1;
             }
             TraceOp::GapInQuery => {
                 // consuming target, gap in query
                 gaps += 1;
+                if traced {
+                    columns.push((b'-', target[cj - 1], b'D'));
+                }
                 cj -= 1;
             }
             TraceOp::None => break,
@@ -240,7 +338,7 @@ pub fn smith_waterman_local(
 
     let alignment_length = matches + mismatches + gaps;
 
-    Some(AlignmentResult {
+    let result = AlignmentResult {
         score: max_score,
         query_start: ci,
         query_end: max_i,
@@ -250,7 +348,41 @@ pub fn smith_waterman_local(
         mismatches,
         gaps,
         alignment_length,
-    })
+    };
+    let trace = traced.then(|| {
+        columns.reverse();
+        build_trace(&columns)
+    });
+    Some((result, trace))
+}
+
+/// Assemble aligned strings and a run-length encoded CIGAR from alignment
+/// columns in order.
+fn build_trace(columns: &[(u8, u8, u8)]) -> AlignmentTrace {
+    let aligned_query = columns.iter().map(|&(q, _, _)| q as char).collect();
+    let aligned_target = columns.iter().map(|&(_, t, _)| t as char).collect();
+
+    let mut cigar = String::new();
+    let mut run: Option<(u8, usize)> = None;
+    for &(_, _, op) in columns {
+        run = match run {
+            Some((current, len)) if current == op => Some((current, len + 1)),
+            Some((current, len)) => {
+                cigar.push_str(&format!("{}{}", len, current as char));
+                Some((op, 1))
+            }
+            None => Some((op, 1)),
+        };
+    }
+    if let Some((op, len)) = run {
+        cigar.push_str(&format!("{}{}", len, op as char));
+    }
+
+    AlignmentTrace {
+        aligned_query,
+        aligned_target,
+        cigar,
+    }
 }
 
 /// Run Smith-Waterman on both strands of the target.
@@ -290,6 +422,290 @@ pub fn align_both_strands(
     }
 }
 
+/// Stands in for target bases already claimed by an alignment; it never
+/// matches a query base.
+const MASKED: u8 = 0;
+
+/// Find up to `max_hits` non-overlapping local alignments of `query` in
+/// `target`, best first.
+///
+/// After each alignment the target span it covers is masked so the next
+/// round finds a different occurrence; the search stops at the first round
+/// whose best score is below `min_score`.
+pub fn smith_waterman_all(
+    query: &[u8],
+    target: &[u8],
+    params: &ScoringParams,
+    band_width: Option<usize>,
+    min_score: i32,
+    max_hits: usize,
+) -> Vec<AlignmentResult> {
+    let mut masked = target.to_vec();
+    let mut hits = Vec::new();
+    while hits.len() < max_hits {
+        let Some(hit) = smith_waterman_local(query, &masked, params, band_width, min_score) else {
+            break;
+        };
+        masked[hit.target_start..hit.target_end].fill(MASKED);
+        hits.push(hit);
+    }
+    hits
+}
+
+/// Like [`align_both_strands`], but returns every non-overlapping alignment
+/// on each strand (up to `max_hits` per strand, see [`smith_waterman_all`]),
+/// best first. Reverse-complement hits are flagged `true` and measured on the
+/// reverse complement, as with [`align_both_strands`].
+pub fn align_all_both_strands(
+    query: &[u8],
+    target: &[u8],
+    params: &ScoringParams,
+    band_width: Option<usize>,
+    min_score: i32,
+    max_hits: usize,
+) -> Vec<(AlignmentResult, bool)> {
+    let target_str: String = target.iter().map(|&b| b as char).collect();
+    let rc_bytes = reverse_complement(&target_str).into_bytes();
+
+    let mut hits: Vec<(AlignmentResult, bool)> =
+        smith_waterman_all(query, target, params, band_width, min_score, max_hits)
+            .into_iter()
+            .map(|hit| (hit, false))
+            .chain(
+                smith_waterman_all(query, &rc_bytes, params, band_width, min_score, max_hits)
+                    .into_iter()
+                    .map(|hit| (hit, true)),
+            )
+            .collect();
+    hits.sort_by_key(|(hit, _)| std::cmp::Reverse(hit.score));
+    hits
+}
+
+/// Source of the best score in a Needleman-Wunsch cell, packed with the
+/// gap-extension flags into one byte per cell.
+const NW_FROM_MATCH: u8 = 0;
+const NW_FROM_GAP_IN_TARGET: u8 = 1;
+const NW_FROM_GAP_IN_QUERY: u8 = 2;
+const NW_SOURCE_MASK: u8 = 0b011;
+/// The gap-in-target state at this cell extends the one above it.
+const NW_EXTEND_GAP_IN_TARGET: u8 = 0b100;
+/// The gap-in-query state at this cell extends the one to its left.
+const NW_EXTEND_GAP_IN_QUERY: u8 = 0b1000;
+
+/// Perform Needleman-Wunsch global alignment with affine gap penalties.
+///
+/// Unlike [`smith_waterman_local`], both sequences are aligned end to end, so
+/// the result always spans the whole query and target and terminal gaps are
+/// penalized. Scores are kept in rolling rows and the traceback in one byte
+/// per cell, so memory is O(nm) bytes rather than O(nm) integers.
+pub fn needleman_wunsch_global(
+    query: &[u8],
+    target: &[u8],
+    params: &ScoringParams,
+) -> AlignmentResult {
+    global_alignment(query, target, params, None).0
+}
+
+/// Needleman-Wunsch alignment returning the result together with the
+/// alignment path: one `NW_FROM_*` step per column, first column first.
+/// Pairs are scored from `matrix` when given, otherwise from the
+/// match/mismatch scores in `params`.
+fn global_alignment(
+    query: &[u8],
+    target: &[u8],
+    params: &ScoringParams,
+    matrix: Option<&SubstitutionMatrix>,
+) -> (AlignmentResult, Vec<u8>) {
+    let n = query.len();
+    let m = target.len();
+    let cols = m + 1;
+    let neg_inf = i32::MIN / 2;
+    let gap = |len: usize| params.gap_open + params.gap_extend * len as i32;
+
+    // best[j]: best score at (i, j); gap_t[j]: best score ending in a gap in
+    // the target (query consumed, vertical move) at (i, j)
+    let mut best: Vec<i32> = (0..cols).map(|j| if j == 0 { 0 } else { gap(j) }).collect();
+    let mut gap_t = vec![neg_inf; cols];
+    let mut trace = vec![0u8; (n + 1) * cols];
+
+    for (j, cell) in trace.iter_mut().enumerate().take(cols).skip(1) {
+        *cell = NW_FROM_GAP_IN_QUERY | if j > 1 { NW_EXTEND_GAP_IN_QUERY } else { 0 };
+    }
+
+    for i in 1..=n {
+        let row = i * cols;
+        let mut diag = best[0];
+        best[0] = gap(i);
+        gap_t[0] = best[0];
+        trace[row] = NW_FROM_GAP_IN_TARGET | if i > 1 { NW_EXTEND_GAP_IN_TARGET } else { 0 };
+
+        // Best score ending in a gap in the query (target consumed, horizontal)
+        let mut gap_q = neg_inf;
+
+        for j in 1..cols {
+            let mut cell = 0u8;
+
+            let t_open = best[j] + params.gap_open + params.gap_extend;
+            let t_ext = gap_t[j] + params.gap_extend;
+            if t_ext > t_open {
+                cell |= NW_EXTEND_GAP_IN_TARGET;
+            }
+            gap_t[j] = t_open.max(t_ext);
+
+            let q_open = best[j - 1] + params.gap_open + params.gap_extend;
+            let q_ext = gap_q + params.gap_extend;
+            if q_ext > q_open {
+                cell |= NW_EXTEND_GAP_IN_QUERY;
+            }
+            gap_q = q_open.max(q_ext);
+
+            let (q_base, t_base) = (query[i - 1], target[j - 1]);
+            let sub = match matrix {
+                Some(matrix) => matrix.score(q_base, t_base),
+                None if q_base.eq_ignore_ascii_case(&t_base) => params.match_score,
+                None => params.mismatch_score,
+            };
+            let diag_score = diag + sub;
+
+            diag = best[j];
+            best[j] = if diag_score >= gap_t[j] && diag_score >= gap_q {
+                cell |= NW_FROM_MATCH;
+                diag_score
+            } else if gap_t[j] >= gap_q {
+                cell |= NW_FROM_GAP_IN_TARGET;
+                gap_t[j]
+            } else {
+                cell |= NW_FROM_GAP_IN_QUERY;
+                gap_q
+            };
+            trace[row + j] = cell;
+        }
+    }
+
+    // Traceback from the bottom-right corner
+    let (mut i, mut j) = (n, m);
+    let mut state = trace[i * cols + j] & NW_SOURCE_MASK;
+    let (mut matches, mut mismatches, mut gaps) = (0usize, 0usize, 0usize);
+    let mut path = Vec::with_capacity(n.max(m));
+
+    while i > 0 || j > 0 {
+        let cell = trace[i * cols + j];
+        path.push(state);
+        match state {
+            NW_FROM_MATCH => {
+                if query[i - 1].eq_ignore_ascii_case(&target[j - 1]) {
+                    matches += 1;
+                } else {
+                    mismatches += 1;
+                }
+                i -= 1;
+                j -= 1;
+                state = trace[i * cols + j] & NW_SOURCE_MASK;
+            }
+            NW_FROM_GAP_IN_TARGET => {
+                gaps += 1;
+                i -= 1;
+                if cell & NW_EXTEND_GAP_IN_TARGET == 0 {
+                    state = trace[i * cols + j] & NW_SOURCE_MASK;
+                }
+            }
+            _ => {
+                gaps += 1;
+                j -= 1;
+                if cell & NW_EXTEND_GAP_IN_QUERY == 0 {
+                    state = trace[i * cols + j] & NW_SOURCE_MASK;
+                }
+            }
+        }
+    }
+
+    path.reverse();
+
+    let result = AlignmentResult {
+        score: best[m],
+        target_start: 0,
+        target_end: m,
+        query_start: 0,
+        query_end: n,
+        matches,
+        mismatches,
+        gaps,
+        alignment_length: matches + mismatches + gaps,
+    };
+    (result, path)
+}
+
+/// Percent identity of two sequences over their full global alignment.
+///
+/// Identity is matches divided by the whole alignment length (gaps
+/// included), so it is not fooled by a local alignment anchoring on a repeat.
+pub fn global_identity(a: &str, b: &str, params: &ScoringParams) -> f64 {
+    needleman_wunsch_global(a.as_bytes(), b.as_bytes(), params).percent_identity()
+}
+
+/// Gap penalties for the protein alignment behind [`codon_align`].
+const CODON_GAP_OPEN: i32 = -11;
+const CODON_GAP_EXTEND: i32 = -1;
+
+/// Align two coding sequences codon by codon, keeping reading frames intact.
+///
+/// Both sequences are translated with the standard code, the proteins are
+/// globally aligned with BLOSUM62, and the protein alignment is mapped back onto the
+/// original codons. Gaps are therefore always whole codons (`---`). A trailing
+/// incomplete codon is not part of the alignment. Returns the two gapped
+/// nucleotide strings, which have equal length.
+pub fn codon_align(a: &str, b: &str) -> (String, String) {
+    let table = CodonTable::standard();
+    let protein_a = translate(a, &table);
+    let protein_b = translate(b, &table);
+    // Match and mismatch scores come from the matrix instead
+    let params = ScoringParams {
+        match_score: 0,
+        mismatch_score: 0,
+        gap_open: CODON_GAP_OPEN,
+        gap_extend: CODON_GAP_EXTEND,
+    };
+    let (_, path) = global_alignment(
+        protein_a.as_bytes(),
+        protein_b.as_bytes(),
+        &params,
+        Some(&SubstitutionMatrix::blosum62()),
+    );
+
+    let codons_a: Vec<&str> = codons(a).collect();
+    let codons_b: Vec<&str> = codons(b).collect();
+    let (mut aligned_a, mut aligned_b) = (String::new(), String::new());
+    let (mut i, mut j) = (0, 0);
+
+    for step in path {
+        match step {
+            NW_FROM_MATCH => {
+                aligned_a.push_str(codons_a[i]);
+                aligned_b.push_str(codons_b[j]);
+                i += 1;
+                j += 1;
+            }
+            NW_FROM_GAP_IN_TARGET => {
+                aligned_a.push_str(codons_a[i]);
+                aligned_b.push_str("---");
+                i += 1;
+            }
+            _ => {
+                aligned_a.push_str("---");
+                aligned_b.push_str(codons_b[j]);
+                j += 1;
+            }
+        }
+    }
+
+    (aligned_a, aligned_b)
+}
+
+/// Complete codons of `seq`, in order
+fn codons(seq: &str) -> impl Iterator<Item = &str> {
+    (0..seq.len() / 3).map(move |k| &seq[k * 3..k * 3 + 3])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -538,6 +954,33 @@ mod tests {
         assert!((aln.query_coverage(0) - 0.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_map_alignment_to_target() {
+        let aln = AlignmentResult {
+            score: 20,
+            target_start: 10,
+            target_end: 20,
+            query_start: 0,
+            query_end: 10,
+            matches: 10,
+            mismatches: 0,
+            gaps: 0,
+            alignment_length: 10,
+        };
+        assert_eq!(map_alignment_to_target(&aln, 100, false), (10, 20));
+        assert_eq!(map_alignment_to_target(&aln, 100, true), (80, 90));
+    }
+
+    #[test]
+    fn test_map_alignment_to_target_rc_hit() {
+        // Query's reverse complement sits at 6..15 of the forward target
+        let query = b"AAACCCGGG";
+        let target = b"TTTTTTCCCGGGTTTTTTTTT";
+        let (aln, is_rc) = align_both_strands(query, target, &default_params(), None, 1).unwrap();
+        assert!(is_rc);
+        assert_eq!(map_alignment_to_target(&aln, target.len(), is_rc), (6, 15));
+    }
+
     #[test]
     fn test_zero_length_alignment_result() {
         let aln = AlignmentResult {
@@ -567,4 +1010,183 @@ mod tests {
         let result_fail = smith_waterman_local(query, target, &params, None, 9);
         assert!(result_fail.is_none());
     }
+
+    // -----------------------------------------------------------------
+    // Global alignment
+    // -----------------------------------------------------------------
+
+    /// Deterministic pseudo-random DNA so alignments have a unique optimum.
+    fn pseudo_random_dna(len: usize, seed: u64) -> String {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                b"ACGT"[(state >> 33) as usize % 4] as char
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_global_identity_with_insertion() {
+        let a = pseudo_random_dna(300, 7);
+        let b = format!("{}GGG{}", &a[..150], &a[150..]);
+
+        let result = needleman_wunsch_global(a.as_bytes(), b.as_bytes(), &default_params());
+        assert_eq!(result.gaps, 3);
+        assert_eq!(result.mismatches, 0);
+        assert_eq!(result.matches, 300);
+        assert_eq!(result.alignment_length, 303);
+        assert_eq!((result.query_end, result.target_end), (300, 303));
+
+        let identity = global_identity(&a, &b, &default_params());
+        assert!(identity > 98.0 && identity < 100.0, "identity = {}", identity);
+    }
+
+    #[test]
+    fn test_traced_alignment_strings_and_cigar() {
+        let params = default_params();
+        let query = b"ACGTACGTTTGCAGT";
+        let target = b"ACGTACGTATTGCAGT";
+
+        let (result, trace) =
+            smith_waterman_local_traced(query, target, &params, None, 0).unwrap();
+        assert_eq!(trace.aligned_query.len(), result.alignment_length);
+        assert_eq!(trace.aligned_target.len(), result.alignment_length);
+        assert_eq!(trace.aligned_query.replace('-', ""), "ACGTACGTTTGCAGT");
+        assert_eq!(trace.aligned_target.replace('-', ""), "ACGTACGTATTGCAGT");
+        assert_eq!(trace.aligned_query.matches('-').count(), 1);
+        assert_eq!(trace.cigar, "8M1D7M");
+
+        // Counts agree with the untraced path
+        let plain = smith_waterman_local(query, target, &params, None, 0).unwrap();
+        assert_eq!(
+            (plain.score, plain.matches, plain.gaps),
+            (result.score, result.matches, result.gaps)
+        );
+
+        let (_, trace) =
+            smith_waterman_local_traced(b"ACGTAACGTACGT", b"ACGTACGTACGT", &params, None, 0)
+                .unwrap();
+        assert!(trace.cigar.contains("1I"), "{}", trace.cigar);
+        assert!(trace.aligned_target.contains('-'));
+    }
+
+    #[test]
+    fn test_global_nearly_identical_30mers() {
+        let a = "ATGGCTAGCAAAGGAGAAGAACTTTTCACT";
+        let b = "ATGGCTAGCAAAGGTGAAGAACTTTTCACT";
+
+        let result = needleman_wunsch_global(a.as_bytes(), b.as_bytes(), &default_params());
+        assert_eq!(result.matches, 29);
+        assert_eq!(result.mismatches, 1);
+        assert_eq!(result.gaps, 0);
+        assert_eq!(result.alignment_length, 30);
+        assert_eq!(result.score, 29 * 2 - 3);
+    }
+
+    #[test]
+    fn test_global_leading_insertion_is_gapped() {
+        let query = "ATGGCTAGCAAAGGAGAAGAACTTTTCACT";
+        let target = format!("CCCCC{}", query);
+
+        // Local alignment drops the unmatched prefix...
+        let local =
+            smith_waterman_local(query.as_bytes(), target.as_bytes(), &default_params(), None, 0)
+                .unwrap();
+        assert_eq!(local.target_start, 5);
+        assert_eq!(local.gaps, 0);
+
+        // ...while global alignment spans it as gaps
+        let global = needleman_wunsch_global(query.as_bytes(), target.as_bytes(), &default_params());
+        assert_eq!((global.target_start, global.target_end), (0, 35));
+        assert_eq!((global.query_start, global.query_end), (0, 30));
+        assert_eq!(global.matches, 30);
+        assert_eq!(global.gaps, 5);
+        assert_eq!(global.alignment_length, 35);
+    }
+
+    #[test]
+    fn test_global_identical_and_empty() {
+        let a = "ACGTACGTAC";
+        assert!((global_identity(a, a, &default_params()) - 100.0).abs() < 1e-9);
+
+        let result = needleman_wunsch_global(b"", b"ACG", &default_params());
+        assert_eq!(result.gaps, 3);
+        assert_eq!(result.score, -5 - 2 * 3);
+        assert_eq!(global_identity("", "", &default_params()), 0.0);
+    }
+
+    #[test]
+    fn test_codon_align_keeps_frame() {
+        let a = "ATGGCTAAAGGTGAAGAACTGTTCACCGGTTAA";
+        // Drop the AAA codon and one extra codon from the middle of b
+        let b = "ATGGCTGGTGAACTGTTCACCGGTTAA";
+
+        let (aligned_a, aligned_b) = codon_align(a, b);
+        assert_eq!(aligned_a.len(), aligned_b.len());
+        assert_eq!(aligned_a.replace('-', ""), a);
+        assert_eq!(aligned_b.replace('-', ""), b);
+
+        for aligned in [&aligned_a, &aligned_b] {
+            for run in aligned.split(|c: char| c != '-').filter(|r| !r.is_empty()) {
+                assert_eq!(run.len() % 3, 0, "frame-breaking gap in {}", aligned);
+            }
+        }
+        assert_eq!(aligned_b.matches('-').count(), 6);
+    }
+
+    #[test]
+    fn test_codon_align_prefers_conservative_substitution() {
+        // M L G W against M I W: the gap belongs opposite G so that the
+        // isoleucine pairs with the similar leucine
+        let (aligned_a, aligned_b) = codon_align("ATGCTGGGTTGG", "ATGATTTGG");
+        assert_eq!(aligned_a, "ATGCTGGGTTGG");
+        assert_eq!(aligned_b, "ATGATT---TGG");
+    }
+
+    #[test]
+    fn test_protein_alignment_scores_similarity() {
+        let blosum = SubstitutionMatrix::blosum62();
+        let query = b"MVSKGEELFTGVVPILVELDGDVNGHKF";
+        // Conservative substitutions (K/R, E/D, I/V, F/Y) keep a high score
+        let similar = b"MVSRGEDLFTGVVPVLVELDGDVNGHRY";
+        let dissimilar = b"PQWCHNTAMRSYWGCPKHQMTRASWPCN";
+        assert_eq!(similar.len(), dissimilar.len());
+
+        let exact = smith_waterman_protein(query, query, &blosum, -11, -1, 0).unwrap();
+        let close = smith_waterman_protein(query, similar, &blosum, -11, -1, 0).unwrap();
+        assert_eq!(exact.matches, query.len());
+        assert!(close.score < exact.score);
+        assert!(close.score > 100, "score = {}", close.score);
+        assert!(close.mismatches > 0);
+
+        let far = smith_waterman_protein(query, dissimilar, &blosum, -11, -1, 0);
+        assert!(far.is_none_or(|far| far.score < close.score / 4));
+        assert!(smith_waterman_protein(query, dissimilar, &blosum, -11, -1, 50).is_none());
+    }
+
+    #[test]
+    fn test_protein_alignment_finds_domain() {
+        let blosum = SubstitutionMatrix::blosum62();
+        let domain = b"GDVNGHKFSVSGEGEGDATYGKL";
+        let target = b"MSTAPQRRSSGDVNGHKFSVSGEGEGDATYGKLTLKFICTT";
+        let result = smith_waterman_protein(domain, target, &blosum, -11, -1, 0).unwrap();
+        assert_eq!((result.target_start, result.target_end), (10, 33));
+        assert_eq!(result.percent_identity(), 100.0);
+    }
+
+    #[test]
+    fn test_smith_waterman_all_finds_each_copy() {
+        let part = b"GGCATCGATTGCAAGCTTAGCC";
+        let target = [&b"TTTTTTTTTT"[..], part, b"AAAAAAAAAAAAAAAAAAAA", part, b"TTTTT"].concat();
+        let params = default_params();
+
+        let hits = smith_waterman_all(part, &target, &params, None, 20, 10);
+        assert_eq!(hits.len(), 2);
+        let mut spans: Vec<(usize, usize)> =
+            hits.iter().map(|h| (h.target_start, h.target_end)).collect();
+        spans.sort();
+        assert_eq!(spans, vec![(10, 32), (52, 74)]);
+        assert_eq!(smith_waterman_all(part, &target, &params, None, 20, 1).len(), 1);
+    }
 }
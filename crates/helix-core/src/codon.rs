@@ -1,5 +1,24 @@
 use std::collections::HashMap;
 
+use thiserror::Error;
+
+/// Problems found by [`CodonTable::validate`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CodonTableError {
+    #[error("Invalid codon '{0}': expected three of A, C, G, T")]
+    InvalidCodon(String),
+    #[error("Codon {0} is assigned more than one amino acid")]
+    AmbiguousCodon(String),
+    #[error("Codon {0} has no amino acid assigned")]
+    MissingCodon(String),
+    #[error("Stop codon {0} does not translate to '*'")]
+    StopNotTerminator(String),
+    #[error("Codon {0} translates to '*' but is not listed as a stop codon")]
+    UnlistedStop(String),
+    #[error("Start codon {0} is a stop codon or not in the table")]
+    InvalidStart(String),
+}
+
 /// Standard and organism-specific codon tables
 pub struct CodonTable {
     pub name: String,
@@ -7,6 +26,14 @@ pub struct CodonTable {
     table: HashMap<String, char>,
     start_codons: Vec<String>,
     stop_codons: Vec<String>,
+    /// Codons given conflicting amino acids in [`CodonTable::custom`]
+    /// (e.g. both `TGA` and `UGA`), reported by [`CodonTable::validate`]
+    ambiguous: Vec<String>,
+}
+
+/// Uppercase a codon and read RNA `U` as `T`, matching the table keys.
+fn normalize_codon(codon: &str) -> String {
+    codon.to_uppercase().replace('U', "T")
 }
 
 impl CodonTable {
@@ -42,6 +69,7 @@ impl CodonTable {
             table,
             start_codons: vec!["ATG".to_string(), "CTG".to_string(), "TTG".to_string()],
             stop_codons: vec!["TAA".to_string(), "TAG".to_string(), "TGA".to_string()],
+            ambiguous: Vec::new(),
         }
     }
 
@@ -63,23 +91,136 @@ impl CodonTable {
         ct
     }
 
-    /// Translate a single codon to an amino acid
+    /// NCBI IDs of the tables available through [`CodonTable::from_id`]
+    pub const BUILTIN_IDS: &'static [u8] = &[1, 11];
+
+    /// Look up a built-in table by its NCBI translation table ID
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::standard()),
+            11 => Some(Self::bacterial()),
+            _ => None,
+        }
+    }
+
+    /// Build a user-defined genetic code.
+    ///
+    /// Codons are uppercased and `U` is read as `T`. The table is not checked
+    /// here; call [`CodonTable::validate`] before using a table built from
+    /// user input.
+    pub fn custom(
+        name: impl Into<String>,
+        id: u8,
+        codon_to_aa: HashMap<String, char>,
+        starts: Vec<String>,
+        stops: Vec<String>,
+    ) -> Self {
+        let mut table = HashMap::new();
+        let mut ambiguous = Vec::new();
+        for (codon, aa) in codon_to_aa {
+            let codon = normalize_codon(&codon);
+            let aa = aa.to_ascii_uppercase();
+            match table.insert(codon.clone(), aa) {
+                Some(previous) if previous != aa => ambiguous.push(codon),
+                _ => {}
+            }
+        }
+        ambiguous.sort();
+        ambiguous.dedup();
+
+        CodonTable {
+            name: name.into(),
+            id,
+            table,
+            start_codons: starts.iter().map(|c| normalize_codon(c)).collect(),
+            stop_codons: stops.iter().map(|c| normalize_codon(c)).collect(),
+            ambiguous,
+        }
+    }
+
+    /// Check that all 64 codons are assigned exactly one amino acid, that the
+    /// stop codons are exactly the codons translating to `*`, and that every
+    /// start codon codes for an amino acid.
+    pub fn validate(&self) -> Result<(), CodonTableError> {
+        if let Some(codon) = self.ambiguous.first() {
+            return Err(CodonTableError::AmbiguousCodon(codon.clone()));
+        }
+        for codon in self.table.keys() {
+            if codon.len() != 3 || !codon.chars().all(|c| matches!(c, 'A' | 'C' | 'G' | 'T')) {
+                return Err(CodonTableError::InvalidCodon(codon.clone()));
+            }
+        }
+        for codon in all_codons() {
+            if !self.table.contains_key(&codon) {
+                return Err(CodonTableError::MissingCodon(codon));
+            }
+        }
+        for stop in &self.stop_codons {
+            if self.table.get(stop) != Some(&'*') {
+                return Err(CodonTableError::StopNotTerminator(stop.clone()));
+            }
+        }
+        let mut terminators: Vec<&String> = self
+            .table
+            .iter()
+            .filter(|(_, &aa)| aa == '*')
+            .map(|(codon, _)| codon)
+            .collect();
+        terminators.sort();
+        if let Some(unlisted) = terminators.into_iter().find(|c| !self.stop_codons.contains(c)) {
+            return Err(CodonTableError::UnlistedStop(unlisted.clone()));
+        }
+        for start in &self.start_codons {
+            match self.table.get(start) {
+                Some(&aa) if aa != '*' => {}
+                _ => return Err(CodonTableError::InvalidStart(start.clone())),
+            }
+        }
+        Ok(())
+    }
+
+    /// Codon to amino acid assignments, e.g. as the starting point for a
+    /// custom table
+    pub fn codons(&self) -> &HashMap<String, char> {
+        &self.table
+    }
+
+    pub fn start_codons(&self) -> &[String] {
+        &self.start_codons
+    }
+
+    pub fn stop_codons(&self) -> &[String] {
+        &self.stop_codons
+    }
+
+    /// Amino acid for a DNA or RNA codon (`U` is read as `T`), or `X` if
+    /// the codon is not in the table.
     pub fn translate_codon(&self, codon: &str) -> char {
         self.table
-            .get(&codon.to_uppercase())
+            .get(&normalize_codon(codon))
             .copied()
             .unwrap_or('X')
     }
 
     pub fn is_start_codon(&self, codon: &str) -> bool {
-        self.start_codons.contains(&codon.to_uppercase())
+        self.start_codons.contains(&normalize_codon(codon))
     }
 
     pub fn is_stop_codon(&self, codon: &str) -> bool {
-        self.stop_codons.contains(&codon.to_uppercase())
+        self.stop_codons.contains(&normalize_codon(codon))
     }
 }
 
+/// All 64 DNA codons in TCAG order
+fn all_codons() -> impl Iterator<Item = String> {
+    const BASES: [char; 4] = ['T', 'C', 'A', 'G'];
+    BASES.iter().flat_map(|&a| {
+        BASES
+            .iter()
+            .flat_map(move |&b| BASES.iter().map(move |&c| [a, b, c].iter().collect()))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,10 +244,76 @@ mod tests {
         assert!(table.is_stop_codon("TGA"));
     }
 
+    #[test]
+    fn test_from_id() {
+        assert_eq!(CodonTable::from_id(1).unwrap().name, "Standard");
+        assert_eq!(CodonTable::from_id(11).unwrap().id, 11);
+        assert!(CodonTable::from_id(42).is_none());
+    }
+
     #[test]
     fn test_bacterial_table() {
         let table = CodonTable::bacterial();
         assert!(table.is_start_codon("GTG"));
         assert!(table.is_start_codon("TTG"));
     }
+
+    #[test]
+    fn test_custom_table_translate() {
+        // Vertebrate-mitochondrial style: TGA reads through as tryptophan
+        let standard = CodonTable::standard();
+        let mut codons = standard.codons().clone();
+        codons.insert("TGA".to_string(), 'W');
+        let table = CodonTable::custom(
+            "Custom",
+            100,
+            codons,
+            vec!["ATG".to_string()],
+            vec!["TAA".to_string(), "TAG".to_string()],
+        );
+        assert_eq!(table.validate(), Ok(()));
+        assert_eq!(crate::operations::translate("ATGTGATAA", &table), "MW*");
+        assert!(!table.is_stop_codon("TGA"));
+    }
+
+    #[test]
+    fn test_custom_table_validation() {
+        let codons = CodonTable::standard().codons().clone();
+        let starts = vec!["ATG".to_string()];
+
+        let table = CodonTable::custom("NoTGA", 101, codons.clone(), starts.clone(), vec![
+            "TAA".to_string(),
+            "TAG".to_string(),
+        ]);
+        assert_eq!(
+            table.validate(),
+            Err(CodonTableError::UnlistedStop("TGA".to_string()))
+        );
+
+        let mut missing = codons.clone();
+        missing.remove("GGG");
+        let table = CodonTable::custom("Missing", 102, missing, starts, vec![
+            "TAA".to_string(),
+            "TAG".to_string(),
+            "TGA".to_string(),
+        ]);
+        assert_eq!(
+            table.validate(),
+            Err(CodonTableError::MissingCodon("GGG".to_string()))
+        );
+
+        let mut both = codons.clone();
+        both.insert("UGA".to_string(), 'W');
+        let table = CodonTable::custom("Both", 103, both, vec!["ATG".to_string()], vec![
+            "TAA".to_string(),
+            "TAG".to_string(),
+        ]);
+        assert_eq!(
+            table.validate(),
+            Err(CodonTableError::AmbiguousCodon("TGA".to_string()))
+        );
+
+        assert_eq!(CodonTable::standard().validate(), Ok(()));
+        assert_eq!(CodonTable::bacterial().validate(), Ok(()));
+    }
 }
@@ -0,0 +1,351 @@
+//! Codon usage tables and codon optimization (back-translation).
+
+use std::collections::HashMap;
+
+use crate::codon::CodonTable;
+use crate::iupac;
+use crate::operations::reverse_complement;
+
+/// Floor on a codon's relative adaptiveness in [`cai`], the value commonly
+/// substituted for codons absent from the reference set.
+const MIN_ADAPTIVENESS: f64 = 0.01;
+
+/// Fraction of each amino acid's codons that use a given codon in highly
+/// expressed genes of E. coli K-12 (Kazusa codon usage database).
+const E_COLI_FREQUENCIES: [(&str, f64); 64] = [
+    ("TTT", 0.57), ("TTC", 0.43), ("TTA", 0.13), ("TTG", 0.13),
+    ("CTT", 0.10), ("CTC", 0.10), ("CTA", 0.04), ("CTG", 0.50),
+    ("ATT", 0.51), ("ATC", 0.42), ("ATA", 0.07), ("ATG", 1.00),
+    ("GTT", 0.26), ("GTC", 0.22), ("GTA", 0.15), ("GTG", 0.37),
+    ("TCT", 0.15), ("TCC", 0.15), ("TCA", 0.12), ("TCG", 0.15),
+    ("CCT", 0.16), ("CCC", 0.12), ("CCA", 0.19), ("CCG", 0.52),
+    ("ACT", 0.17), ("ACC", 0.44), ("ACA", 0.13), ("ACG", 0.27),
+    ("GCT", 0.16), ("GCC", 0.27), ("GCA", 0.21), ("GCG", 0.36),
+    ("TAT", 0.57), ("TAC", 0.43), ("TAA", 0.64), ("TAG", 0.07),
+    ("CAT", 0.57), ("CAC", 0.43), ("CAA", 0.35), ("CAG", 0.65),
+    ("AAT", 0.45), ("AAC", 0.55), ("AAA", 0.76), ("AAG", 0.24),
+    ("GAT", 0.63), ("GAC", 0.37), ("GAA", 0.69), ("GAG", 0.31),
+    ("TGT", 0.44), ("TGC", 0.56), ("TGA", 0.29), ("TGG", 1.00),
+    ("CGT", 0.38), ("CGC", 0.40), ("CGA", 0.06), ("CGG", 0.10),
+    ("AGT", 0.15), ("AGC", 0.28), ("AGA", 0.04), ("AGG", 0.02),
+    ("GGT", 0.34), ("GGC", 0.40), ("GGA", 0.11), ("GGG", 0.15),
+];
+
+/// Codon fractions for S. cerevisiae (Kazusa codon usage database).
+const S_CEREVISIAE_FREQUENCIES: [(&str, f64); 64] = [
+    ("TTT", 0.59), ("TTC", 0.41), ("TTA", 0.28), ("TTG", 0.29),
+    ("CTT", 0.13), ("CTC", 0.06), ("CTA", 0.14), ("CTG", 0.11),
+    ("ATT", 0.46), ("ATC", 0.26), ("ATA", 0.27), ("ATG", 1.00),
+    ("GTT", 0.39), ("GTC", 0.21), ("GTA", 0.21), ("GTG", 0.19),
+    ("TCT", 0.26), ("TCC", 0.16), ("TCA", 0.21), ("TCG", 0.10),
+    ("CCT", 0.31), ("CCC", 0.15), ("CCA", 0.42), ("CCG", 0.12),
+    ("ACT", 0.35), ("ACC", 0.22), ("ACA", 0.30), ("ACG", 0.14),
+    ("GCT", 0.38), ("GCC", 0.22), ("GCA", 0.29), ("GCG", 0.11),
+    ("TAT", 0.56), ("TAC", 0.44), ("TAA", 0.47), ("TAG", 0.23),
+    ("CAT", 0.64), ("CAC", 0.36), ("CAA", 0.69), ("CAG", 0.31),
+    ("AAT", 0.59), ("AAC", 0.41), ("AAA", 0.58), ("AAG", 0.42),
+    ("GAT", 0.65), ("GAC", 0.35), ("GAA", 0.70), ("GAG", 0.30),
+    ("TGT", 0.63), ("TGC", 0.37), ("TGA", 0.30), ("TGG", 1.00),
+    ("CGT", 0.14), ("CGC", 0.06), ("CGA", 0.07), ("CGG", 0.04),
+    ("AGT", 0.16), ("AGC", 0.11), ("AGA", 0.48), ("AGG", 0.21),
+    ("GGT", 0.47), ("GGC", 0.19), ("GGA", 0.22), ("GGG", 0.12),
+];
+
+/// Relative codon frequencies for each amino acid of an organism
+#[derive(Debug, Clone)]
+pub struct CodonUsageTable {
+    pub name: String,
+    /// Codons per amino acid (uppercase, `*` for stops), most frequent first
+    codons: HashMap<char, Vec<(String, f64)>>,
+}
+
+impl CodonUsageTable {
+    /// Build a table from per-codon frequencies, grouping codons by the amino
+    /// acid `table` assigns them. Codons the table does not know are ignored.
+    pub fn new(name: impl Into<String>, frequencies: &[(&str, f64)], table: &CodonTable) -> Self {
+        let mut codons: HashMap<char, Vec<(String, f64)>> = HashMap::new();
+        for &(codon, frequency) in frequencies {
+            let codon = codon.to_uppercase().replace('U', "T");
+            if let Some(&aa) = table.codons().get(&codon) {
+                codons.entry(aa).or_default().push((codon, frequency));
+            }
+        }
+        for choices in codons.values_mut() {
+            choices.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        }
+        Self {
+            name: name.into(),
+            codons,
+        }
+    }
+
+    /// E. coli K-12
+    pub fn e_coli() -> Self {
+        Self::new("Escherichia coli", &E_COLI_FREQUENCIES, &CodonTable::standard())
+    }
+
+    /// S. cerevisiae
+    pub fn s_cerevisiae() -> Self {
+        Self::new("Saccharomyces cerevisiae", &S_CEREVISIAE_FREQUENCIES, &CodonTable::standard())
+    }
+
+    /// Codons for `amino_acid` (case-insensitive), most frequent first
+    pub fn codons_for(&self, amino_acid: char) -> &[(String, f64)] {
+        self.codons
+            .get(&amino_acid.to_ascii_uppercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Back-translate `protein` using the most frequent codon for each residue.
+/// Residues with no codon in `usage` become `NNN`.
+pub fn codon_optimize(protein: &str, usage: &CodonUsageTable) -> String {
+    codon_optimize_avoiding(protein, usage, None)
+}
+
+/// Like [`codon_optimize`], but when `avoid_site` is given (e.g. a restriction
+/// site, IUPAC codes allowed) codons that would create the site on either
+/// strand are replaced by the next most frequent synonymous codons. Earlier
+/// residues are revisited when the site spans a codon boundary; if no choice
+/// within reach of the site avoids it, the top codon is kept.
+pub fn codon_optimize_avoiding(
+    protein: &str,
+    usage: &CodonUsageTable,
+    avoid_site: Option<&str>,
+) -> String {
+    let sites: Vec<String> = avoid_site
+        .filter(|site| !site.is_empty())
+        .map(|site| {
+            let site = site.to_uppercase();
+            let rc = reverse_complement(&site);
+            if rc == site {
+                vec![site]
+            } else {
+                vec![site, rc]
+            }
+        })
+        .unwrap_or_default();
+    let site_len = sites.first().map_or(0, String::len);
+    // Residues a site can reach back across from the codon being placed
+    let max_backtrack = site_len / 3 + 1;
+
+    let residues: Vec<char> = protein.chars().filter(|c| !c.is_whitespace()).collect();
+    let options = |i: usize| -> Vec<&str> {
+        let choices = usage.codons_for(residues[i]);
+        if choices.is_empty() {
+            vec!["NNN"]
+        } else {
+            choices.iter().map(|(codon, _)| codon.as_str()).collect()
+        }
+    };
+    let creates_site = |dna: &str, codon: &str| {
+        let tail = format!("{}{}", &dna[dna.len().saturating_sub(site_len + 2)..], codon);
+        sites
+            .iter()
+            .any(|site| !iupac::find_positions(&tail, site, false).is_empty())
+    };
+
+    let mut dna = String::with_capacity(residues.len() * 3);
+    // Index into `options(i)` of the codon chosen for each placed residue
+    let mut chosen: Vec<usize> = Vec::with_capacity(residues.len());
+    let mut next_choice = 0;
+    let mut furthest = 0;
+    // Residues before `floor` are fixed
+    let mut floor = 0;
+
+    while chosen.len() < residues.len() {
+        let i = chosen.len();
+        furthest = furthest.max(i);
+        let codons = options(i);
+        let found = (next_choice..codons.len()).find(|&k| !creates_site(&dna, codons[k]));
+        match found {
+            Some(k) => {
+                dna.push_str(codons[k]);
+                chosen.push(k);
+                next_choice = 0;
+            }
+            None if i > floor.max(furthest.saturating_sub(max_backtrack)) => {
+                let previous = chosen.pop().expect("i > 0");
+                dna.truncate(dna.len() - 3);
+                next_choice = previous + 1;
+            }
+            None => {
+                // Unavoidable from here: keep the top codon and move on
+                dna.push_str(codons[0]);
+                chosen.push(0);
+                next_choice = 0;
+                floor = i + 1;
+            }
+        }
+    }
+    dna
+}
+
+/// Codon adaptation index of `cds` against `usage` (Sharp & Li, 1987).
+///
+/// The geometric mean over the CDS codons of each codon's relative
+/// adaptiveness: its frequency divided by that of the most frequent
+/// synonymous codon. Stop codons, residues with a single codon (Met, Trp)
+/// and codons not in `usage` (ambiguous bases, a trailing partial codon) are
+/// skipped. Returns 0.0 if no codon is scored; 1.0 means every codon is the
+/// table's preferred one.
+pub fn cai(cds: &str, usage: &CodonUsageTable) -> f64 {
+    let mut adaptiveness: HashMap<&str, f64> = HashMap::new();
+    for (&aa, codons) in &usage.codons {
+        let Some(&(_, max)) = codons.first() else {
+            continue;
+        };
+        if aa == '*' || codons.len() < 2 || max <= 0.0 {
+            continue;
+        }
+        for (codon, freq) in codons {
+            adaptiveness.insert(codon, freq / max);
+        }
+    }
+
+    let cds = cds.to_uppercase().replace('U', "T");
+    let (mut log_sum, mut counted) = (0.0, 0usize);
+    for codon in cds.as_bytes().chunks_exact(3) {
+        let Some(&w) = std::str::from_utf8(codon).ok().and_then(|c| adaptiveness.get(c)) else {
+            continue;
+        };
+        // A codon never seen in the reference would zero the product
+        log_sum += w.max(MIN_ADAPTIVENESS).ln();
+        counted += 1;
+    }
+    if counted == 0 {
+        0.0
+    } else {
+        (log_sum / counted as f64).exp()
+    }
+}
+
+/// Back-translate `protein` to a degenerate DNA sequence, e.g. for primers
+/// against a protein motif.
+///
+/// Each codon position is the IUPAC code for the bases found at that position
+/// among all codons `table` assigns the residue, so the result covers every
+/// synonymous codon. For residues whose codons differ at more than one
+/// position (Leu `TTR`/`CTN`, Ser, Arg) the per-position collapse also admits
+/// some codons of other residues, e.g. Leu becomes `YTN`. Residues the table
+/// does not encode become `NNN`.
+pub fn reverse_translate_degenerate(protein: &str, table: &CodonTable) -> String {
+    let mut by_residue: HashMap<char, Vec<&str>> = HashMap::new();
+    for (codon, &aa) in table.codons() {
+        by_residue.entry(aa).or_default().push(codon);
+    }
+
+    let mut dna = String::with_capacity(protein.len() * 3);
+    for aa in protein.chars().filter(|c| !c.is_whitespace()) {
+        match by_residue.get(&aa.to_ascii_uppercase()) {
+            Some(codons) => {
+                for position in 0..3 {
+                    let bases: String = codons
+                        .iter()
+                        .filter_map(|codon| codon.chars().nth(position))
+                        .collect();
+                    dna.push(iupac::code_for(&bases).unwrap_or('N'));
+                }
+            }
+            None => dna.push_str("NNN"),
+        }
+    }
+    dna
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::translate;
+
+    #[test]
+    fn test_optimize_met() {
+        assert_eq!(codon_optimize("M", &CodonUsageTable::e_coli()), "ATG");
+        assert_eq!(codon_optimize("m", &CodonUsageTable::s_cerevisiae()), "ATG");
+    }
+
+    #[test]
+    fn test_builtin_tables_cover_every_residue() {
+        for usage in [CodonUsageTable::e_coli(), CodonUsageTable::s_cerevisiae()] {
+            for aa in "ACDEFGHIKLMNPQRSTVWY*".chars() {
+                let total: f64 = usage.codons_for(aa).iter().map(|(_, f)| f).sum();
+                assert!((total - 1.0).abs() < 0.02, "{} {}: {}", usage.name, aa, total);
+            }
+        }
+        assert_eq!(CodonUsageTable::e_coli().codons_for('L')[0].0, "CTG");
+        assert_eq!(CodonUsageTable::s_cerevisiae().codons_for('R')[0].0, "AGA");
+    }
+
+    #[test]
+    fn test_optimize_roundtrips_through_translate() {
+        let protein = "MSKGEELFTGVVPILVELDGDVNGHKFSVSGEGEGDATYGKLTLKFICTTGKLPVPWPTLVTTLTYGVQCFSRYPDHMKQ*";
+        let table = CodonTable::standard();
+        for usage in [CodonUsageTable::e_coli(), CodonUsageTable::s_cerevisiae()] {
+            let dna = codon_optimize(protein, &usage);
+            assert_eq!(dna.len(), protein.len() * 3);
+            assert_eq!(translate(&dna, &table), protein);
+        }
+        assert_eq!(codon_optimize("MX", &CodonUsageTable::e_coli()), "ATGNNN");
+    }
+
+    #[test]
+    fn test_reverse_translate_degenerate() {
+        let table = CodonTable::standard();
+        assert_eq!(reverse_translate_degenerate("M", &table), "ATG");
+        assert_eq!(reverse_translate_degenerate("W", &table), "TGG");
+        assert_eq!(reverse_translate_degenerate("MW", &table), "ATGTGG");
+
+        assert_eq!(reverse_translate_degenerate("F", &table), "TTY");
+        assert_eq!(reverse_translate_degenerate("K", &table), "AAR");
+        assert_eq!(reverse_translate_degenerate("I", &table), "ATH");
+        assert_eq!(reverse_translate_degenerate("G", &table), "GGN");
+        assert_eq!(reverse_translate_degenerate("L", &table), "YTN");
+        assert_eq!(reverse_translate_degenerate("S", &table), "WSN");
+        assert_eq!(reverse_translate_degenerate("R", &table), "MGN");
+        assert_eq!(reverse_translate_degenerate("*", &table), "TRR");
+        assert_eq!(reverse_translate_degenerate("MX", &table), "ATGNNN");
+
+        // Every codon of the protein matches the degenerate sequence
+        let dna = reverse_translate_degenerate("MKWL", &table);
+        assert_eq!(iupac::find_positions("ATGAAGTGGCTA", &dna, false), vec![0]);
+    }
+
+    #[test]
+    fn test_optimize_avoiding_site() {
+        let usage = CodonUsageTable::e_coli();
+        // E. coli's top codons for H, M give CAT ATG, an NdeI site
+        assert_eq!(codon_optimize("HM", &usage), "CATATG");
+
+        let dna = codon_optimize_avoiding("HM", &usage, Some("CATATG"));
+        assert_eq!(dna, "CACATG");
+        assert_eq!(translate(&dna, &CodonTable::standard()), "HM");
+
+        // A non-palindromic site is also avoided on the reverse strand:
+        // BsaI GGTCTC reads GAGACC on the top strand
+        let dna = codon_optimize_avoiding("EDH", &usage, Some("GGTCTC"));
+        assert!(!dna.contains("GAGACC") && !dna.contains("GGTCTC"), "{}", dna);
+        assert_eq!(translate(&dna, &CodonTable::standard()), "EDH");
+
+        // Met and Trp have a single codon, so this site cannot be avoided
+        assert_eq!(codon_optimize_avoiding("MW", &usage, Some("ATGTGG")), "ATGTGG");
+    }
+
+    #[test]
+    fn test_cai() {
+        let protein = "MSKGEELFTGVVPILVELDGDVNGHKFSVSGEGEGDATYGKLTLKFICTTGKLPVPWPTLVTTLTYGVQCFSRYPDHMKQ*";
+        for usage in [CodonUsageTable::e_coli(), CodonUsageTable::s_cerevisiae()] {
+            let optimized = codon_optimize(protein, &usage);
+            assert!((cai(&optimized, &usage) - 1.0).abs() < 1e-9, "{}", usage.name);
+        }
+
+        let usage = CodonUsageTable::e_coli();
+        // CTA is the rarest E. coli Leu codon: w = 0.04 / 0.50
+        assert!((cai("CTA", &usage) - 0.08).abs() < 1e-9);
+        assert!((cai("ATGCTGCTATGGTAA", &usage) - 0.08f64.sqrt()).abs() < 1e-9);
+        assert!(cai("CTGCTG", &usage) > cai("CTGCTA", &usage));
+        // Nothing scorable
+        assert_eq!(cai("ATGTGGTAA", &usage), 0.0);
+        assert_eq!(cai("", &usage), 0.0);
+    }
+}
@@ -0,0 +1,90 @@
+//! Word-match dot plots for comparing two sequences.
+
+use std::collections::HashMap;
+
+use crate::operations::reverse_complement;
+
+/// A word shared by both sequences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DotplotPoint {
+    /// Start of the word in the first sequence (0-based)
+    pub x: usize,
+    /// Start of the word in the second sequence (0-based)
+    pub y: usize,
+    /// Whether the word in the second sequence matches on the reverse strand
+    pub is_reverse: bool,
+}
+
+/// Every position pair where `a` and `b` share a word of `word_size` bases
+/// (case-insensitive), on the same strand or with the word in `b` reverse
+/// complemented. Words containing anything other than A, C, G or T are
+/// skipped. Points are sorted by `x`, then `y`.
+///
+/// Pass the same sequence twice for a self-comparison: repeats show up as
+/// off-diagonal runs and inverted repeats as reverse points.
+pub fn dotplot(a: &str, b: &str, word_size: usize) -> Vec<DotplotPoint> {
+    let a = a.to_uppercase();
+    let b = b.to_uppercase();
+    let mut points = Vec::new();
+    if word_size == 0 || a.len() < word_size || b.len() < word_size {
+        return points;
+    }
+
+    let is_acgt = |w: &[u8]| w.iter().all(|c| matches!(c, b'A' | b'C' | b'G' | b'T'));
+    let mut words: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (x, word) in a.as_bytes().windows(word_size).enumerate() {
+        if is_acgt(word) {
+            words.entry(word).or_default().push(x);
+        }
+    }
+
+    for (y, word) in b.as_bytes().windows(word_size).enumerate() {
+        if !is_acgt(word) {
+            continue;
+        }
+        if let Some(xs) = words.get(word) {
+            points.extend(xs.iter().map(|&x| DotplotPoint { x, y, is_reverse: false }));
+        }
+        let rc = reverse_complement(std::str::from_utf8(word).unwrap_or_default());
+        if let Some(xs) = words.get(rc.as_bytes()) {
+            points.extend(xs.iter().map(|&x| DotplotPoint { x, y, is_reverse: true }));
+        }
+    }
+
+    points.sort_by_key(|p| (p.x, p.y, p.is_reverse));
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_comparison_diagonal() {
+        let seq = "ACGTTGCAAGGCTTAC";
+        let points = dotplot(seq, seq, 4);
+        for x in 0..=seq.len() - 4 {
+            assert!(points.contains(&DotplotPoint { x, y: x, is_reverse: false }));
+        }
+    }
+
+    #[test]
+    fn test_repeat_and_inverted_repeat() {
+        //          repeat      inverted
+        let a = "GATTACAcccGATTACA";
+        let forward = dotplot(a, a, 7);
+        assert!(forward.contains(&DotplotPoint { x: 0, y: 10, is_reverse: false }));
+        assert!(forward.contains(&DotplotPoint { x: 10, y: 0, is_reverse: false }));
+
+        let b = "TGTAATC";
+        let reverse = dotplot(a, b, 7);
+        assert!(reverse.iter().all(|p| p.is_reverse));
+        assert_eq!(reverse.len(), 2);
+    }
+
+    #[test]
+    fn test_skips_ambiguous_words() {
+        assert!(dotplot("ACGNACG", "ACGNACG", 4).is_empty());
+        assert!(dotplot("ACGT", "ACGT", 0).is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum FeatureType {
     Promoter,
@@ -83,7 +83,7 @@ impl FeatureType {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Strand {
     Forward,
@@ -109,14 +109,31 @@ impl Strand {
     }
 }
 
+/// A `gap()` element inside a join location, e.g. `join(1..100,gap(50),151..200)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JoinGap {
+    /// Number of join ranges that precede this gap
+    pub after: usize,
+    /// Gap length, if one was given (`gap(50)`, `gap(unk100)`); `None` for `gap()`
+    pub length: Option<usize>,
+    /// Whether the length is only an estimate (`gap(unk100)`)
+    #[serde(default)]
+    pub unknown: bool,
+}
+
 /// Represents the location of a feature on the sequence
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Location {
     /// Simple range: start..end
     Simple { start: usize, end: usize },
     /// Join of multiple ranges: join(1..100, 200..300)
-    Join { ranges: Vec<(usize, usize)> },
+    Join {
+        ranges: Vec<(usize, usize)>,
+        /// Sequence gaps between ranges: join(1..100, gap(50), 151..200)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        gaps: Vec<JoinGap>,
+    },
     /// Complement of a location
     Complement { inner: Box<Location> },
 }
@@ -126,10 +143,17 @@ impl Location {
         Location::Simple { start, end }
     }
 
+    pub fn join(ranges: Vec<(usize, usize)>) -> Self {
+        Location::Join {
+            ranges,
+            gaps: Vec::new(),
+        }
+    }
+
     pub fn start(&self) -> usize {
         match self {
             Location::Simple { start, .. } => *start,
-            Location::Join { ranges } => ranges.first().map(|r| r.0).unwrap_or(0),
+            Location::Join { ranges, .. } => ranges.first().map(|r| r.0).unwrap_or(0),
             Location::Complement { inner } => inner.start(),
         }
     }
@@ -137,7 +161,7 @@ impl Location {
     pub fn end(&self) -> usize {
         match self {
             Location::Simple { end, .. } => *end,
-            Location::Join { ranges } => ranges.last().map(|r| r.1).unwrap_or(0),
+            Location::Join { ranges, .. } => ranges.last().map(|r| r.1).unwrap_or(0),
             Location::Complement { inner } => inner.end(),
         }
     }
@@ -145,7 +169,7 @@ impl Location {
     pub fn len(&self) -> usize {
         match self {
             Location::Simple { start, end } => end.saturating_sub(*start),
-            Location::Join { ranges } => ranges.iter().map(|(s, e)| e.saturating_sub(*s)).sum(),
+            Location::Join { ranges, .. } => ranges.iter().map(|(s, e)| e.saturating_sub(*s)).sum(),
             Location::Complement { inner } => inner.len(),
         }
     }
@@ -153,6 +177,15 @@ impl Location {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// All `(start, end)` spans covered by this location, in location order
+    pub fn ranges(&self) -> Vec<(usize, usize)> {
+        match self {
+            Location::Simple { start, end } => vec![(*start, *end)],
+            Location::Join { ranges, .. } => ranges.clone(),
+            Location::Complement { inner } => inner.ranges(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -201,12 +234,33 @@ impl Feature {
         self.location.end()
     }
 
+    /// Color used to draw this feature, falling back to the feature type's
+    /// default when none is set.
     pub fn effective_color(&self) -> &str {
         self.color
             .as_deref()
             .unwrap_or_else(|| self.feature_type.default_color())
     }
 
+    /// Name given by the qualifiers, in order of precedence: `/label`,
+    /// `/gene`, `/product`, `/note`.
+    pub fn qualifier_name(&self) -> Option<&str> {
+        ["label", "gene", "product", "note"]
+            .iter()
+            .find_map(|key| self.get_qualifier(key))
+    }
+
+    /// Name to show for this feature: `name` when set, otherwise
+    /// [`Feature::qualifier_name`], otherwise the GenBank feature key.
+    pub fn display_name(&self) -> String {
+        if !self.name.is_empty() {
+            return self.name.clone();
+        }
+        self.qualifier_name()
+            .unwrap_or_else(|| self.feature_type.to_genbank_key())
+            .to_string()
+    }
+
     pub fn get_qualifier(&self, key: &str) -> Option<&str> {
         self.qualifiers
             .iter()
@@ -226,6 +280,21 @@ impl Feature {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_display_name_chain() {
+        let mut f = Feature::new("", FeatureType::Cds, 0, 30, Strand::Forward);
+        assert_eq!(f.display_name(), "CDS");
+        f.add_qualifier("note", "putative");
+        f.add_qualifier("product", "beta-lactamase");
+        assert_eq!(f.display_name(), "beta-lactamase");
+        f.add_qualifier("gene", "bla");
+        assert_eq!(f.display_name(), "bla");
+        f.add_qualifier("label", "AmpR");
+        assert_eq!(f.display_name(), "AmpR");
+        f.name = "renamed".to_string();
+        assert_eq!(f.display_name(), "renamed");
+    }
+
     #[test]
     fn test_feature_type_from_genbank() {
         assert_eq!(FeatureType::from_genbank_key("CDS"), FeatureType::Cds);
@@ -253,14 +322,21 @@ mod tests {
 
     #[test]
     fn test_location_join() {
-        let loc = Location::Join {
-            ranges: vec![(100, 200), (300, 400)],
-        };
+        let loc = Location::join(vec![(100, 200), (300, 400)]);
         assert_eq!(loc.start(), 100);
         assert_eq!(loc.end(), 400);
         assert_eq!(loc.len(), 200);
     }
 
+    #[test]
+    fn test_location_ranges() {
+        let loc = Location::Complement {
+            inner: Box::new(Location::join(vec![(0, 10), (20, 30)])),
+        };
+        assert_eq!(loc.ranges(), vec![(0, 10), (20, 30)]);
+        assert_eq!(Location::simple(5, 8).ranges(), vec![(5, 8)]);
+    }
+
     #[test]
     fn test_feature_creation() {
         let f = Feature::new("GFP", FeatureType::Cds, 100, 800, Strand::Forward);
@@ -269,4 +345,12 @@ mod tests {
         assert_eq!(f.end(), 800);
         assert_eq!(f.effective_color(), "#5b9cf5");
     }
+
+    #[test]
+    fn test_effective_color_prefers_own_color() {
+        let mut f = Feature::new("AmpR", FeatureType::Cds, 0, 100, Strand::Reverse);
+        f.color = Some("#ff0000".to_string());
+        f.add_qualifier("ApEinfo_revcolor", "#0000ff");
+        assert_eq!(f.effective_color(), "#ff0000");
+    }
 }
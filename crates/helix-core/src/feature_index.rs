@@ -0,0 +1,172 @@
+//! Interval index over feature spans for fast position lookups.
+
+use crate::feature::Feature;
+
+/// One indexed span, half-open `[start, end)`
+#[derive(Debug, Clone, Copy)]
+struct Interval {
+    start: usize,
+    end: usize,
+    /// Index of the owning feature in the slice the index was built from
+    feature: usize,
+}
+
+/// Static interval tree over the spans of a feature list.
+///
+/// Intervals are kept sorted by start and treated as an implicit balanced
+/// binary tree (each subrange's midpoint is its root), with the largest end in
+/// every subtree stored alongside, so stabbing and overlap queries run in
+/// O(log n + k). Every part of a `Join` is indexed; a span with `start > end`
+/// wraps through the origin and is indexed as `start..` plus `..end`.
+///
+/// Queries return indices into the feature slice passed to [`FeatureIndex::build`],
+/// sorted and deduplicated.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureIndex {
+    intervals: Vec<Interval>,
+    /// Largest `end` in the subtree rooted at each position
+    max_end: Vec<usize>,
+}
+
+impl FeatureIndex {
+    pub fn build(features: &[Feature]) -> Self {
+        let mut intervals = Vec::new();
+        for (feature, f) in features.iter().enumerate() {
+            for (start, end) in f.location.ranges() {
+                if start < end {
+                    intervals.push(Interval { start, end, feature });
+                } else if start > end {
+                    intervals.push(Interval { start, end: usize::MAX, feature });
+                    if end > 0 {
+                        intervals.push(Interval { start: 0, end, feature });
+                    }
+                }
+            }
+        }
+        intervals.sort_unstable_by_key(|iv| (iv.start, iv.end));
+
+        let mut max_end = vec![0; intervals.len()];
+        Self::fill_max_end(&intervals, &mut max_end, 0, intervals.len());
+        Self { intervals, max_end }
+    }
+
+    fn fill_max_end(intervals: &[Interval], max_end: &mut [usize], lo: usize, hi: usize) -> usize {
+        if lo >= hi {
+            return 0;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let left = Self::fill_max_end(intervals, max_end, lo, mid);
+        let right = Self::fill_max_end(intervals, max_end, mid + 1, hi);
+        max_end[mid] = intervals[mid].end.max(left).max(right);
+        max_end[mid]
+    }
+
+    /// Number of indexed spans (a feature may contribute several)
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Features with a span covering `pos`
+    pub fn query(&self, pos: usize) -> Vec<usize> {
+        self.query_range(pos, pos.saturating_add(1))
+    }
+
+    /// Features with a span overlapping `start..end`
+    pub fn query_range(&self, start: usize, end: usize) -> Vec<usize> {
+        let mut hits = Vec::new();
+        if start < end {
+            self.collect(0, self.intervals.len(), start, end, &mut hits);
+        }
+        hits.sort_unstable();
+        hits.dedup();
+        hits
+    }
+
+    fn collect(&self, lo: usize, hi: usize, start: usize, end: usize, hits: &mut Vec<usize>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        // Nothing in this subtree reaches the query
+        if self.max_end[mid] <= start {
+            return;
+        }
+        self.collect(lo, mid, start, end, hits);
+
+        let iv = self.intervals[mid];
+        // Intervals right of `mid` start at or after this one
+        if iv.start >= end {
+            return;
+        }
+        if iv.end > start {
+            hits.push(iv.feature);
+        }
+        self.collect(mid + 1, hi, start, end, hits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::{FeatureType, Location, Strand};
+
+    fn linear_scan(features: &[Feature], start: usize, end: usize) -> Vec<usize> {
+        (0..features.len())
+            .filter(|&i| {
+                features[i].location.ranges().iter().any(|&(s, e)| {
+                    if s <= e {
+                        s < end && e > start
+                    } else {
+                        e > start || s < end
+                    }
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_linear_scan() {
+        // Deterministic pseudo-random layout with nested and overlapping spans
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % bound as u64) as usize
+        };
+
+        let mut features = Vec::new();
+        for i in 0..5000 {
+            let start = next(1_000_000);
+            let len = 1 + next(if i % 50 == 0 { 50_000 } else { 2_000 });
+            features.push(Feature::new(
+                format!("f{}", i),
+                FeatureType::Gene,
+                start,
+                start + len,
+                Strand::Forward,
+            ));
+        }
+        let mut joined = Feature::new("join", FeatureType::Cds, 0, 0, Strand::Forward);
+        joined.location = Location::join(vec![(10, 20), (500_000, 500_100)]);
+        features.push(joined);
+        features.push(Feature::new("wrap", FeatureType::Misc, 999_990, 15, Strand::Forward));
+
+        let index = FeatureIndex::build(&features);
+        assert_eq!(index.len(), 5004);
+
+        for pos in [0, 12, 17, 250_000, 500_050, 999_995, 1_000_500] {
+            assert_eq!(index.query(pos), linear_scan(&features, pos, pos + 1), "pos {}", pos);
+        }
+        for _ in 0..200 {
+            let start = next(1_000_000);
+            let end = start + 1 + next(10_000);
+            assert_eq!(index.query_range(start, end), linear_scan(&features, start, end));
+        }
+        assert!(index.query_range(5, 5).is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! IUPAC nucleotide ambiguity codes.
+
+/// Concrete bases represented by an IUPAC nucleotide code (case-insensitive).
+/// `U` is treated as `T`. Returns `None` for characters that are not codes.
+pub fn expand(code: char) -> Option<&'static str> {
+    let bases = match code.to_ascii_uppercase() {
+        'A' => "A",
+        'C' => "C",
+        'G' => "G",
+        'T' | 'U' => "T",
+        'R' => "AG",
+        'Y' => "CT",
+        'S' => "CG",
+        'W' => "AT",
+        'K' => "GT",
+        'M' => "AC",
+        'B' => "CGT",
+        'D' => "AGT",
+        'H' => "ACT",
+        'V' => "ACG",
+        'N' => "ACGT",
+        _ => return None,
+    };
+    Some(bases)
+}
+
+/// Whether the concrete `base` is one of the bases `code` stands for.
+pub fn matches(code: char, base: char) -> bool {
+    let base = match base.to_ascii_uppercase() {
+        'U' => 'T',
+        b => b,
+    };
+    expand(code).is_some_and(|bases| bases.contains(base))
+}
+
+/// The IUPAC code standing for exactly the concrete bases in `bases` (any
+/// order, duplicates allowed, `U` read as `T`). Returns `None` if `bases` is
+/// empty or contains anything other than A, C, G, T or U.
+pub fn code_for(bases: &str) -> Option<char> {
+    let mut set = [false; 4];
+    for base in bases.chars() {
+        let index = "ACGT".find(match base.to_ascii_uppercase() {
+            'U' => 'T',
+            b => b,
+        })?;
+        set[index] = true;
+    }
+    "ACGTRYSWKMBDHVN".chars().find(|&code| {
+        let expanded = expand(code).unwrap_or_default();
+        "ACGT".chars().zip(set).all(|(base, present)| expanded.contains(base) == present)
+    })
+}
+
+/// Start positions where the IUPAC `pattern` matches `sequence` (forward
+/// strand only). On circular sequences matches may run through the origin.
+pub fn find_positions(sequence: &str, pattern: &str, is_circular: bool) -> Vec<usize> {
+    let seq: Vec<char> = sequence.chars().collect();
+    let pat: Vec<char> = pattern.chars().collect();
+    let n = seq.len();
+    if pat.is_empty() || pat.len() > n {
+        return Vec::new();
+    }
+
+    let last_start = if is_circular { n } else { n - pat.len() + 1 };
+    (0..last_start)
+        .filter(|&i| {
+            pat.iter()
+                .enumerate()
+                .all(|(j, &code)| matches(code, seq[(i + j) % n]))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(expand('A'), Some("A"));
+        assert_eq!(expand('r'), Some("AG"));
+        assert_eq!(expand('N'), Some("ACGT"));
+        assert_eq!(expand('X'), None);
+    }
+
+    #[test]
+    fn test_code_for() {
+        assert_eq!(code_for("A"), Some('A'));
+        assert_eq!(code_for("ga"), Some('R'));
+        assert_eq!(code_for("TCCT"), Some('Y'));
+        assert_eq!(code_for("UGC"), Some('B'));
+        assert_eq!(code_for("ACGT"), Some('N'));
+        assert_eq!(code_for(""), None);
+        assert_eq!(code_for("AX"), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches('N', 'G'));
+        assert!(matches('R', 'a'));
+        assert!(!matches('R', 'C'));
+        assert!(matches('T', 'U'));
+        assert!(!matches('A', 'N'));
+    }
+
+    #[test]
+    fn test_find_positions() {
+        assert_eq!(find_positions("AGGACCTGGTCC", "GGNCC", false), vec![1, 7]);
+        assert!(find_positions("CCAGG", "GGNCC", false).is_empty());
+        // GG|ACC wraps through the origin
+        assert_eq!(find_positions("ACCTTGG", "GGNCC", true), vec![5]);
+    }
+}
@@ -0,0 +1,66 @@
+//! K-mer composition analysis.
+
+use std::collections::HashMap;
+
+use crate::operations::reverse_complement;
+
+/// Count every k-mer of length `k` in `seq` (case-insensitive).
+///
+/// With `both_strands` set, each k-mer is canonicalized to the
+/// lexicographically smaller of itself and its reverse complement, so a k-mer
+/// and its reverse complement share one count. K-mers containing anything other
+/// than A, C, G or T are skipped.
+pub fn kmer_counts(seq: &str, k: usize, both_strands: bool) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    let bases = seq.to_uppercase().into_bytes();
+    if k == 0 || bases.len() < k {
+        return counts;
+    }
+
+    for window in bases.windows(k) {
+        if !window.iter().all(|b| matches!(b, b'A' | b'C' | b'G' | b'T')) {
+            continue;
+        }
+        let kmer = String::from_utf8_lossy(window).into_owned();
+        let kmer = if both_strands {
+            let rc = reverse_complement(&kmer);
+            if rc < kmer { rc } else { kmer }
+        } else {
+            kmer
+        };
+        *counts.entry(kmer).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kmer_counts() {
+        let counts = kmer_counts("ACGT", 2, false);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["AC"], 1);
+        assert_eq!(counts["CG"], 1);
+        assert_eq!(counts["GT"], 1);
+    }
+
+    #[test]
+    fn test_kmer_counts_canonical() {
+        // GT is the reverse complement of AC; CG is its own reverse complement
+        let counts = kmer_counts("ACGT", 2, true);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["AC"], 2);
+        assert_eq!(counts["CG"], 1);
+    }
+
+    #[test]
+    fn test_kmer_counts_skips_ambiguous() {
+        let counts = kmer_counts("acNgt", 2, false);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["AC"], 1);
+        assert_eq!(counts["GT"], 1);
+        assert!(kmer_counts("AC", 3, false).is_empty());
+    }
+}
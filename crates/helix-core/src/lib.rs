@@ -1,9 +1,20 @@
 pub mod alignment;
 pub mod codon;
+pub mod codon_usage;
+pub mod dotplot;
 pub mod feature;
+pub mod feature_index;
+pub mod iupac;
+pub mod kmer;
 pub mod operations;
+pub mod primer;
+pub mod promoter;
+pub mod repeats;
+pub mod restriction;
 pub mod search;
 pub mod sequence;
+pub mod substitution;
+pub mod trim;
 
 pub use feature::*;
 pub use sequence::*;
@@ -1,4 +1,56 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
 use crate::codon::CodonTable;
+use crate::primer::TmParams;
+
+/// Kind of biological sequence, used to pick the valid alphabet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MoleculeType {
+    Dna,
+    Rna,
+    Protein,
+}
+
+impl MoleculeType {
+    /// Whether `c` (case-insensitive) belongs to this alphabet, including IUPAC
+    /// ambiguity codes for nucleotides and `*` for protein stops
+    pub fn allows(&self, c: char) -> bool {
+        let c = c.to_ascii_uppercase();
+        match self {
+            MoleculeType::Dna => "ACGTRYSWKMBDHVN".contains(c),
+            MoleculeType::Rna => "ACGURYSWKMBDHVN".contains(c),
+            MoleculeType::Protein => c.is_ascii_uppercase() || c == '*',
+        }
+    }
+}
+
+/// Whether `c` is an alignment gap character
+pub fn is_gap(c: char) -> bool {
+    matches!(c, '-' | '.')
+}
+
+/// Length of an aligned sequence ignoring gap characters
+pub fn ungapped_len(seq: &str) -> usize {
+    seq.chars().filter(|&c| !is_gap(c)).count()
+}
+
+/// Map a 0-based alignment column to the 0-based position in the ungapped
+/// sequence. Returns `None` if the column is a gap or past the end.
+pub fn gapped_to_ungapped_position(seq: &str, col: usize) -> Option<usize> {
+    let mut ungapped = 0;
+    for (i, c) in seq.chars().enumerate() {
+        if i == col {
+            return (!is_gap(c)).then_some(ungapped);
+        }
+        if !is_gap(c) {
+            ungapped += 1;
+        }
+    }
+    None
+}
 
 /// Complement a single DNA base
 pub fn complement_base(base: char) -> char {
@@ -27,21 +79,564 @@ pub fn reverse_complement(seq: &str) -> String {
     seq.chars().rev().map(complement_base).collect()
 }
 
-/// Translate a DNA sequence to amino acids using the given codon table
+/// Reverse a sequence without complementing it
+pub fn reverse(seq: &str) -> String {
+    seq.chars().rev().collect()
+}
+
+/// Transcribe DNA to RNA: uppercase and replace T with U
+pub fn transcribe(seq: &str) -> String {
+    seq.to_uppercase().replace('T', "U")
+}
+
+/// Reverse-transcribe RNA to DNA: uppercase and replace U with T
+pub fn reverse_transcribe(rna: &str) -> String {
+    rna.to_uppercase().replace('U', "T")
+}
+
+/// What to do with the one or two bases left over after the last full codon
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartialCodonPolicy {
+    /// Leave the partial codon out
+    #[default]
+    Drop,
+    /// Append [`PARTIAL_CODON_INDICATOR`]
+    Indicate,
+}
+
+/// Marks a trailing partial codon under [`PartialCodonPolicy::Indicate`]
+pub const PARTIAL_CODON_INDICATOR: char = '>';
+
+/// Translate a DNA sequence to amino acids using the given codon table.
+/// A trailing partial codon is dropped.
 pub fn translate(seq: &str, table: &CodonTable) -> String {
+    translate_with_policy(seq, table, PartialCodonPolicy::Drop)
+}
+
+/// Translate a DNA sequence, handling a trailing partial codon per `policy`
+pub fn translate_with_policy(seq: &str, table: &CodonTable, policy: PartialCodonPolicy) -> String {
     let bases: Vec<char> = seq.to_uppercase().chars().collect();
-    let mut protein = String::with_capacity(bases.len() / 3);
+    let mut protein = String::with_capacity(bases.len() / 3 + 1);
 
     for chunk in bases.chunks(3) {
         if chunk.len() == 3 {
             let codon: String = chunk.iter().collect();
             protein.push(table.translate_codon(&codon));
+        } else if policy == PartialCodonPolicy::Indicate {
+            protein.push(PARTIAL_CODON_INDICATOR);
         }
     }
 
     protein
 }
 
+/// Translate from `start` in frame, e.g. to preview the protein from a start
+/// codon under the cursor without running the ORF finder.
+///
+/// With `stop_at_stop` translation ends at the first in-frame stop codon,
+/// which is included as `*` so callers can tell a complete ORF from one that
+/// runs off the end. Otherwise, or if no stop is found, translation runs to the
+/// last complete codon. A `start` past the end yields an empty string.
+pub fn translate_from(seq: &str, start: usize, table: &CodonTable, stop_at_stop: bool) -> String {
+    let bases: Vec<char> = seq.to_uppercase().chars().skip(start).collect();
+    let mut protein = String::with_capacity(bases.len() / 3);
+
+    for chunk in bases.chunks_exact(3) {
+        let codon: String = chunk.iter().collect();
+        let aa = table.translate_codon(&codon);
+        protein.push(aa);
+        if stop_at_stop && aa == '*' {
+            break;
+        }
+    }
+
+    protein
+}
+
+/// Position of the first stop (`*`) in `protein` that is not the terminal
+/// residue, i.e. a premature stop.
+pub fn has_internal_stop(protein: &str) -> Option<usize> {
+    let body = protein.strip_suffix('*').unwrap_or(protein);
+    body.find('*')
+}
+
+/// Problems found by [`validate_cds`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CdsValidation {
+    /// Length is a whole number of codons
+    pub complete_codons: bool,
+    pub starts_with_start: bool,
+    pub ends_with_stop: bool,
+    /// Residue index of the first premature stop codon, if any
+    pub internal_stop: Option<usize>,
+}
+
+impl CdsValidation {
+    pub fn is_valid(&self) -> bool {
+        self.complete_codons
+            && self.starts_with_start
+            && self.ends_with_stop
+            && self.internal_stop.is_none()
+    }
+}
+
+/// Check that `seq` reads as a single coding sequence under `table`: whole
+/// codons, a start codon, a terminal stop and no premature stops (which point
+/// to a pseudogene or a sequencing error).
+pub fn validate_cds(seq: &str, table: &CodonTable) -> CdsValidation {
+    let seq = seq.to_uppercase();
+    let protein = translate(&seq, table);
+    CdsValidation {
+        complete_codons: !seq.is_empty() && seq.len().is_multiple_of(3),
+        starts_with_start: seq.get(..3).is_some_and(|c| table.is_start_codon(c)),
+        ends_with_stop: protein.ends_with('*'),
+        internal_stop: has_internal_stop(&protein),
+    }
+}
+
+/// Small seeded PRNG (SplitMix64) so generated controls are reproducible
+/// without pulling in an RNG dependency.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform value in `0..bound` (`bound` > 0)
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_f64() * bound as f64) as usize
+    }
+}
+
+/// Generate a random DNA sequence of `len` bases whose expected GC fraction
+/// is `gc_fraction` (clamped to 0.0–1.0). The same seed always gives the same
+/// output.
+pub fn random_dna(len: usize, gc_fraction: f64, seed: u64) -> String {
+    let gc = gc_fraction.clamp(0.0, 1.0);
+    let mut rng = SplitMix64(seed);
+    (0..len)
+        .map(|_| {
+            let strong = rng.next_f64() < gc;
+            let first = rng.next_u64() & 1 == 0;
+            match (strong, first) {
+                (true, true) => 'G',
+                (true, false) => 'C',
+                (false, true) => 'A',
+                (false, false) => 'T',
+            }
+        })
+        .collect()
+}
+
+/// Randomly permute the characters of `seq` (Fisher-Yates), preserving its
+/// exact composition. The same seed always gives the same output.
+pub fn shuffle_preserving_composition(seq: &str, seed: u64) -> String {
+    let mut chars: Vec<char> = seq.chars().collect();
+    let mut rng = SplitMix64(seed);
+    for i in (1..chars.len()).rev() {
+        let j = rng.next_below(i + 1);
+        chars.swap(i, j);
+    }
+    chars.into_iter().collect()
+}
+
+/// Protein translation of one reading frame
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FrameTranslation {
+    /// +1, +2, +3 for the forward strand; -1, -2, -3 for the reverse strand
+    pub frame: i8,
+    pub protein: String,
+}
+
+/// Translate all six reading frames, in the order +1, +2, +3, -1, -2, -3.
+///
+/// Frame +k translates `seq` from offset k-1; frame -k translates the reverse
+/// complement from offset k-1.
+///
+/// In forward-strand coordinates for a sequence of length `len`, residue `i`
+/// of frame +k covers `k-1+3i .. k+2+3i`. Residue `i` of frame -k covers
+/// `len-(k-1)-3(i+1) .. len-(k-1)-3i`: reverse frames start at the right end
+/// of the sequence and read leftwards, so a track drawing them must place
+/// residues right to left.
+pub fn translate_six_frames(seq: &str, table: &CodonTable) -> [FrameTranslation; 6] {
+    let rc = reverse_complement(seq);
+    let frame = |strand: &str, offset: usize, frame: i8| FrameTranslation {
+        frame,
+        protein: strand.get(offset..).map(|s| translate(s, table)).unwrap_or_default(),
+    };
+    [
+        frame(seq, 0, 1),
+        frame(seq, 1, 2),
+        frame(seq, 2, 3),
+        frame(&rc, 0, -1),
+        frame(&rc, 1, -2),
+        frame(&rc, 2, -3),
+    ]
+}
+
+/// Translate one reading frame: +1..+3 on the forward strand, -1..-3 on the
+/// reverse complement (see [`translate_six_frames`]). Other frame numbers
+/// give an empty string.
+pub fn translate_frame(seq: &str, frame: i8, table: &CodonTable) -> String {
+    let offset = (frame.unsigned_abs() as usize).wrapping_sub(1);
+    let strand = match frame {
+        1..=3 => std::borrow::Cow::Borrowed(seq),
+        -3..=-1 => std::borrow::Cow::Owned(reverse_complement(seq)),
+        _ => return String::new(),
+    };
+    strand.get(offset..).map(|s| translate(s, table)).unwrap_or_default()
+}
+
+/// Memoized frame translations, so redrawing an unchanged protein track does
+/// not re-translate the sequence.
+///
+/// Entries are keyed on a 64-bit hash of the sequence plus the frame and the
+/// table's ID and name, so editing the sequence simply misses the old entries.
+/// When `capacity` entries are held the cache is cleared before inserting.
+#[derive(Debug)]
+pub struct TranslationCache {
+    entries: HashMap<(u64, i8), String>,
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl TranslationCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity: capacity.max(1),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Translation of `frame`, from the cache when available
+    pub fn translate_frame(&mut self, seq: &str, frame: i8, table: &CodonTable) -> String {
+        let mut hasher = DefaultHasher::new();
+        seq.hash(&mut hasher);
+        table.id.hash(&mut hasher);
+        table.name.hash(&mut hasher);
+        let key = (hasher.finish(), frame);
+
+        if let Some(protein) = self.entries.get(&key) {
+            self.hits += 1;
+            return protein.clone();
+        }
+        self.misses += 1;
+        if self.entries.len() >= self.capacity {
+            self.entries.clear();
+        }
+        let protein = translate_frame(seq, frame, table);
+        self.entries.insert(key, protein.clone());
+        protein
+    }
+
+    /// All six frames in [`translate_six_frames`] order, through the cache
+    pub fn translate_six_frames(&mut self, seq: &str, table: &CodonTable) -> [FrameTranslation; 6] {
+        [1, 2, 3, -1, -2, -3].map(|frame| FrameTranslation {
+            frame,
+            protein: self.translate_frame(seq, frame, table),
+        })
+    }
+
+    /// Lookups served from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Lookups that had to translate
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Clean pasted input down to a bare sequence in the given alphabet.
+///
+/// Whitespace, digits (e.g. from numbered GenBank/alignment output) and any
+/// other character outside `alphabet` are removed and the result is
+/// uppercased. Gap characters (`-`, `.`) are kept only when `keep_gaps` is set.
+/// Returns the cleaned sequence and the number of characters removed.
+pub fn clean_sequence(input: &str, alphabet: MoleculeType, keep_gaps: bool) -> (String, usize) {
+    let mut cleaned = String::with_capacity(input.len());
+    let mut removed = 0;
+
+    for c in input.chars() {
+        if alphabet.allows(c) || (keep_gaps && is_gap(c)) {
+            cleaned.push(c.to_ascii_uppercase());
+        } else {
+            removed += 1;
+        }
+    }
+
+    (cleaned, removed)
+}
+
+/// Positions (0-based char indices) of characters outside `alphabet`.
+/// Whitespace is ignored so that line-wrapped input is not flagged.
+pub fn validate_alphabet(seq: &str, alphabet: MoleculeType) -> Vec<usize> {
+    seq.chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_whitespace() && !alphabet.allows(*c))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Guess whether a sequence is DNA, RNA or protein.
+///
+/// Letters are considered nucleotides when at least 90% of them are A, C, G,
+/// T, U or N; such a sequence is RNA if it contains U but no T, otherwise DNA.
+/// Anything else is protein. Non-letters are ignored and empty input is DNA.
+pub fn infer_molecule_type(seq: &str) -> MoleculeType {
+    let letters: Vec<char> = seq
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if letters.is_empty() {
+        return MoleculeType::Dna;
+    }
+
+    let nucleotides = letters.iter().filter(|c| "ACGTUN".contains(**c)).count();
+    if nucleotides * 10 < letters.len() * 9 {
+        return MoleculeType::Protein;
+    }
+
+    if letters.contains(&'U') && !letters.contains(&'T') {
+        MoleculeType::Rna
+    } else {
+        MoleculeType::Dna
+    }
+}
+
+/// Number of distinct oligos a degenerate primer represents: the product of
+/// the number of bases each IUPAC code stands for, saturating at `u64::MAX`.
+/// Characters that are not IUPAC nucleotide codes contribute a factor of 1.
+pub fn degeneracy(primer: &str) -> u64 {
+    primer
+        .chars()
+        .filter_map(crate::iupac::expand)
+        .fold(1u64, |acc, bases| acc.saturating_mul(bases.len() as u64))
+}
+
+/// How [`melting_temp`] estimates a melting temperature
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TmMethod {
+    /// Wallace rule, 2 °C per A/T and 4 °C per G/C, for oligos under 14 bases;
+    /// longer oligos use the GC formula `64.9 + 41 * (GC - 16.4) / N`
+    BasicWallace,
+    /// SantaLucia (1998) nearest-neighbor thermodynamics
+    NearestNeighbor(TmParams),
+}
+
+impl Default for TmMethod {
+    fn default() -> Self {
+        TmMethod::NearestNeighbor(TmParams::default())
+    }
+}
+
+/// Melting temperature of `seq` in °C (case-insensitive).
+///
+/// Returns `NaN` when `seq` is empty or contains anything other than A, C, G
+/// or T, so ambiguous primers are never given a misleading Tm.
+pub fn melting_temp(seq: &str, method: TmMethod) -> f64 {
+    match method {
+        TmMethod::NearestNeighbor(params) => crate::primer::tm_nearest_neighbor(seq, &params),
+        TmMethod::BasicWallace => {
+            let seq = seq.to_uppercase();
+            if seq.is_empty() || !seq.bytes().all(|b| matches!(b, b'A' | b'C' | b'G' | b'T')) {
+                return f64::NAN;
+            }
+            let gc = seq.bytes().filter(|b| matches!(b, b'G' | b'C')).count() as f64;
+            let n = seq.len() as f64;
+            if seq.len() < 14 {
+                2.0 * (n - gc) + 4.0 * gc
+            } else {
+                64.9 + 41.0 * (gc - 16.4) / n
+            }
+        }
+    }
+}
+
+/// Longest hairpin loop [`estimate_oligo_dg`] considers
+const OLIGO_MAX_LOOP: usize = 20;
+
+/// Hairpin loop initiation ΔG (kcal/mol) at 37 °C (SantaLucia & Hicks 2004),
+/// extrapolated logarithmically past 9 bases.
+fn hairpin_loop_penalty(loop_len: usize) -> f64 {
+    match loop_len {
+        0..=4 => 3.5,
+        5 => 3.3,
+        6 => 4.0,
+        7 => 4.2,
+        8 => 4.3,
+        9 => 4.5,
+        n => 4.6 + 1.08 * (n as f64 / 10.0).ln(),
+    }
+}
+
+/// Rough secondary-structure ΔG (kcal/mol at 37 °C) of a short oligo from its
+/// most stable self-hairpin: nearest-neighbor stacking over the stem plus a
+/// loop initiation penalty. Negative values mean a stable structure; 0.0 means
+/// no hairpin forms.
+///
+/// This ignores mismatches, bulges, dangling ends and multi-branch structures,
+/// so it is for ranking oligos against each other rather than an exact MFE.
+pub fn estimate_oligo_dg(seq: &str) -> f64 {
+    let upper = seq.to_uppercase();
+    crate::repeats::find_stems(
+        upper.as_bytes(),
+        1,
+        crate::repeats::MIN_HAIRPIN_LOOP,
+        OLIGO_MAX_LOOP,
+    )
+    .iter()
+    .map(|hairpin| {
+        let arm = &upper[hairpin.start..hairpin.start + hairpin.stem_length];
+        let stacking: f64 = (0..arm.len().saturating_sub(1))
+            .filter_map(|k| crate::primer::nn_dg37(&arm[k..k + 2]))
+            .sum();
+        stacking + hairpin_loop_penalty(hairpin.loop_length)
+    })
+    .fold(0.0, f64::min)
+}
+
+/// Molecule form for [`molecular_weight`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoleculeKind {
+    DnaSingleStranded,
+    /// Both strands of a duplex; the second strand is the complement
+    DnaDoubleStranded,
+    Rna,
+    Protein,
+}
+
+/// Mass of water lost per phosphodiester or peptide bond (Da)
+const WATER_MASS: f64 = 18.0153;
+
+/// Average mass (Da) of a deoxynucleoside monophosphate
+fn dna_monophosphate_mass(base: char) -> Option<f64> {
+    match base {
+        'A' => Some(331.2218),
+        'C' => Some(307.1971),
+        'G' => Some(347.2212),
+        'T' => Some(322.2085),
+        _ => None,
+    }
+}
+
+/// Average mass (Da) of a ribonucleoside monophosphate
+fn rna_monophosphate_mass(base: char) -> Option<f64> {
+    match base {
+        'A' => Some(347.2212),
+        'C' => Some(323.1965),
+        'G' => Some(363.2206),
+        'U' => Some(324.1813),
+        _ => None,
+    }
+}
+
+/// Average mass (Da) of an amino acid residue (free amino acid minus water)
+fn amino_acid_residue_mass(aa: char) -> Option<f64> {
+    let free = match aa {
+        'A' => 89.0932,
+        'R' => 174.2010,
+        'N' => 132.1179,
+        'D' => 133.1027,
+        'C' => 121.1582,
+        'Q' => 146.1445,
+        'E' => 147.1293,
+        'G' => 75.0666,
+        'H' => 155.1546,
+        'I' => 131.1729,
+        'L' => 131.1729,
+        'K' => 146.1876,
+        'M' => 149.2113,
+        'F' => 165.1891,
+        'P' => 115.1305,
+        'S' => 105.0926,
+        'T' => 119.1192,
+        'W' => 204.2252,
+        'Y' => 181.1885,
+        'V' => 117.1463,
+        _ => return None,
+    };
+    Some(free - WATER_MASS)
+}
+
+/// Mass of the units in `seq` joined by condensation bonds, plus the number
+/// of characters skipped. With `residue_masses`, `mass` already excludes the
+/// water lost to each bond.
+fn polymer_mass(seq: &str, mass: impl Fn(char) -> Option<f64>, residue_masses: bool) -> (f64, usize) {
+    let mut total = 0.0;
+    let mut units = 0usize;
+    let mut ignored = 0usize;
+    for c in seq.chars().filter(|c| !c.is_whitespace()) {
+        match mass(c.to_ascii_uppercase()) {
+            Some(m) => {
+                total += m;
+                units += 1;
+            }
+            None => ignored += 1,
+        }
+    }
+    if units == 0 {
+        return (0.0, ignored);
+    }
+    let mass = if residue_masses {
+        // Residues already lack the bond water; add back one for the termini
+        total + WATER_MASS
+    } else {
+        total - (units - 1) as f64 * WATER_MASS
+    };
+    (mass, ignored)
+}
+
+/// Average molar mass of `seq` in daltons.
+///
+/// Nucleic acids use monophosphate masses joined by phosphodiester bonds;
+/// proteins use average residue masses of the 20 standard amino acids.
+/// Double-stranded DNA adds the complementary strand. Characters without a
+/// mass (ambiguity codes, gaps, stops) are skipped and their count returned
+/// alongside the mass; whitespace is ignored. An empty sequence weighs 0.0.
+pub fn molecular_weight(seq: &str, kind: MoleculeKind) -> (f64, usize) {
+    match kind {
+        MoleculeKind::DnaSingleStranded => polymer_mass(seq, dna_monophosphate_mass, false),
+        MoleculeKind::DnaDoubleStranded => {
+            let (top, ignored) = polymer_mass(seq, dna_monophosphate_mass, false);
+            let (bottom, _) = polymer_mass(
+                seq,
+                |c| dna_monophosphate_mass(complement_base(c)),
+                false,
+            );
+            (top + bottom, ignored)
+        }
+        MoleculeKind::Rna => polymer_mass(seq, rna_monophosphate_mass, false),
+        MoleculeKind::Protein => polymer_mass(seq, amino_acid_residue_mass, true),
+    }
+}
+
 /// Calculate GC content as a fraction (0.0 to 1.0)
 pub fn gc_content(seq: &str) -> f64 {
     if seq.is_empty() {
@@ -83,34 +678,95 @@ pub struct Orf {
 }
 
 /// Find open reading frames in a sequence
+///
+/// With the `parallel` feature enabled the six frames (and the reverse
+/// complement) are scanned concurrently; the result is identical either way.
 pub fn find_orfs(seq: &str, min_length_aa: usize) -> Vec<Orf> {
+    #[cfg(feature = "parallel")]
+    {
+        find_orfs_parallel(seq, min_length_aa)
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        find_orfs_serial(seq, min_length_aa)
+    }
+}
+
+/// Scan all six frames one after another
+#[cfg_attr(feature = "parallel", allow(dead_code))]
+fn find_orfs_serial(seq: &str, min_length_aa: usize) -> Vec<Orf> {
     let table = CodonTable::standard();
     let upper = seq.to_uppercase();
     let bases: Vec<char> = upper.chars().collect();
-    let mut orfs = Vec::new();
 
     // Forward frames (1, 2, 3)
-    for frame_offset in 0..3 {
-        find_orfs_in_frame(&bases, frame_offset, (frame_offset + 1) as i8, min_length_aa, &table, &mut orfs);
-    }
+    let mut orfs: Vec<Orf> = (0..3)
+        .flat_map(|offset| orfs_in_strand_frame(&bases, offset, false, min_length_aa, &table))
+        .collect();
 
     // Reverse frames (-1, -2, -3)
-    let rc = reverse_complement(&upper);
-    let rc_bases: Vec<char> = rc.chars().collect();
-    for frame_offset in 0..3 {
-        let mut frame_orfs = Vec::new();
-        find_orfs_in_frame(&rc_bases, frame_offset, -(frame_offset as i8 + 1), min_length_aa, &table, &mut frame_orfs);
+    let rc_bases: Vec<char> = reverse_complement(&upper).chars().collect();
+    orfs.extend(
+        (0..3).flat_map(|offset| orfs_in_strand_frame(&rc_bases, offset, true, min_length_aa, &table)),
+    );
+
+    orfs.sort_by_key(|o| o.start);
+    orfs
+}
+
+/// Scan all six frames on the rayon thread pool
+#[cfg(feature = "parallel")]
+fn find_orfs_parallel(seq: &str, min_length_aa: usize) -> Vec<Orf> {
+    use rayon::prelude::*;
+
+    let table = CodonTable::standard();
+    let upper = seq.to_uppercase();
+
+    let scan_strand = |bases: &[char], reverse: bool| -> Vec<Orf> {
+        (0..3)
+            .into_par_iter()
+            .flat_map_iter(|offset| {
+                orfs_in_strand_frame(bases, offset, reverse, min_length_aa, &table)
+            })
+            .collect()
+    };
+
+    let (mut orfs, reverse_orfs) = rayon::join(
+        || scan_strand(&upper.chars().collect::<Vec<char>>(), false),
+        || scan_strand(&reverse_complement(&upper).chars().collect::<Vec<char>>(), true),
+    );
+    orfs.extend(reverse_orfs);
+
+    orfs.sort_by_key(|o| o.start);
+    orfs
+}
+
+/// Find ORFs in one frame of one strand. For the reverse strand `bases` is the
+/// reverse complement and positions are remapped onto the forward strand.
+fn orfs_in_strand_frame(
+    bases: &[char],
+    offset: usize,
+    reverse: bool,
+    min_length_aa: usize,
+    table: &CodonTable,
+) -> Vec<Orf> {
+    let frame = if reverse {
+        -(offset as i8 + 1)
+    } else {
+        offset as i8 + 1
+    };
+    let mut orfs = Vec::new();
+    find_orfs_in_frame(bases, offset, frame, min_length_aa, table, &mut orfs);
+
+    if reverse {
         // Remap positions to the forward strand
-        for orf in &mut frame_orfs {
+        for orf in &mut orfs {
             let new_start = bases.len() - orf.end;
             let new_end = bases.len() - orf.start;
             orf.start = new_start;
             orf.end = new_end;
         }
-        orfs.extend(frame_orfs);
     }
-
-    orfs.sort_by_key(|o| o.start);
     orfs
 }
 
@@ -194,6 +850,129 @@ pub fn replace_bases(seq: &str, start: usize, length: usize, replacement: &str)
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_translation_cache() {
+        let table = CodonTable::standard();
+        let mut cache = TranslationCache::new(64);
+        let seq = "ATGGCTAAAGGTGAAGAACTGTTCACCGGTTAA";
+
+        let first = cache.translate_frame(seq, 1, &table);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        let second = cache.translate_frame(seq, 1, &table);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert_eq!(first, second);
+        assert_eq!(first, translate(seq, &table));
+
+        // A different frame, table or sequence is a miss
+        cache.translate_frame(seq, -2, &table);
+        cache.translate_frame(seq, 1, &CodonTable::bacterial());
+        cache.translate_frame("ATGAAA", 1, &table);
+        assert_eq!((cache.hits(), cache.misses()), (1, 4));
+
+        let frames = cache.translate_six_frames(seq, &table);
+        assert_eq!(frames, translate_six_frames(seq, &table));
+        assert_eq!(cache.hits(), 3);
+    }
+
+    #[test]
+    fn test_melting_temp_nearest_neighbor() {
+        // Reference value from Biopython's Tm_NN (SantaLucia 1998, 50 mM Na+,
+        // 25 nM of each strand)
+        let params = TmParams {
+            na_mm: 50.0,
+            primer_nm: 50.0,
+        };
+        let tm = melting_temp("CGTTCCAAAGATGTGGGCATGAGCTTAC", TmMethod::NearestNeighbor(params));
+        assert!((tm - 60.32).abs() < 1.0, "{}", tm);
+
+        // 20-mer at the default 250 nM
+        let tm = melting_temp("TAATACGACTCACTATAGGG", TmMethod::default());
+        assert!((tm - 46.8).abs() < 1.0, "{}", tm);
+    }
+
+    #[test]
+    fn test_melting_temp_wallace() {
+        assert_eq!(melting_temp("ATGCATGC", TmMethod::BasicWallace), 24.0);
+        assert_eq!(melting_temp("atgcatgc", TmMethod::BasicWallace), 24.0);
+        let long = melting_temp("TAATACGACTCACTATAGGG", TmMethod::BasicWallace);
+        assert!((long - (64.9 + 41.0 * (8.0 - 16.4) / 20.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_melting_temp_rejects_ambiguous() {
+        assert!(melting_temp("ATGNATGC", TmMethod::BasicWallace).is_nan());
+        assert!(melting_temp("ATGRATGCATGCATGC", TmMethod::default()).is_nan());
+        assert!(melting_temp("", TmMethod::BasicWallace).is_nan());
+    }
+
+    #[test]
+    fn test_molecular_weight_plasmid() {
+        // Rule of thumb for dsDNA: 617.96 Da per bp + 36.04
+        let plasmid = random_dna(2686, 0.5, 19);
+        let (mass, ignored) = molecular_weight(&plasmid, MoleculeKind::DnaDoubleStranded);
+        let estimate = 2686.0 * 617.96 + 36.04;
+        assert_eq!(ignored, 0);
+        assert!((mass - estimate).abs() / estimate < 0.01, "{}", mass);
+
+        let (single, _) = molecular_weight(&plasmid, MoleculeKind::DnaSingleStranded);
+        assert!((mass / single - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_molecular_weight_small_molecules() {
+        assert_eq!(molecular_weight("", MoleculeKind::Protein), (0.0, 0));
+        assert_eq!(molecular_weight("", MoleculeKind::DnaDoubleStranded), (0.0, 0));
+
+        // A single residue weighs the same as the free amino acid
+        let (gly, _) = molecular_weight("G", MoleculeKind::Protein);
+        assert!((gly - 75.0666).abs() < 1e-9);
+        let (dipeptide, _) = molecular_weight("GG", MoleculeKind::Protein);
+        assert!((dipeptide - (2.0 * 75.0666 - WATER_MASS)).abs() < 1e-9);
+
+        let (rna, ignored) = molecular_weight("AC-GU*N", MoleculeKind::Rna);
+        assert_eq!(ignored, 3);
+        assert!((rna - molecular_weight("ACGU", MoleculeKind::Rna).0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_has_internal_stop() {
+        assert_eq!(has_internal_stop("MK*LV"), Some(2));
+        assert_eq!(has_internal_stop("MKLV*"), None);
+        assert_eq!(has_internal_stop("MKLV"), None);
+        assert_eq!(has_internal_stop("M**"), Some(1));
+    }
+
+    #[test]
+    fn test_validate_cds() {
+        let table = CodonTable::standard();
+        let good = validate_cds("ATGAAACTGGTTTAA", &table);
+        assert!(good.is_valid());
+
+        let premature = validate_cds("ATGAAATAACTGGTTTAA", &table);
+        assert_eq!(premature.internal_stop, Some(2));
+        assert!(!premature.is_valid());
+
+        let truncated = validate_cds("ATGAAACTGGT", &table);
+        assert!(!truncated.complete_codons);
+        assert!(!truncated.ends_with_stop);
+
+        // A multi-byte character across the first codon must not panic
+        assert!(!validate_cds("ATéGAAATAA", &table).starts_with_start);
+    }
+
+    #[test]
+    fn test_translate_from_stops_at_first_stop() {
+        let table = CodonTable::standard();
+        //         GGG GA|ATG AAA TTT TAA GGC TGA
+        let seq = "GGGGAATGAAATTTTAAGGCTGA";
+        assert_eq!(translate_from(seq, 5, &table, true), "MKF*");
+        assert_eq!(translate_from(seq, 5, &table, false), "MKF*G*");
+
+        // No stop: translate to the last complete codon
+        assert_eq!(translate_from("ATGAAATT", 0, &table, true), "MK");
+        assert_eq!(translate_from("ATG", 10, &table, true), "");
+    }
+
     #[test]
     fn test_complement_base() {
         assert_eq!(complement_base('A'), 'T');
@@ -209,6 +988,45 @@ mod tests {
         assert_eq!(reverse_complement(""), "");
     }
 
+    #[test]
+    fn test_reverse() {
+        assert_eq!(reverse("ATCG"), "GCTA");
+        assert_eq!(reverse(&reverse("ATGCCGTA")), "ATGCCGTA");
+        assert_eq!(reverse(""), "");
+    }
+
+    #[test]
+    fn test_estimate_oligo_dg() {
+        // 8 bp GC stem closing a 4-base loop
+        let hairpin = estimate_oligo_dg("GCGCGCGCAAAAGCGCGCGC");
+        let random = estimate_oligo_dg("ATGACTAGCTTAGCAGTCAA");
+        assert!(hairpin < -10.0, "{}", hairpin);
+        assert!(hairpin < random);
+
+        // A single-base stem cannot overcome the loop penalty
+        assert_eq!(estimate_oligo_dg("AAAAAAAAAA"), 0.0);
+        assert_eq!(estimate_oligo_dg("gcgcgcgcaaaagcgcgcgc"), hairpin);
+        // Longer stems are more stable
+        assert!(estimate_oligo_dg("GCGCGCAAAAGCGCGC") > hairpin);
+    }
+
+    #[test]
+    fn test_transcribe() {
+        assert_eq!(transcribe("ATG"), "AUG");
+        assert_eq!(transcribe("atgcat"), "AUGCAU");
+        assert_eq!(reverse_transcribe("AUGcau"), "ATGCAT");
+        assert_eq!(reverse_transcribe(&transcribe("GATTACA")), "GATTACA");
+    }
+
+    #[test]
+    fn test_translate_rna() {
+        let table = CodonTable::standard();
+        assert_eq!(translate("AUGUAA", &table), "M*");
+        assert_eq!(translate("augUUU", &table), "MF");
+        assert!(table.is_start_codon("AUG"));
+        assert!(table.is_stop_codon("uga"));
+    }
+
     #[test]
     fn test_translate() {
         let table = CodonTable::standard();
@@ -217,6 +1035,56 @@ mod tests {
         assert_eq!(translate("AT", &table), ""); // incomplete codon
     }
 
+    #[test]
+    fn test_translate_with_partial_codon_policy() {
+        let table = CodonTable::standard();
+        assert_eq!(translate_with_policy("ATGA", &table, PartialCodonPolicy::Indicate), "M>");
+        assert_eq!(translate_with_policy("ATGA", &table, PartialCodonPolicy::Drop), "M");
+        assert_eq!(translate_with_policy("ATGAAA", &table, PartialCodonPolicy::Indicate), "MK");
+        assert_eq!(translate_with_policy("AT", &table, PartialCodonPolicy::Indicate), ">");
+        assert_eq!(PartialCodonPolicy::default(), PartialCodonPolicy::Drop);
+    }
+
+    #[test]
+    fn test_clean_sequence() {
+        let (cleaned, removed) = clean_sequence("1 atcg\n2 GGCC", MoleculeType::Dna, false);
+        assert_eq!(cleaned, "ATCGGGCC");
+        assert_eq!(removed, 5);
+
+        let (cleaned, _) = clean_sequence("AC-GU\r\n", MoleculeType::Rna, true);
+        assert_eq!(cleaned, "AC-GU");
+        let (cleaned, _) = clean_sequence("AC-GU", MoleculeType::Rna, false);
+        assert_eq!(cleaned, "ACGU");
+
+        let (cleaned, removed) = clean_sequence("mkv*", MoleculeType::Protein, false);
+        assert_eq!(cleaned, "MKV*");
+        assert_eq!(removed, 0);
+    }
+
+    #[test]
+    fn test_validate_alphabet() {
+        assert!(validate_alphabet("ACGT\nNNRY", MoleculeType::Dna).is_empty());
+        assert_eq!(validate_alphabet("ACXGTZ", MoleculeType::Dna), vec![2, 5]);
+        assert_eq!(validate_alphabet("ACGU", MoleculeType::Dna), vec![3]);
+    }
+
+    #[test]
+    fn test_infer_molecule_type() {
+        assert_eq!(infer_molecule_type("ATGCATGCNN"), MoleculeType::Dna);
+        assert_eq!(infer_molecule_type("AUGCAUGC"), MoleculeType::Rna);
+        assert_eq!(infer_molecule_type("MKVLSEGEWQLV"), MoleculeType::Protein);
+        assert_eq!(infer_molecule_type(""), MoleculeType::Dna);
+    }
+
+    #[test]
+    fn test_degeneracy() {
+        assert_eq!(degeneracy("ARNGG"), 8);
+        assert_eq!(degeneracy("ACGT"), 1);
+        assert_eq!(degeneracy("NNN"), 64);
+        assert_eq!(degeneracy(""), 1);
+        assert_eq!(degeneracy(&"N".repeat(40)), u64::MAX);
+    }
+
     #[test]
     fn test_gc_content() {
         assert!((gc_content("ATCG") - 0.5).abs() < f64::EPSILON);
@@ -241,6 +1109,28 @@ mod tests {
         assert_eq!(orfs[0].protein, "MK");
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_find_orfs_parallel_matches_serial() {
+        // Deterministic pseudo-random sequence long enough to contain many ORFs
+        let mut state: u32 = 12345;
+        let seq: String = (0..50_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                ['A', 'C', 'G', 'T'][(state >> 16) as usize % 4]
+            })
+            .collect();
+
+        let serial = find_orfs_serial(&seq, 30);
+        let parallel = find_orfs_parallel(&seq, 30);
+        assert!(!serial.is_empty());
+        assert_eq!(serial.len(), parallel.len());
+        for (a, b) in serial.iter().zip(&parallel) {
+            assert_eq!((a.start, a.end, a.frame), (b.start, b.end, b.frame));
+            assert_eq!(a.protein, b.protein);
+        }
+    }
+
     #[test]
     fn test_insert_bases() {
         assert_eq!(insert_bases("AACCTTGG", 4, "XX"), "AACCXXTTGG");
@@ -258,4 +1148,72 @@ mod tests {
     fn test_replace_bases() {
         assert_eq!(replace_bases("AACCTTGG", 2, 4, "XX"), "AAXXGG");
     }
+
+    #[test]
+    fn test_translate_six_frames_order() {
+        let table = CodonTable::standard();
+        let frames = translate_six_frames("ATGAAATAG", &table);
+        let labels: Vec<i8> = frames.iter().map(|f| f.frame).collect();
+        assert_eq!(labels, vec![1, 2, 3, -1, -2, -3]);
+        assert_eq!(frames[0].protein, "MK*");
+        assert_eq!(frames[3].protein, translate("CTATTTCAT", &table));
+        assert!(translate_six_frames("AT", &table).iter().all(|f| f.protein.is_empty()));
+    }
+
+    #[test]
+    fn test_six_frames_forward_coordinates() {
+        let table = CodonTable::standard();
+        let seq = "GCTATTTCATGGCC";
+        let frames = translate_six_frames(seq, &table);
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[3].protein, translate(&reverse_complement(seq), &table));
+
+        // Residue i of frame -k starts at len-(k-1)-3(i+1) on the forward strand
+        let len = seq.len();
+        for (k, frame) in (1..=3).zip(&frames[3..]) {
+            for (i, aa) in frame.protein.chars().enumerate() {
+                let start = len - (k - 1) - 3 * (i + 1);
+                let codon = reverse_complement(&seq[start..start + 3]);
+                assert_eq!(table.translate_codon(&codon), aa, "frame -{} residue {}", k, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_dna() {
+        let a = random_dna(10_000, 0.7, 42);
+        assert_eq!(a.len(), 10_000);
+        assert_eq!(a, random_dna(10_000, 0.7, 42));
+        assert_ne!(a, random_dna(10_000, 0.7, 43));
+        let gc = gc_content(&a);
+        assert!((gc - 0.7).abs() < 0.03, "gc = {}", gc);
+        assert!(random_dna(100, 0.0, 1).chars().all(|c| c == 'A' || c == 'T'));
+        assert!(random_dna(100, 1.0, 1).chars().all(|c| c == 'G' || c == 'C'));
+        assert!(random_dna(0, 0.5, 1).is_empty());
+    }
+
+    #[test]
+    fn test_shuffle_preserving_composition() {
+        let seq = "AAAACCCGGTTTTTTACGNN";
+        let shuffled = shuffle_preserving_composition(seq, 7);
+        let counts = |s: &str| {
+            let mut c: Vec<char> = s.chars().collect();
+            c.sort_unstable();
+            c
+        };
+        assert_eq!(counts(&shuffled), counts(seq));
+        assert_ne!(shuffled, seq);
+        assert_eq!(shuffled, shuffle_preserving_composition(seq, 7));
+        assert_eq!(shuffle_preserving_composition("", 7), "");
+    }
+
+    #[test]
+    fn test_gapped_positions() {
+        assert_eq!(ungapped_len("AC-GT"), 4);
+        assert_eq!(ungapped_len("--.."), 0);
+        assert_eq!(gapped_to_ungapped_position("AC-GT", 0), Some(0));
+        assert_eq!(gapped_to_ungapped_position("AC-GT", 2), None);
+        assert_eq!(gapped_to_ungapped_position("AC-GT", 3), Some(2));
+        assert_eq!(gapped_to_ungapped_position("AC-GT", 5), None);
+    }
 }
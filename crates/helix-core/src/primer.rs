@@ -0,0 +1,331 @@
+//! Primer design: melting temperature, assembly overlaps and mutagenesis.
+
+use std::collections::HashMap;
+
+use crate::codon::CodonTable;
+use crate::operations::reverse_complement;
+
+/// Gas constant in cal/(K·mol)
+const R: f64 = 1.987;
+
+/// Reaction conditions for melting temperature calculations
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TmParams {
+    /// Monovalent cation (Na+) concentration in mM
+    pub na_mm: f64,
+    /// Total primer strand concentration in nM
+    pub primer_nm: f64,
+}
+
+impl Default for TmParams {
+    fn default() -> Self {
+        Self {
+            na_mm: 50.0,
+            primer_nm: 250.0,
+        }
+    }
+}
+
+/// SantaLucia (1998) unified nearest-neighbor ΔH (kcal/mol) and ΔS
+/// (cal/K·mol) for a dinucleotide step, read 5'→3' on the top strand. Steps
+/// not in the table are looked up by their reverse complement.
+fn nn_step(step: &str) -> Option<(f64, f64)> {
+    let params = match step {
+        "AA" => (-7.9, -22.2),
+        "AT" => (-7.2, -20.4),
+        "TA" => (-7.2, -21.3),
+        "CA" => (-8.5, -22.7),
+        "GT" => (-8.4, -22.4),
+        "CT" => (-7.8, -21.0),
+        "GA" => (-8.2, -22.2),
+        "CG" => (-10.6, -27.2),
+        "GC" => (-9.8, -24.4),
+        "GG" => (-8.0, -19.9),
+        _ => return None,
+    };
+    Some(params)
+}
+
+/// Free energy (kcal/mol) at 37 °C of a Watson-Crick stacking step, from the
+/// same nearest-neighbor table. `None` for steps with non-ACGT bases.
+pub(crate) fn nn_dg37(step: &str) -> Option<f64> {
+    let (dh, ds) = nn_step(step).or_else(|| nn_step(&reverse_complement(step)))?;
+    Some(dh - 310.15 * ds / 1000.0)
+}
+
+/// Initiation ΔH and ΔS for a terminal base pair
+fn terminal_init(base: u8) -> (f64, f64) {
+    match base {
+        b'G' | b'C' => (0.1, -2.8),
+        _ => (2.3, 4.1),
+    }
+}
+
+/// Melting temperature (°C) of a primer against its perfect complement using
+/// SantaLucia (1998) unified nearest-neighbor parameters with the SantaLucia
+/// entropy salt correction.
+///
+/// Case-insensitive. Returns `NaN` for sequences shorter than 2 bases or
+/// containing anything other than A, C, G or T.
+pub fn tm_nearest_neighbor(seq: &str, params: &TmParams) -> f64 {
+    let seq = seq.to_uppercase();
+    let bases = seq.as_bytes();
+    if bases.len() < 2 || !bases.iter().all(|b| matches!(b, b'A' | b'C' | b'G' | b'T')) {
+        return f64::NAN;
+    }
+
+    let (mut dh, mut ds) = (0.0, 0.0);
+    for i in 0..bases.len() - 1 {
+        let step = &seq[i..i + 2];
+        let (h, s) = nn_step(step)
+            .or_else(|| nn_step(&reverse_complement(step)))
+            .expect("all ACGT dinucleotides are covered");
+        dh += h;
+        ds += s;
+    }
+    for &end in [bases[0], bases[bases.len() - 1]].iter() {
+        let (h, s) = terminal_init(end);
+        dh += h;
+        ds += s;
+    }
+
+    // Self-complementary primers anneal to themselves: symmetry penalty and
+    // the full strand concentration instead of a quarter of it.
+    let self_complementary = reverse_complement(&seq) == seq;
+    let divisor = if self_complementary {
+        ds -= 1.4;
+        1.0
+    } else {
+        4.0
+    };
+
+    ds += 0.368 * (bases.len() - 1) as f64 * (params.na_mm / 1000.0).ln();
+    let ct = params.primer_nm * 1e-9 / divisor;
+    dh * 1000.0 / (ds + R * ct.ln()) - 273.15
+}
+
+/// Shortest overlap [`design_gibson_overlap`] will trim down to
+pub const MIN_GIBSON_OVERLAP: usize = 15;
+
+/// Overlap sequence shared by two fragments at a Gibson assembly junction
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GibsonJunction {
+    /// Overlap sequence, reading across the junction from `left` into `right`
+    pub overlap: String,
+    /// Bases taken from the 3' end of the left fragment
+    pub from_left: usize,
+    /// Bases taken from the 5' end of the right fragment
+    pub from_right: usize,
+    /// Nearest-neighbor Tm of the overlap at default conditions
+    pub tm: f64,
+}
+
+/// Design the overlap for joining `left` to `right` by Gibson assembly.
+///
+/// The overlap is centered on the junction, taking half of `overlap_len` from
+/// the end of `left` and the rest from the start of `right` (less if a
+/// fragment is too short). While its Tm is above `target_tm` it is trimmed
+/// from alternating outer ends, never below [`MIN_GIBSON_OVERLAP`] bases, so
+/// the result is the shortest overlap that still reaches the target.
+///
+/// Returns `None` if either fragment contains non-ASCII characters.
+pub fn design_gibson_overlap(
+    left: &str,
+    right: &str,
+    overlap_len: usize,
+    target_tm: f64,
+) -> Option<GibsonJunction> {
+    if !left.is_ascii() || !right.is_ascii() {
+        return None;
+    }
+    let params = TmParams::default();
+    let left = left.to_uppercase();
+    let right = right.to_uppercase();
+
+    let mut from_left = (overlap_len / 2).min(left.len());
+    let mut from_right = (overlap_len - from_left).min(right.len());
+    let junction = |from_left: usize, from_right: usize| {
+        format!("{}{}", &left[left.len() - from_left..], &right[..from_right])
+    };
+
+    let mut overlap = junction(from_left, from_right);
+    let mut tm = tm_nearest_neighbor(&overlap, &params);
+    while from_left + from_right > MIN_GIBSON_OVERLAP {
+        let (l, r) = if from_left >= from_right && from_left > 0 {
+            (from_left - 1, from_right)
+        } else {
+            (from_left, from_right - 1)
+        };
+        let candidate = junction(l, r);
+        let candidate_tm = tm_nearest_neighbor(&candidate, &params);
+        if candidate_tm.is_nan() || candidate_tm < target_tm {
+            break;
+        }
+        (from_left, from_right, overlap, tm) = (l, r, candidate, candidate_tm);
+    }
+
+    Some(GibsonJunction {
+        overlap,
+        from_left,
+        from_right,
+        tm,
+    })
+}
+
+/// Suggested codon change for a site-directed amino acid substitution
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MutationPlan {
+    /// 0-based residue index in the CDS
+    pub residue_index: usize,
+    pub current_codon: String,
+    pub current_aa: char,
+    pub new_codon: String,
+    pub new_aa: char,
+    /// 0-based nucleotide positions in the CDS that change
+    pub edit_positions: Vec<usize>,
+}
+
+/// Plan the smallest codon edit that changes residue `residue_index` of `cds`
+/// to `new_aa`.
+///
+/// Among the codons for `new_aa` the fewest base changes win; ties go to the
+/// codon used most often elsewhere in `cds` (as a stand-in for the host's
+/// codon usage), then to alphabetical order. Returns `None` when the residue is
+/// past the last complete codon or `table` has no codon for `new_aa`.
+pub fn plan_point_mutation(
+    cds: &str,
+    residue_index: usize,
+    new_aa: char,
+    table: &CodonTable,
+) -> Option<MutationPlan> {
+    let cds = cds.to_uppercase();
+    let new_aa = new_aa.to_ascii_uppercase();
+    let start = residue_index.checked_mul(3)?;
+    let current_codon = cds.get(start..start.checked_add(3)?)?.to_string();
+
+    let mut usage: HashMap<&str, usize> = HashMap::new();
+    for codon in cds.as_bytes().chunks_exact(3) {
+        if let Ok(codon) = std::str::from_utf8(codon) {
+            *usage.entry(codon).or_insert(0) += 1;
+        }
+    }
+
+    let differences = |codon: &str| -> Vec<usize> {
+        codon
+            .bytes()
+            .zip(current_codon.bytes())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| start + i)
+            .collect()
+    };
+
+    let new_codon = table
+        .codons()
+        .iter()
+        .filter(|(_, &aa)| aa == new_aa)
+        .map(|(codon, _)| codon.as_str())
+        .min_by_key(|codon| {
+            let uses = usage.get(codon).copied().unwrap_or(0);
+            (differences(codon).len(), std::cmp::Reverse(uses), *codon)
+        })?
+        .to_string();
+
+    Some(MutationPlan {
+        residue_index,
+        current_aa: table.translate_codon(&current_codon),
+        edit_positions: differences(&new_codon),
+        current_codon,
+        new_codon,
+        new_aa,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tm_nearest_neighbor() {
+        let params = TmParams::default();
+        let tm = tm_nearest_neighbor("AGCGGATAACAATTTCACACAGGA", &params);
+        assert!((tm - 56.7).abs() < 0.1, "{}", tm);
+        assert_eq!(tm, tm_nearest_neighbor("agcggataacaatttcacacagga", &params));
+
+        // GC-rich primers melt higher than AT-rich ones of the same length
+        let at = tm_nearest_neighbor("AAAAAAAAAATTTTTTTTTT", &params);
+        let gc = tm_nearest_neighbor("GCGCGCGCGCATATATAT", &params);
+        assert!(gc > at + 10.0);
+
+        // More salt stabilizes the duplex
+        let high_salt = TmParams { na_mm: 200.0, ..params };
+        assert!(tm_nearest_neighbor("AGCGGATAACAATTTCACACAGGA", &high_salt) > tm);
+    }
+
+    #[test]
+    fn test_gibson_overlap_requested_length() {
+        let left = "TTTTTTTTTTAGCGGATAACAATTTCACAC";
+        let right = "AGGAAACAGCTATGACCATGAAAAAAAAAA";
+        let junction = design_gibson_overlap(left, right, 30, 90.0).unwrap();
+        assert_eq!(junction.overlap.len(), 30);
+        assert_eq!((junction.from_left, junction.from_right), (15, 15));
+        assert_eq!(junction.overlap, "ATAACAATTTCACACAGGAAACAGCTATGA");
+        assert_eq!(
+            junction.tm,
+            tm_nearest_neighbor(&junction.overlap, &TmParams::default())
+        );
+    }
+
+    #[test]
+    fn test_gibson_overlap_trims_to_target() {
+        let left = "GCGCGGCCGCGGCCGCGCGGATCCGCGGCCGCGG";
+        let right = "CCGCGGCCGCGGATCCGCGGCCGCGCGCGGCCGC";
+        let full = design_gibson_overlap(left, right, 40, 200.0).unwrap();
+        let trimmed = design_gibson_overlap(left, right, 40, 60.0).unwrap();
+        assert!(trimmed.overlap.len() < full.overlap.len());
+        assert!(trimmed.overlap.len() >= MIN_GIBSON_OVERLAP);
+        assert!(trimmed.tm >= 60.0);
+        assert!(full.overlap.contains(&trimmed.overlap));
+    }
+
+    #[test]
+    fn test_gibson_overlap_rejects_non_ascii() {
+        assert!(design_gibson_overlap("ACGTACGTACGTACGTACé", "ACGTACGT", 20, 50.0).is_none());
+        assert!(design_gibson_overlap("ACGTACGT", "éACGTACGTACGT", 20, 50.0).is_none());
+    }
+
+    #[test]
+    fn test_plan_point_mutation_single_base() {
+        let table = CodonTable::standard();
+        //         M   E   K   L   *
+        let cds = "ATGGAAAAACTGTAA";
+
+        // E (GAA) -> D: GAT and GAC are both one edit away
+        let plan = plan_point_mutation(cds, 1, 'D', &table).unwrap();
+        assert_eq!(plan.current_codon, "GAA");
+        assert_eq!(plan.current_aa, 'E');
+        assert_eq!(plan.new_codon, "GAC");
+        assert_eq!(plan.edit_positions, vec![5]);
+
+        // K (AAA) -> R: AGA is one edit, CGN would take two
+        let plan = plan_point_mutation(cds, 2, 'r', &table).unwrap();
+        assert_eq!(plan.new_codon, "AGA");
+        assert_eq!(plan.edit_positions, vec![7]);
+
+        // Ties prefer codons already used in the CDS
+        let plan = plan_point_mutation("ATGGAAGATGATTAA", 1, 'D', &table).unwrap();
+        assert_eq!(plan.new_codon, "GAT");
+
+        assert!(plan_point_mutation(cds, 5, 'A', &table).is_none());
+        assert!(plan_point_mutation(cds, 1, 'B', &table).is_none());
+        assert!(plan_point_mutation(cds, usize::MAX / 3, 'A', &table).is_none());
+    }
+
+    #[test]
+    fn test_tm_invalid_input() {
+        let params = TmParams::default();
+        assert!(tm_nearest_neighbor("ACGNACGT", &params).is_nan());
+        assert!(tm_nearest_neighbor("A", &params).is_nan());
+        assert!(tm_nearest_neighbor("", &params).is_nan());
+    }
+}
@@ -0,0 +1,120 @@
+//! Sigma70 promoter element detection for bacterial promoters.
+
+use crate::search::find_pattern_mismatches;
+
+/// Consensus -35 box.
+pub const MINUS_35_CONSENSUS: &str = "TTGACA";
+/// Consensus -10 (Pribnow) box.
+pub const MINUS_10_CONSENSUS: &str = "TATAAT";
+/// Allowed spacer between the end of the -35 box and the start of the -10 box.
+pub const SPACER_RANGE: std::ops::RangeInclusive<usize> = 15..=19;
+/// Spacer length of the strongest promoters.
+const OPTIMAL_SPACER: usize = 17;
+/// Most mismatches tolerated in each box.
+const MAX_BOX_MISMATCHES: usize = 2;
+
+/// The -35 and -10 boxes found in a promoter region.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PromoterModel {
+    /// Start of the -35 box in the full sequence (0-based).
+    pub minus35_start: usize,
+    /// Start of the -10 box in the full sequence (0-based).
+    pub minus10_start: usize,
+    /// Bases between the end of the -35 box and the start of the -10 box.
+    pub spacer: usize,
+    /// Mismatches from `TTGACA`.
+    pub minus35_mismatches: usize,
+    /// Mismatches from `TATAAT`.
+    pub minus10_mismatches: usize,
+    /// 0.0 to 1.0: the fraction of the 12 consensus box bases matched, less
+    /// 0.05 for each base the spacer differs from 17.
+    pub score: f64,
+}
+
+/// Find the best-scoring pair of -35 and -10 boxes on the forward strand of
+/// `seq[region.0..region.1]`.
+///
+/// Each box may differ from its consensus in up to two bases, and the spacer
+/// between them must be 15 to 19 bases. Positions are reported in `seq`
+/// coordinates. Returns `None` if no such pair exists in the region. For a
+/// promoter on the reverse strand, pass the reverse complement.
+pub fn find_promoter_elements(seq: &str, region: (usize, usize)) -> Option<PromoterModel> {
+    let (start, end) = (region.0.min(seq.len()), region.1.min(seq.len()));
+    let window = seq.get(start..end)?;
+
+    let boxes = |consensus: &str| {
+        find_pattern_mismatches(window, consensus, MAX_BOX_MISMATCHES, false)
+            .into_iter()
+            .filter(|m| !m.is_complement)
+            .map(|m| {
+                let mismatches = m.matched.bytes().zip(consensus.bytes()).filter(|(a, b)| a != b);
+                (m.start, mismatches.count())
+            })
+            .collect::<Vec<_>>()
+    };
+    let minus35 = boxes(MINUS_35_CONSENSUS);
+    let minus10 = boxes(MINUS_10_CONSENSUS);
+
+    let mut best: Option<PromoterModel> = None;
+    for &(pos35, mm35) in &minus35 {
+        for &(pos10, mm10) in &minus10 {
+            let Some(spacer) = pos10.checked_sub(pos35 + MINUS_35_CONSENSUS.len()) else {
+                continue;
+            };
+            if !SPACER_RANGE.contains(&spacer) {
+                continue;
+            }
+            let matched = 12 - mm35 - mm10;
+            let score = matched as f64 / 12.0 - 0.05 * spacer.abs_diff(OPTIMAL_SPACER) as f64;
+            if best.as_ref().is_none_or(|b| score > b.score) {
+                best = Some(PromoterModel {
+                    minus35_start: start + pos35,
+                    minus10_start: start + pos10,
+                    spacer,
+                    minus35_mismatches: mm35,
+                    minus10_mismatches: mm10,
+                    score,
+                });
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_promoter() {
+        let upstream = "GCGCGCGCGC";
+        let spacer = "GCGGCGCGCGGCGCGGC"; // 17 bp
+        let seq = format!("{}TTGACA{}TATAATGCGCGCGC", upstream, spacer);
+
+        let model = find_promoter_elements(&seq, (0, seq.len())).unwrap();
+        assert_eq!(model.minus35_start, 10);
+        assert_eq!(model.minus10_start, 33);
+        assert_eq!(model.spacer, 17);
+        assert_eq!((model.minus35_mismatches, model.minus10_mismatches), (0, 0));
+        assert_eq!(model.score, 1.0);
+
+        // Positions stay in full-sequence coordinates for a sub-region
+        let model = find_promoter_elements(&seq, (5, seq.len())).unwrap();
+        assert_eq!((model.minus35_start, model.minus10_start), (10, 33));
+    }
+
+    #[test]
+    fn test_spacing_and_mismatches() {
+        let spacer = "GCGGCGCGCGGCGCGGC";
+        // Weaker boxes are still found, with a lower score
+        let seq = format!("GCGCTTGACT{}TAAAATGCGC", spacer);
+        let model = find_promoter_elements(&seq, (0, seq.len())).unwrap();
+        assert_eq!((model.minus35_mismatches, model.minus10_mismatches), (1, 1));
+        assert!(model.score < 1.0 && model.score > 0.8);
+
+        // A 10 bp spacer is too short
+        let seq = format!("GCGCTTGACA{}TATAATGCGC", &spacer[..10]);
+        assert!(find_promoter_elements(&seq, (0, seq.len())).is_none());
+        assert!(find_promoter_elements(&seq, (30, 10)).is_none());
+    }
+}
@@ -0,0 +1,294 @@
+//! Inverted repeat, hairpin, tandem repeat and homopolymer detection.
+
+use crate::operations::complement_base;
+
+/// An inverted repeat: a stem whose reverse complement follows it downstream,
+/// separated by a loop. With a loop of 0 this is a palindromic site (GAATTC).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct InvertedRepeat {
+    /// Start of the upstream stem (0-based, inclusive).
+    pub start: usize,
+    /// End of the downstream stem (0-based, exclusive).
+    pub end: usize,
+    /// Number of paired bases in each arm of the stem.
+    pub stem_length: usize,
+    /// Number of unpaired bases between the two arms.
+    pub loop_length: usize,
+}
+
+/// Smallest loop that can physically close a hairpin.
+pub(crate) const MIN_HAIRPIN_LOOP: usize = 3;
+
+/// Find inverted repeats with a stem of at least `min_stem` bases and a loop
+/// of at most `max_loop` bases.
+///
+/// Each stem is extended as far as it pairs, so a hairpin is reported once
+/// with its full stem rather than once per nested sub-stem.
+pub fn find_inverted_repeats(seq: &str, min_stem: usize, max_loop: usize) -> Vec<InvertedRepeat> {
+    find_stems(seq.to_uppercase().as_bytes(), min_stem, 0, max_loop)
+}
+
+/// Find inverted repeats whose loops are in `min_loop..=max_loop`.
+pub(crate) fn find_stems(bases: &[u8], min_stem: usize, min_loop: usize, max_loop: usize) -> Vec<InvertedRepeat> {
+    let n = bases.len();
+    let pairs = |a: u8, b: u8| complement_base(a as char) == b as char && a != b'N';
+    let mut repeats = Vec::new();
+
+    // `i` is the end of the upstream arm, `i + loop_len` the start of the downstream arm
+    for i in 1..n {
+        for loop_len in min_loop..=max_loop {
+            let j = i + loop_len;
+            if j >= n {
+                break;
+            }
+
+            // Skip if the stem could continue into the loop: the same hairpin is
+            // reported with the smaller loop instead.
+            if loop_len >= min_loop + 2 && pairs(bases[i], bases[j - 1]) {
+                continue;
+            }
+
+            let mut stem = 0;
+            while stem < i && j + stem < n && pairs(bases[i - 1 - stem], bases[j + stem]) {
+                stem += 1;
+            }
+
+            if stem >= min_stem.max(1) {
+                repeats.push(InvertedRepeat {
+                    start: i - stem,
+                    end: j + stem,
+                    stem_length: stem,
+                    loop_length: loop_len,
+                });
+            }
+        }
+    }
+
+    repeats.sort_by_key(|r| (r.start, r.end));
+    repeats
+}
+
+/// Longest hairpin stem in `seq` with a loop of 3 to `max_loop` bases (0 if none).
+pub fn best_hairpin_stem(seq: &str, max_loop: usize) -> usize {
+    find_stems(seq.to_uppercase().as_bytes(), 1, MIN_HAIRPIN_LOOP, max_loop)
+        .iter()
+        .map(|r| r.stem_length)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Slide a window of `window` bases along `seq` and return the windows
+/// `(start, end)` whose strongest internal hairpin has a stem of at most
+/// `max_stem_score` base pairs. Hairpin loops of 3–10 bases are considered.
+/// Sequences containing non-ASCII characters have no windows.
+///
+/// Useful for screening guide RNA or primer candidates for local structure.
+pub fn hairpin_free_windows(seq: &str, window: usize, max_stem_score: usize) -> Vec<(usize, usize)> {
+    const MAX_LOOP: usize = 10;
+
+    if window == 0 || seq.len() < window || !seq.is_ascii() {
+        return Vec::new();
+    }
+
+    (0..=seq.len() - window)
+        .filter(|&start| best_hairpin_stem(&seq[start..start + window], MAX_LOOP) <= max_stem_score)
+        .map(|start| (start, start + window))
+        .collect()
+}
+
+/// A run of a short unit repeated head to tail, such as a microsatellite.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TandemRepeat {
+    /// Start of the first copy (0-based, inclusive).
+    pub start: usize,
+    /// End of the last complete copy (0-based, exclusive).
+    pub end: usize,
+    /// The repeating unit, uppercased.
+    pub unit: String,
+    /// Number of complete copies of `unit`.
+    pub copy_number: usize,
+}
+
+/// Find runs of at least `min_copies` consecutive copies of a unit between
+/// `min_unit` and `max_unit` bases long.
+///
+/// At each start the longest run is kept, preferring the shortest unit when
+/// several describe the same run ("CA" rather than "CACA"). A run that lies
+/// inside one already reported (the same repeat read from a later phase) is
+/// skipped.
+pub fn find_tandem_repeats(
+    seq: &str,
+    min_unit: usize,
+    max_unit: usize,
+    min_copies: usize,
+) -> Vec<TandemRepeat> {
+    let bases = seq.to_uppercase().into_bytes();
+    let n = bases.len();
+    let min_unit = min_unit.max(1);
+    let min_copies = min_copies.max(2);
+    let mut repeats = Vec::new();
+    // End of the furthest-reaching run reported so far, including any
+    // trailing partial copy
+    let mut covered = 0;
+
+    for start in 0..n {
+        // (unit length, run length including a trailing partial copy)
+        let mut best: Option<(usize, usize)> = None;
+        for unit in min_unit..=max_unit.min(n - start) {
+            let mut run = unit;
+            while start + run < n && bases[start + run] == bases[start + run - unit] {
+                run += 1;
+            }
+            if run / unit < min_copies {
+                continue;
+            }
+            if best.is_none_or(|(_, best_run)| run > best_run) {
+                best = Some((unit, run));
+            }
+        }
+
+        let Some((unit, run)) = best else { continue };
+        if start + run <= covered {
+            continue;
+        }
+        covered = start + run;
+        let copy_number = run / unit;
+        repeats.push(TandemRepeat {
+            start,
+            end: start + copy_number * unit,
+            unit: String::from_utf8_lossy(&bases[start..start + unit]).into_owned(),
+            copy_number,
+        });
+    }
+
+    repeats
+}
+
+/// Runs of a single base at least `min_run` long, as `(start, base, length)`
+/// with the base uppercased. Useful for flagging regions that are hard to
+/// sequence or synthesize.
+pub fn find_homopolymers(seq: &str, min_run: usize) -> Vec<(usize, char, usize)> {
+    let bases = seq.as_bytes();
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < bases.len() {
+        let base = bases[start].to_ascii_uppercase();
+        let len = bases[start..]
+            .iter()
+            .take_while(|b| b.to_ascii_uppercase() == base)
+            .count();
+        if len >= min_run.max(1) {
+            runs.push((start, base as char, len));
+        }
+        start += len;
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palindromic_site() {
+        let repeats = find_inverted_repeats("TTGAATTCTT", 3, 0);
+        assert!(repeats
+            .iter()
+            .any(|r| r.start == 2 && r.end == 8 && r.stem_length == 3 && r.loop_length == 0));
+    }
+
+    #[test]
+    fn test_hairpin() {
+        let repeats = find_inverted_repeats("GGGGAAAACCCC", 4, 10);
+        assert_eq!(
+            repeats,
+            vec![InvertedRepeat {
+                start: 0,
+                end: 12,
+                stem_length: 4,
+                loop_length: 4,
+            }]
+        );
+
+        // Stem shorter than required, or loop longer than allowed
+        assert!(find_inverted_repeats("GGGGAAAACCCC", 5, 10).is_empty());
+        assert!(find_inverted_repeats("GGGGAAAACCCC", 4, 3).is_empty());
+
+        // Case-insensitive, and found inside flanking sequence
+        let repeats = find_inverted_repeats("ttggggaaaaccccaa", 4, 10);
+        assert!(repeats
+            .iter()
+            .any(|r| r.start <= 2 && r.end >= 14 && r.loop_length == 4));
+    }
+
+    #[test]
+    fn test_best_hairpin_stem() {
+        assert_eq!(best_hairpin_stem("GGGGGAAAACCCCC", 10), 5);
+        assert_eq!(best_hairpin_stem("AAAAAAAAAAAA", 10), 0);
+    }
+
+    #[test]
+    fn test_hairpin_free_windows() {
+        // 20 bp without structure, then a strong designed hairpin
+        let plain = "ACAACAACAACAACAACAAC";
+        let hairpin = "GCGCGCGAAAACGCGCGC";
+        let seq = format!("{}{}", plain, hairpin);
+
+        let windows = hairpin_free_windows(&seq, 18, 3);
+        assert!(windows.contains(&(0, 18)), "plain window should pass");
+        assert!(
+            !windows.contains(&(20, 38)),
+            "window containing the hairpin should be excluded"
+        );
+    }
+
+    #[test]
+    fn test_hairpin_free_windows_short_input() {
+        assert!(hairpin_free_windows("ACGT", 10, 3).is_empty());
+        assert!(hairpin_free_windows("ACGT", 0, 3).is_empty());
+        assert!(hairpin_free_windows("ACGéTACGT", 4, 3).is_empty());
+    }
+
+    #[test]
+    fn test_tandem_repeats() {
+        let repeats = find_tandem_repeats("ggCACACACAtt", 2, 6, 3);
+        assert_eq!(
+            repeats,
+            vec![TandemRepeat { start: 2, end: 10, unit: "CA".to_string(), copy_number: 4 }]
+        );
+
+        let repeats = find_tandem_repeats("ATGATGATG", 2, 6, 3);
+        assert_eq!(repeats.len(), 1);
+        assert_eq!((repeats[0].unit.as_str(), repeats[0].copy_number), ("ATG", 3));
+        assert_eq!((repeats[0].start, repeats[0].end), (0, 9));
+    }
+
+    #[test]
+    fn test_tandem_repeats_min_copies() {
+        assert!(find_tandem_repeats("CACA", 2, 4, 3).is_empty());
+        assert!(find_tandem_repeats("ACGTAGCA", 1, 4, 2).is_empty());
+        // A trailing partial copy does not produce a second, shifted report
+        let repeats = find_tandem_repeats("CACACACAC", 2, 2, 3);
+        assert_eq!(repeats.len(), 1);
+        assert_eq!(repeats[0].copy_number, 4);
+    }
+
+    #[test]
+    fn test_homopolymers() {
+        assert_eq!(
+            find_homopolymers("GCAAAAATcgggggT", 5),
+            vec![(2, 'A', 5), (9, 'G', 5)]
+        );
+        assert!(find_homopolymers("GCAAAAT", 5).is_empty());
+        assert_eq!(find_homopolymers("", 3), vec![]);
+    }
+
+    #[test]
+    fn test_trinucleotide_repeat() {
+        let repeats = find_tandem_repeats("TTCAGCAGCAGTT", 3, 3, 3);
+        assert_eq!(
+            repeats,
+            vec![TandemRepeat { start: 2, end: 11, unit: "CAG".to_string(), copy_number: 3 }]
+        );
+    }
+}
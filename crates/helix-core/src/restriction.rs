@@ -0,0 +1,573 @@
+//! Restriction enzyme site search.
+
+use crate::iupac;
+use crate::operations::reverse_complement;
+
+/// A restriction enzyme and where it cuts relative to its recognition site.
+///
+/// Cut offsets are measured on the top strand from the first base of the
+/// recognition site, e.g. EcoRI `G^AATTC` cuts the top strand at 1 and the
+/// bottom strand at 5. Offsets may be negative or past the end of the site for
+/// enzymes that cut outside their recognition sequence.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RestrictionEnzyme {
+    pub name: String,
+    /// Recognition sequence, which may contain IUPAC ambiguity codes
+    pub recognition_site: String,
+    pub cut_offset_top: isize,
+    pub cut_offset_bottom: isize,
+}
+
+impl RestrictionEnzyme {
+    pub fn new(
+        name: impl Into<String>,
+        recognition_site: impl Into<String>,
+        cut_offset_top: isize,
+        cut_offset_bottom: isize,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            recognition_site: recognition_site.into().to_uppercase(),
+            cut_offset_top,
+            cut_offset_bottom,
+        }
+    }
+
+    /// Whether the recognition site reads the same on both strands
+    pub fn is_palindromic(&self) -> bool {
+        reverse_complement(&self.recognition_site) == self.recognition_site
+    }
+}
+
+/// A small built-in set of common cloning enzymes: EcoRI, BamHI, HindIII,
+/// NotI and XhoI.
+pub fn builtin_enzymes() -> Vec<RestrictionEnzyme> {
+    vec![
+        RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5),
+        RestrictionEnzyme::new("BamHI", "GGATCC", 1, 5),
+        RestrictionEnzyme::new("HindIII", "AAGCTT", 1, 5),
+        RestrictionEnzyme::new("NotI", "GCGGCCGC", 2, 6),
+        RestrictionEnzyme::new("XhoI", "CTCGAG", 1, 5),
+    ]
+}
+
+/// Shape of the end left by a cut
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverhangType {
+    Blunt,
+    /// Top strand cut before the bottom strand (e.g. EcoRI)
+    FivePrime,
+    /// Bottom strand cut before the top strand (e.g. PstI)
+    ThreePrime,
+}
+
+/// A position where an enzyme cuts the sequence
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CutSite {
+    pub enzyme: String,
+    /// Start of the matched recognition site (0-based)
+    pub site_start: usize,
+    /// Top-strand cut position (cut happens before this base)
+    pub top_cut: usize,
+    /// Bottom-strand cut position, in top-strand coordinates
+    pub bottom_cut: usize,
+    /// Whether the site was found on the reverse strand
+    pub is_complement: bool,
+    pub overhang_type: OverhangType,
+    /// Top-strand bases between the two cuts (empty for blunt ends)
+    pub overhang: String,
+}
+
+/// Find every cut site of `enzymes` in `sequence`.
+///
+/// Recognition sites may contain IUPAC codes (e.g. `GGNCC`, `RCATGY`).
+/// Non-palindromic sites are also searched on the reverse strand, with the cut
+/// offsets mirrored. On linear sequences cuts that would fall outside the
+/// molecule are dropped; on circular sequences they wrap. Results are sorted by
+/// top-strand cut position.
+pub fn find_cut_sites(
+    sequence: &str,
+    enzymes: &[RestrictionEnzyme],
+    is_circular: bool,
+) -> Vec<CutSite> {
+    let seq = sequence.to_uppercase();
+    let bases = seq.as_bytes();
+    let len = seq.len() as isize;
+    let mut sites = Vec::new();
+
+    for enzyme in enzymes {
+        let site = &enzyme.recognition_site;
+        let site_len = site.len() as isize;
+
+        let mut strands = vec![(site.clone(), false)];
+        if !enzyme.is_palindromic() {
+            strands.push((reverse_complement(site), true));
+        }
+
+        for (pattern, is_complement) in strands {
+            for start in iupac::find_positions(&seq, &pattern, is_circular) {
+                let p = start as isize;
+                let (top, bottom) = if is_complement {
+                    (
+                        p + site_len - enzyme.cut_offset_bottom,
+                        p + site_len - enzyme.cut_offset_top,
+                    )
+                } else {
+                    (p + enzyme.cut_offset_top, p + enzyme.cut_offset_bottom)
+                };
+
+                let overhang_type = match top.cmp(&bottom) {
+                    std::cmp::Ordering::Equal => OverhangType::Blunt,
+                    std::cmp::Ordering::Less => OverhangType::FivePrime,
+                    std::cmp::Ordering::Greater => OverhangType::ThreePrime,
+                };
+
+                let (top, bottom) = if is_circular {
+                    (top.rem_euclid(len), bottom.rem_euclid(len))
+                } else if (0..=len).contains(&top) && (0..=len).contains(&bottom) {
+                    (top, bottom)
+                } else {
+                    continue;
+                };
+
+                let (from, to) = if overhang_type == OverhangType::ThreePrime {
+                    (bottom, top)
+                } else {
+                    (top, bottom)
+                };
+                let overhang_len = (to - from).rem_euclid(len.max(1));
+                let overhang = (0..overhang_len)
+                    .map(|k| bases[(from + k).rem_euclid(len) as usize] as char)
+                    .collect();
+
+                sites.push(CutSite {
+                    enzyme: enzyme.name.clone(),
+                    site_start: start,
+                    top_cut: top as usize,
+                    bottom_cut: bottom as usize,
+                    is_complement,
+                    overhang_type,
+                    overhang,
+                });
+            }
+        }
+    }
+
+    sites.sort_by_key(|s| s.top_cut);
+    sites
+}
+
+/// Digest `sequence` with `enzymes`, built-in or user-defined; an alias for
+/// [`find_cut_sites`].
+pub fn digest(sequence: &str, enzymes: &[RestrictionEnzyme], is_circular: bool) -> Vec<CutSite> {
+    find_cut_sites(sequence, enzymes, is_circular)
+}
+
+/// Enzymes that cut `sequence` exactly once, e.g. for linearizing a plasmid.
+/// Returned in the order given.
+pub fn single_cutters<'a>(
+    sequence: &str,
+    enzymes: &'a [RestrictionEnzyme],
+    is_circular: bool,
+) -> Vec<&'a RestrictionEnzyme> {
+    enzymes
+        .iter()
+        .filter(|e| find_cut_sites(sequence, std::slice::from_ref(*e), is_circular).len() == 1)
+        .collect()
+}
+
+/// A piece of a digested molecule, between two top-strand cuts.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Fragment {
+    /// Top-strand start (0-based, the left cut)
+    pub start: usize,
+    /// Top-strand end (exclusive, the right cut). Less than `start` for a
+    /// fragment spanning the origin of a circular sequence.
+    pub end: usize,
+    pub length: usize,
+    /// Cut that made the left end; `None` for the start of a linear molecule
+    pub left_end: Option<CutSite>,
+    /// Cut that made the right end; `None` for the end of a linear molecule
+    pub right_end: Option<CutSite>,
+}
+
+/// Digest `sequence` with `enzymes` and return the fragments in order along
+/// the top strand. Cuts at the same top-strand position are counted once.
+/// An uncut sequence gives no fragments; a circular sequence cut once gives a
+/// single full-length fragment with the same cut at both ends.
+pub fn digest_fragments(
+    sequence: &str,
+    enzymes: &[RestrictionEnzyme],
+    is_circular: bool,
+) -> Vec<Fragment> {
+    let mut sites = find_cut_sites(sequence, enzymes, is_circular);
+    sites.dedup_by_key(|s| s.top_cut);
+    if sites.is_empty() {
+        return Vec::new();
+    }
+
+    let len = sequence.len();
+    let fragment = |start: usize, end: usize, left: Option<&CutSite>, right: Option<&CutSite>| {
+        let length = if is_circular && end <= start {
+            len - start + end
+        } else {
+            end - start
+        };
+        Fragment {
+            start,
+            end,
+            length,
+            left_end: left.cloned(),
+            right_end: right.cloned(),
+        }
+    };
+
+    let mut fragments: Vec<Fragment> = sites
+        .windows(2)
+        .map(|w| fragment(w[0].top_cut, w[1].top_cut, Some(&w[0]), Some(&w[1])))
+        .collect();
+    let (first, last) = (&sites[0], &sites[sites.len() - 1]);
+    if is_circular {
+        fragments.push(fragment(last.top_cut, first.top_cut, Some(last), Some(first)));
+    } else {
+        fragments.insert(0, fragment(0, first.top_cut, None, Some(first)));
+        fragments.push(fragment(last.top_cut, len, Some(last), None));
+    }
+    fragments
+}
+
+/// A molecule formed by ligating fragments end to end
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LigationProduct {
+    /// Constituent fragments in order: index into the input and whether the
+    /// fragment is flipped
+    pub fragments: Vec<(usize, bool)>,
+    pub length: usize,
+    pub is_circular: bool,
+}
+
+/// Predict the products of religating `fragments`.
+///
+/// Only ends cut by one of `enzymes_used` take part; two ends join when
+/// [`overhangs_compatible`] allows it, with fragments used at most once and in
+/// either orientation. Returns every linear product of two or more fragments
+/// and every circular product, including a single fragment closing on itself.
+/// Products that are the same molecule read from the other end (or, for
+/// circles, from another starting fragment) are reported once, in the reading
+/// that sorts first.
+///
+/// The search is exhaustive, so it is intended for the handful of fragments of
+/// a typical digest.
+pub fn ligate(fragments: &[Fragment], enzymes_used: &[RestrictionEnzyme]) -> Vec<LigationProduct> {
+    // Ends a fragment presents in a given orientation: (left, right)
+    let ends = |(index, reversed): (usize, bool)| {
+        let fragment = &fragments[index];
+        let usable = |end: &Option<CutSite>| {
+            end.clone()
+                .filter(|cut| enzymes_used.iter().any(|e| e.name == cut.enzyme))
+        };
+        let (left, right) = (usable(&fragment.left_end), usable(&fragment.right_end));
+        if reversed {
+            (right, left)
+        } else {
+            (left, right)
+        }
+    };
+    let joins = |right: &Option<CutSite>, left: &Option<CutSite>| match (right, left) {
+        (Some(a), Some(b)) => overhangs_compatible(a, b),
+        _ => false,
+    };
+
+    let mut products: Vec<LigationProduct> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    let mut stack: Vec<Vec<(usize, bool)>> =
+        (0..fragments.len()).map(|i| vec![(i, false)]).collect();
+
+    while let Some(chain) = stack.pop() {
+        let length = chain.iter().map(|&(i, _)| fragments[i].length).sum();
+        let (first_left, _) = ends(chain[0]);
+        let (_, last_right) = ends(chain[chain.len() - 1]);
+
+        if joins(&last_right, &first_left) {
+            let key = circular_key(&chain);
+            if seen.insert((true, key.clone())) {
+                products.push(LigationProduct {
+                    fragments: key,
+                    length,
+                    is_circular: true,
+                });
+            }
+        }
+        if chain.len() > 1 {
+            let key = linear_key(&chain);
+            if seen.insert((false, key.clone())) {
+                products.push(LigationProduct {
+                    fragments: key,
+                    length,
+                    is_circular: false,
+                });
+            }
+        }
+
+        for next in 0..fragments.len() {
+            if chain.iter().any(|&(i, _)| i == next) {
+                continue;
+            }
+            for reversed in [false, true] {
+                if joins(&last_right, &ends((next, reversed)).0) {
+                    let mut extended = chain.clone();
+                    extended.push((next, reversed));
+                    stack.push(extended);
+                }
+            }
+        }
+    }
+
+    products.sort_by(|a, b| {
+        (a.fragments.len(), !a.is_circular, &a.fragments).cmp(&(
+            b.fragments.len(),
+            !b.is_circular,
+            &b.fragments,
+        ))
+    });
+    products
+}
+
+/// The same chain read from the other end: reversed order, each fragment flipped
+fn flip_chain(chain: &[(usize, bool)]) -> Vec<(usize, bool)> {
+    chain.iter().rev().map(|&(i, reversed)| (i, !reversed)).collect()
+}
+
+fn linear_key(chain: &[(usize, bool)]) -> Vec<(usize, bool)> {
+    chain.to_vec().min(flip_chain(chain))
+}
+
+/// Smallest rotation of the chain or its flip, so every reading of a circle
+/// gives the same key
+fn circular_key(chain: &[(usize, bool)]) -> Vec<(usize, bool)> {
+    [chain.to_vec(), flip_chain(chain)]
+        .into_iter()
+        .flat_map(|c| {
+            (0..c.len()).map(move |k| {
+                let mut rotated = c.clone();
+                rotated.rotate_left(k);
+                rotated
+            })
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// Whether the ends left by two cuts can be ligated to each other.
+///
+/// Blunt ends are compatible with any blunt end. Sticky ends need the same
+/// overhang type and matching overhangs; since either fragment may be flipped,
+/// an overhang also matches its reverse complement.
+pub fn overhangs_compatible(a: &CutSite, b: &CutSite) -> bool {
+    if a.overhang_type != b.overhang_type {
+        return false;
+    }
+    a.overhang_type == OverhangType::Blunt
+        || a.overhang == b.overhang
+        || a.overhang == reverse_complement(&b.overhang)
+}
+
+/// Pairs of different enzymes (by name) that cut `sequence` and leave at least
+/// one pair of mutually ligatable ends.
+pub fn find_compatible_enzyme_pairs(
+    sequence: &str,
+    enzymes: &[RestrictionEnzyme],
+    is_circular: bool,
+) -> Vec<(String, String)> {
+    let sites: Vec<Vec<CutSite>> = enzymes
+        .iter()
+        .map(|e| find_cut_sites(sequence, std::slice::from_ref(e), is_circular))
+        .collect();
+
+    let mut pairs = Vec::new();
+    for i in 0..enzymes.len() {
+        for j in i + 1..enzymes.len() {
+            let compatible = sites[i]
+                .iter()
+                .any(|a| sites[j].iter().any(|b| overhangs_compatible(a, b)));
+            if compatible {
+                pairs.push((enzymes[i].name.clone(), enzymes[j].name.clone()));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecori_cut() {
+        let ecori = RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5);
+        let sites = find_cut_sites("AAGAATTCAA", &[ecori], false);
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].site_start, 2);
+        assert_eq!(sites[0].top_cut, 3);
+        assert_eq!(sites[0].bottom_cut, 7);
+        assert_eq!(sites[0].overhang_type, OverhangType::FivePrime);
+        assert_eq!(sites[0].overhang, "AATT");
+    }
+
+    #[test]
+    fn test_digest_builtin_enzymes() {
+        // EcoRI, BamHI and NotI once, XhoI twice, no HindIII
+        let seq = "GAATTCAAGGATCCAAGCGGCCGCAACTCGAGAACTCGAGAA";
+        let enzymes = builtin_enzymes();
+        let sites = digest(seq, &enzymes, false);
+        let names: Vec<&str> = sites.iter().map(|s| s.enzyme.as_str()).collect();
+        assert_eq!(names, vec!["EcoRI", "BamHI", "NotI", "XhoI", "XhoI"]);
+        assert_eq!(sites[2].top_cut, 18);
+        assert_eq!(sites[2].overhang, "GGCC");
+
+        let singles: Vec<&str> = single_cutters(seq, &enzymes, false)
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(singles, vec!["EcoRI", "BamHI", "NotI"]);
+
+        // User-defined enzymes work alongside the built-ins
+        let custom = RestrictionEnzyme::new("Custom", "CCAAGC", 3, 3);
+        assert_eq!(single_cutters(seq, std::slice::from_ref(&custom), false).len(), 1);
+    }
+
+    #[test]
+    fn test_three_prime_overhang() {
+        let psti = RestrictionEnzyme::new("PstI", "CTGCAG", 5, 1);
+        let sites = find_cut_sites("AACTGCAGAA", &[psti], false);
+        assert_eq!(sites[0].overhang_type, OverhangType::ThreePrime);
+        assert_eq!(sites[0].overhang, "TGCA");
+    }
+
+    #[test]
+    fn test_overhangs_compatible() {
+        // BsaI ends AATG and CATT are reverse complements of each other
+        let bsai = RestrictionEnzyme::new("BsaI", "GGTCTC", 7, 11);
+        let a = &find_cut_sites("GGTCTCAAATGAAAA", std::slice::from_ref(&bsai), false)[0];
+        let b = &find_cut_sites("GGTCTCACATTAAAA", std::slice::from_ref(&bsai), false)[0];
+        assert_eq!(a.overhang, "AATG");
+        assert_eq!(b.overhang, "CATT");
+        assert!(overhangs_compatible(a, b));
+
+        let ecorv = RestrictionEnzyme::new("EcoRV", "GATATC", 3, 3);
+        let blunt = &find_cut_sites("AAGATATCAA", &[ecorv], false)[0];
+        assert_eq!(blunt.overhang_type, OverhangType::Blunt);
+        assert!(!overhangs_compatible(a, blunt));
+        assert!(overhangs_compatible(blunt, blunt));
+    }
+
+    #[test]
+    fn test_digest_fragments() {
+        let ecori = RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5);
+        let seq = "AAGAATTCAAAAGAATTCAAAAAA";
+
+        let linear = digest_fragments(seq, std::slice::from_ref(&ecori), false);
+        let spans: Vec<(usize, usize, usize)> =
+            linear.iter().map(|f| (f.start, f.end, f.length)).collect();
+        assert_eq!(spans, vec![(0, 3, 3), (3, 13, 10), (13, 24, 11)]);
+        assert!(linear[0].left_end.is_none() && linear[2].right_end.is_none());
+
+        let circular = digest_fragments(seq, std::slice::from_ref(&ecori), true);
+        let spans: Vec<(usize, usize, usize)> =
+            circular.iter().map(|f| (f.start, f.end, f.length)).collect();
+        assert_eq!(spans, vec![(3, 13, 10), (13, 3, 14)]);
+    }
+
+    #[test]
+    fn test_ligate_religates_circular_digest() {
+        let ecori = RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5);
+        let seq = "AAGAATTCAAAAGAATTCAAAAAA";
+        let fragments = digest_fragments(seq, std::slice::from_ref(&ecori), true);
+        assert_eq!(fragments.len(), 2);
+
+        let products = ligate(&fragments, std::slice::from_ref(&ecori));
+        let circles: Vec<&LigationProduct> = products.iter().filter(|p| p.is_circular).collect();
+        // Each fragment self-circularizes, and the pair religates (in either
+        // relative orientation) to the original length
+        assert_eq!(circles.len(), 4);
+        assert_eq!(circles[0].fragments, vec![(0, false)]);
+        assert_eq!(circles[0].length, 10);
+        assert_eq!(circles[1].length, 14);
+        assert!(circles[2..]
+            .iter()
+            .all(|p| p.fragments.len() == 2 && p.length == seq.len()));
+
+        let linear: Vec<&LigationProduct> = products.iter().filter(|p| !p.is_circular).collect();
+        assert!(linear.iter().all(|p| p.length == seq.len()));
+
+        // Ends from enzymes not in the ligation set do not join
+        let bamhi = RestrictionEnzyme::new("BamHI", "GGATCC", 1, 5);
+        assert!(ligate(&fragments, &[bamhi]).is_empty());
+    }
+
+    #[test]
+    fn test_ligate_incompatible_ends() {
+        let ecori = RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5);
+        let bamhi = RestrictionEnzyme::new("BamHI", "GGATCC", 1, 5);
+        let enzymes = [ecori, bamhi];
+        // One EcoRI and one BamHI site: neither fragment end pairs with the other
+        let seq = "AAGAATTCAAAAAAGGATCCAAAA";
+        let fragments = digest_fragments(seq, &enzymes, true);
+        assert_eq!(fragments.len(), 2);
+        let products = ligate(&fragments, &enzymes);
+        // Only the original molecule can re-form, joining EcoRI to EcoRI and
+        // BamHI to BamHI
+        assert_eq!(products.len(), 3);
+        let circle = products.iter().find(|p| p.is_circular).unwrap();
+        assert_eq!(circle.fragments, vec![(0, false), (1, false)]);
+        assert_eq!(circle.length, seq.len());
+    }
+
+    #[test]
+    fn test_find_compatible_enzyme_pairs() {
+        let enzymes = [
+            RestrictionEnzyme::new("BamHI", "GGATCC", 1, 5),
+            RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5),
+            RestrictionEnzyme::new("BglII", "AGATCT", 1, 5),
+        ];
+        let seq = "AAGGATCCAAGAATTCAAAGATCTAA";
+        let pairs = find_compatible_enzyme_pairs(seq, &enzymes, false);
+        assert_eq!(pairs, vec![("BamHI".to_string(), "BglII".to_string())]);
+    }
+
+    #[test]
+    fn test_ambiguous_site() {
+        let sau96i = RestrictionEnzyme::new("Sau96I", "GGNCC", 1, 4);
+        for seq in ["AGGACCT", "AGGTCCT"] {
+            let sites = find_cut_sites(seq, std::slice::from_ref(&sau96i), false);
+            assert_eq!(sites.len(), 1, "expected one site in {}", seq);
+            assert_eq!(sites[0].site_start, 1);
+            assert_eq!(sites[0].top_cut, 2);
+            assert_eq!(sites[0].bottom_cut, 5);
+        }
+    }
+
+    #[test]
+    fn test_reverse_strand_site() {
+        // BsaI GGTCTC(1/5), found here as GAGACC on the top strand
+        let bsai = RestrictionEnzyme::new("BsaI", "GGTCTC", 7, 11);
+        let sites = find_cut_sites("AAAAAAAAAAGAGACCAA", &[bsai], false);
+        assert_eq!(sites.len(), 1);
+        assert!(sites[0].is_complement);
+        assert_eq!(sites[0].top_cut, 5);
+        assert_eq!(sites[0].bottom_cut, 9);
+    }
+
+    #[test]
+    fn test_circular_wrap() {
+        let ecori = RestrictionEnzyme::new("EcoRI", "GAATTC", 1, 5);
+        let sites = find_cut_sites("TTCAAAAGAA", std::slice::from_ref(&ecori), true);
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].site_start, 7);
+        assert_eq!(sites[0].top_cut, 8);
+        assert_eq!(sites[0].bottom_cut, 2);
+
+        assert!(find_cut_sites("TTCAAAAGAA", &[ecori], false).is_empty());
+    }
+}
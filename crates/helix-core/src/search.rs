@@ -1,3 +1,4 @@
+use aho_corasick::AhoCorasick;
 use regex::Regex;
 
 /// A match in the sequence
@@ -9,6 +10,18 @@ pub struct SequenceMatch {
     pub is_complement: bool,
 }
 
+/// `sequence` followed by its first `extend` bases (at most one full copy), so
+/// a match of `m` bases that runs through the origin of a circular sequence
+/// can be found by extending by `m - 1`. Position `p` in the result is
+/// position `p % sequence.len()` in `sequence`.
+pub fn doubled_for_search(sequence: &str, extend: usize) -> String {
+    let extend = extend.min(sequence.len());
+    let mut doubled = String::with_capacity(sequence.len() + extend);
+    doubled.push_str(sequence);
+    doubled.push_str(&sequence[..extend]);
+    doubled
+}
+
 /// Find exact pattern matches in a sequence (case-insensitive)
 /// Handles circular sequences by doubling the search space
 pub fn find_pattern(
@@ -25,7 +38,7 @@ pub fn find_pattern(
     }
 
     let search_seq = if is_circular {
-        format!("{}{}", upper_seq, &upper_seq[..upper_pat.len().min(seq_len).saturating_sub(1)])
+        doubled_for_search(&upper_seq, upper_pat.len() - 1)
     } else {
         upper_seq.clone()
     };
@@ -69,6 +82,181 @@ pub fn find_pattern(
     matches
 }
 
+/// Find exact matches of several patterns at once (case-insensitive).
+///
+/// Builds one Aho-Corasick automaton over every pattern and its reverse
+/// complement, so the sequence is scanned once however many patterns there
+/// are. Each hit is paired with the index into `patterns` of the pattern that
+/// matched; the hits for one pattern are the same as [`find_pattern`] returns.
+pub fn find_patterns(
+    sequence: &str,
+    patterns: &[&str],
+    is_circular: bool,
+) -> Vec<(usize, SequenceMatch)> {
+    let upper_seq = sequence.to_uppercase();
+    let seq_len = upper_seq.len();
+    if seq_len == 0 {
+        return Vec::new();
+    }
+
+    // (index into `patterns`, is_complement) for each automaton pattern
+    let mut origins = Vec::new();
+    let mut needles = Vec::new();
+    for (index, pattern) in patterns.iter().enumerate() {
+        let upper_pat = pattern.to_uppercase();
+        if upper_pat.is_empty() {
+            continue;
+        }
+        let rc_pat = crate::operations::reverse_complement(&upper_pat);
+        if rc_pat != upper_pat {
+            origins.push((index, true));
+            needles.push(rc_pat);
+        }
+        origins.push((index, false));
+        needles.push(upper_pat);
+    }
+    if needles.is_empty() {
+        return Vec::new();
+    }
+
+    let longest = needles.iter().map(String::len).max().unwrap_or(0);
+    let search_seq = if is_circular {
+        doubled_for_search(&upper_seq, longest - 1)
+    } else {
+        upper_seq
+    };
+
+    let automaton = AhoCorasick::new(&needles).expect("literal patterns always build");
+    let mut matches: Vec<(usize, SequenceMatch)> = automaton
+        .find_overlapping_iter(&search_seq)
+        .filter(|m| m.start() < seq_len)
+        .map(|m| {
+            let (index, is_complement) = origins[m.pattern().as_usize()];
+            let found = SequenceMatch {
+                start: m.start(),
+                end: m.end() % seq_len,
+                matched: needles[m.pattern().as_usize()].clone(),
+                is_complement,
+            };
+            (index, found)
+        })
+        .collect();
+
+    matches.sort_by_key(|(index, m)| (m.start, *index, m.is_complement));
+    matches
+}
+
+/// Find matches of `pattern` with at most `max_mismatches` substituted bases
+/// (case-insensitive, no gaps). Searches both strands like [`find_pattern`];
+/// `matched` holds the bases actually found in the sequence.
+pub fn find_pattern_mismatches(
+    sequence: &str,
+    pattern: &str,
+    max_mismatches: usize,
+    is_circular: bool,
+) -> Vec<SequenceMatch> {
+    let upper_seq = sequence.to_uppercase();
+    let upper_pat = pattern.to_uppercase();
+    let seq_len = upper_seq.len();
+    let pat_len = upper_pat.len();
+
+    if pat_len == 0 || seq_len == 0 || (!is_circular && pat_len > seq_len) {
+        return Vec::new();
+    }
+
+    let seq_bytes = upper_seq.as_bytes();
+    let base_at = |i: usize| seq_bytes[i % seq_len];
+    let last_start = if is_circular { seq_len } else { seq_len - pat_len + 1 };
+    let scan = |pat: &[u8], is_complement: bool| -> Vec<SequenceMatch> {
+        (0..last_start)
+            .filter_map(|start| {
+                let mut mismatches = 0;
+                for (offset, &b) in pat.iter().enumerate() {
+                    if base_at(start + offset) != b {
+                        mismatches += 1;
+                        if mismatches > max_mismatches {
+                            return None;
+                        }
+                    }
+                }
+                Some(SequenceMatch {
+                    start,
+                    end: (start + pat_len) % seq_len,
+                    matched: (start..start + pat_len).map(|i| base_at(i) as char).collect(),
+                    is_complement,
+                })
+            })
+            .collect()
+    };
+
+    let mut matches = scan(upper_pat.as_bytes(), false);
+
+    // Reverse complement strand
+    let rc_pat = crate::operations::reverse_complement(&upper_pat);
+    if rc_pat != upper_pat {
+        matches.extend(scan(rc_pat.as_bytes(), true));
+    }
+
+    matches.sort_by_key(|m| m.start);
+    matches
+}
+
+/// Find matches of a degenerate pattern, treating IUPAC codes (N, R, Y, S, W,
+/// K, M, B, D, H, V) in `pattern` as the bases they stand for. Searches both
+/// strands like [`find_pattern`]; `matched` holds the bases actually found in
+/// the sequence rather than the pattern.
+pub fn find_pattern_iupac(
+    sequence: &str,
+    pattern: &str,
+    is_circular: bool,
+) -> Vec<SequenceMatch> {
+    let upper_seq = sequence.to_uppercase();
+    let upper_pat = pattern.to_uppercase();
+    let seq_len = upper_seq.len();
+
+    if upper_pat.is_empty() || seq_len == 0 {
+        return Vec::new();
+    }
+
+    let bases_at = |start: usize| -> String {
+        upper_seq
+            .chars()
+            .cycle()
+            .skip(start)
+            .take(upper_pat.len())
+            .collect()
+    };
+
+    let mut matches: Vec<SequenceMatch> =
+        crate::iupac::find_positions(&upper_seq, &upper_pat, is_circular)
+            .into_iter()
+            .map(|start| SequenceMatch {
+                start,
+                end: (start + upper_pat.len()) % seq_len,
+                matched: bases_at(start),
+                is_complement: false,
+            })
+            .collect();
+
+    // Reverse complement strand
+    let rc_pat = crate::operations::reverse_complement(&upper_pat);
+    if rc_pat != upper_pat {
+        matches.extend(
+            crate::iupac::find_positions(&upper_seq, &rc_pat, is_circular)
+                .into_iter()
+                .map(|start| SequenceMatch {
+                    start,
+                    end: (start + rc_pat.len()) % seq_len,
+                    matched: bases_at(start),
+                    is_complement: true,
+                }),
+        );
+    }
+
+    matches.sort_by_key(|m| m.start);
+    matches
+}
+
 /// Find regex pattern matches in a sequence
 pub fn find_regex(
     sequence: &str,
@@ -79,8 +267,7 @@ pub fn find_regex(
     let seq_len = sequence.len();
 
     let search_seq = if is_circular {
-        let extend_len = pattern.len().min(seq_len).saturating_sub(1);
-        format!("{}{}", sequence.to_uppercase(), &sequence.to_uppercase()[..extend_len])
+        doubled_for_search(&sequence.to_uppercase(), pattern.len().saturating_sub(1))
     } else {
         sequence.to_uppercase()
     };
@@ -133,6 +320,107 @@ mod tests {
         assert!(matches.len() >= 2);
     }
 
+    #[test]
+    fn test_find_patterns_matches_separate_searches() {
+        let seq = "GAATTCATGGATCCAAGCTTGGATCCGAATTCATG";
+        let patterns = ["GAATTC", "ggatcc", "CATG"];
+        for is_circular in [false, true] {
+            let key = |index: usize, m: &SequenceMatch| {
+                (index, m.start, m.end, m.matched.clone(), m.is_complement)
+            };
+            let mut combined: Vec<_> = find_patterns(seq, &patterns, is_circular)
+                .iter()
+                .map(|(index, m)| key(*index, m))
+                .collect();
+            let mut separate: Vec<_> = patterns
+                .iter()
+                .enumerate()
+                .flat_map(|(index, p)| {
+                    find_pattern(seq, p, is_circular)
+                        .iter()
+                        .map(|m| key(index, m))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            combined.sort();
+            separate.sort();
+            assert!(!combined.is_empty());
+            assert_eq!(combined, separate);
+        }
+    }
+
+    #[test]
+    fn test_find_patterns_wraps_origin() {
+        let matches = find_patterns("CATGGAAT", &["AATCAT", "GGA"], true);
+        assert!(matches.iter().any(|(index, m)| *index == 0 && m.start == 5 && m.end == 3));
+        assert!(find_patterns("CATGGAAT", &["AATCAT"], false).is_empty());
+        assert!(find_patterns("CATG", &[""], false).is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern_mismatches() {
+        // GGATCC with one substitution at 6, exact at 14
+        let seq = "AAAAAAGGTTCCAAGGATCCAA";
+        let exact = find_pattern_mismatches(seq, "GGATCC", 0, false);
+        assert_eq!(exact.iter().map(|m| m.start).collect::<Vec<_>>(), vec![14]);
+
+        let fuzzy = find_pattern_mismatches(seq, "GGATCC", 1, false);
+        let found: Vec<(usize, &str)> =
+            fuzzy.iter().map(|m| (m.start, m.matched.as_str())).collect();
+        assert_eq!(found, vec![(6, "GGTTCC"), (14, "GGATCC")]);
+
+        let rc = find_pattern_mismatches("TTGAACTT", "AGTTCA", 0, false);
+        assert_eq!(rc.len(), 1);
+        assert!(rc[0].is_complement);
+    }
+
+    #[test]
+    fn test_find_pattern_mismatches_wraps_origin() {
+        let matches = find_pattern_mismatches("TCCAAAAAGGA", "GGATCG", 1, true);
+        assert!(matches.iter().any(|m| m.start == 8 && m.end == 3 && m.matched == "GGATCC"));
+        assert!(find_pattern_mismatches("TCCAAAAAGGA", "GGATCG", 1, false).is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern_iupac_n_matches_any_base() {
+        let matches = find_pattern_iupac("AGGACCTGGTCCGGGCC", "GGNCC", false);
+        let found: Vec<(usize, &str)> =
+            matches.iter().map(|m| (m.start, m.matched.as_str())).collect();
+        assert_eq!(found, vec![(1, "GGACC"), (7, "GGTCC"), (12, "GGGCC")]);
+        assert!(matches.iter().all(|m| !m.is_complement));
+        assert!(find_pattern("AGGACCTGGTCC", "GGNCC", false).is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern_iupac_r_matches_purines() {
+        let matches = find_pattern_iupac("TTGAATTCTTAAATTCTT", "RAATTC", false);
+        let forward: Vec<&str> = matches
+            .iter()
+            .filter(|m| !m.is_complement)
+            .map(|m| m.matched.as_str())
+            .collect();
+        assert_eq!(forward, vec!["GAATTC", "AAATTC"]);
+        assert!(find_pattern_iupac("TTCAATTCTT", "RAATTC", false)
+            .iter()
+            .all(|m| m.is_complement));
+
+        // The reverse strand match GAATTY is reported with the target bases
+        let rc = find_pattern_iupac("CCGAATTTCC", "RAATTC", false);
+        assert_eq!(rc.len(), 1);
+        assert!(rc[0].is_complement);
+        assert_eq!(rc[0].matched, "GAATTT");
+    }
+
+    #[test]
+    fn test_find_pattern_iupac_wraps_origin() {
+        // GG|ACC runs through the origin of the circle
+        let matches = find_pattern_iupac("ACCTTTTGG", "GGNCC", true);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].start, matches[0].end), (7, 3));
+        assert_eq!(matches[0].matched, "GGACC");
+        assert!(find_pattern_iupac("ACCTTTTGG", "GGNCC", false).is_empty());
+    }
+
     #[test]
     fn test_find_regex() {
         let matches = find_regex("ATGAAAGGG", "ATG[A-Z]{3}G", false).unwrap();
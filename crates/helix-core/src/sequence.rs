@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::feature::Feature;
+use crate::codon::CodonTable;
+use crate::feature::{Feature, FeatureType, Location, Strand};
+use crate::feature_index::FeatureIndex;
+use crate::operations::{gc_content, reverse_complement, translate};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -19,7 +25,7 @@ impl std::fmt::Display for Topology {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SequenceMetadata {
     #[serde(default)]
     pub accession: Option<String>,
@@ -37,27 +43,16 @@ pub struct SequenceMetadata {
     pub keywords: Option<String>,
     #[serde(default)]
     pub source: Option<String>,
+    /// Taxonomic lineage from the record, most general clade first
+    #[serde(default)]
+    pub taxonomy: Vec<String>,
     #[serde(default)]
     pub references: Vec<Reference>,
     #[serde(default)]
     pub comments: Vec<String>,
-}
-
-impl Default for SequenceMetadata {
-    fn default() -> Self {
-        Self {
-            accession: None,
-            organism: None,
-            molecule_type: None,
-            division: None,
-            date: None,
-            definition: None,
-            keywords: None,
-            source: None,
-            references: Vec::new(),
-            comments: Vec::new(),
-        }
-    }
+    /// FASTA header line (without `>`) exactly as read
+    #[serde(default)]
+    pub fasta_header: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +64,29 @@ pub struct Reference {
     pub pubmed: Option<String>,
 }
 
+/// How much of a sequence is covered by its features
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CoverageStats {
+    /// Bases covered by at least one feature (overlaps counted once)
+    pub covered_bases: usize,
+    /// `covered_bases` as a fraction of the sequence length (0.0–1.0)
+    pub fraction: f64,
+    /// Number of features per kilobase of sequence
+    pub features_per_kb: f64,
+}
+
+/// Two CDS features on the same strand whose spans overlap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureConflict {
+    pub first: Uuid,
+    pub second: Uuid,
+    /// Overlapping span (0-based, half-open)
+    pub overlap_start: usize,
+    pub overlap_end: usize,
+    /// Whether both CDS use the same reading frame
+    pub in_frame: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sequence {
     pub id: Uuid,
@@ -76,11 +94,20 @@ pub struct Sequence {
     #[serde(default)]
     pub description: String,
     pub topology: Topology,
-    pub sequence: String,
+    /// The bases. Private so every edit goes through a method that drops the
+    /// caches derived from it.
+    sequence: String,
+    /// Private like `sequence`; edit through [`Sequence::features_mut`].
     #[serde(default)]
-    pub features: Vec<Feature>,
+    features: Vec<Feature>,
     #[serde(default)]
     pub metadata: SequenceMetadata,
+    /// Lazily computed reverse complement of `sequence`
+    #[serde(skip)]
+    rc_cache: OnceLock<String>,
+    /// Lazily built interval index over `features`
+    #[serde(skip)]
+    feature_index: OnceLock<FeatureIndex>,
 }
 
 impl Sequence {
@@ -93,9 +120,77 @@ impl Sequence {
             sequence: sequence.into().to_uppercase(),
             features: Vec::new(),
             metadata: SequenceMetadata::default(),
+            rc_cache: OnceLock::new(),
+            feature_index: OnceLock::new(),
         }
     }
 
+    /// Reverse complement of the whole sequence, computed on first use and
+    /// cached until the sequence is edited.
+    pub fn reverse_complement_str(&self) -> &str {
+        self.rc_cache
+            .get_or_init(|| crate::operations::reverse_complement(&self.sequence))
+    }
+
+    /// The bases, uppercase for sequences built by [`Sequence::new`].
+    pub fn sequence(&self) -> &str {
+        &self.sequence
+    }
+
+    /// Replace the bases, invalidating cached derived data.
+    pub fn set_sequence(&mut self, sequence: impl Into<String>) {
+        self.sequence = sequence.into();
+        self.invalidate_caches();
+    }
+
+    /// Replace `start..end` with `bases`, invalidating cached derived data.
+    /// Feature coordinates are not adjusted.
+    pub fn replace_range(&mut self, start: usize, end: usize, bases: &str) {
+        self.sequence.replace_range(start..end, bases);
+        self.invalidate_caches();
+    }
+
+    fn invalidate_caches(&mut self) {
+        self.rc_cache = OnceLock::new();
+        self.feature_index = OnceLock::new();
+    }
+
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Mutable access to the features. The cached feature index is dropped
+    /// and rebuilt on next use.
+    pub fn features_mut(&mut self) -> &mut Vec<Feature> {
+        self.feature_index = OnceLock::new();
+        &mut self.features
+    }
+
+    /// Interval index over the features, built on first use and cached until
+    /// the features change.
+    pub fn feature_index(&self) -> &FeatureIndex {
+        self.feature_index
+            .get_or_init(|| FeatureIndex::build(&self.features))
+    }
+
+    /// Features covering `pos`, in feature order
+    pub fn features_at_position(&self, pos: usize) -> Vec<&Feature> {
+        self.feature_index()
+            .query(pos)
+            .into_iter()
+            .filter_map(|i| self.features.get(i))
+            .collect()
+    }
+
+    /// Features overlapping `start..end`, in feature order
+    pub fn features_in_range(&self, start: usize, end: usize) -> Vec<&Feature> {
+        self.feature_index()
+            .query_range(start, end)
+            .into_iter()
+            .filter_map(|i| self.features.get(i))
+            .collect()
+    }
+
     pub fn len(&self) -> usize {
         self.sequence.len()
     }
@@ -122,8 +217,383 @@ impl Sequence {
         }
     }
 
+    /// The sequence followed by its first `extend` bases (clamped to the
+    /// length), for finding matches that run through the origin. See
+    /// [`crate::search::doubled_for_search`].
+    pub fn doubled_for_search(&self, extend: usize) -> String {
+        crate::search::doubled_for_search(&self.sequence, extend)
+    }
+
+    /// The bases a feature covers, read 5'→3' on its own strand: each range of
+    /// a join in order (wrapping through the origin on circular sequences),
+    /// reverse-complemented for reverse-strand or complement features.
+    /// Ranges past the end of the sequence are clipped.
+    pub fn feature_sequence(&self, feature: &Feature) -> String {
+        let len = self.len();
+        let mut bases: String = feature
+            .location
+            .ranges()
+            .into_iter()
+            .map(|(start, end)| self.subsequence(start.min(len), end.min(len)))
+            .collect();
+        if feature.strand == Strand::Reverse
+            || matches!(feature.location, Location::Complement { .. })
+        {
+            bases = reverse_complement(&bases);
+        }
+        bases
+    }
+
+    /// GC content (0.0 to 1.0) of [`Sequence::feature_sequence`], so a join
+    /// counts only its spliced ranges
+    pub fn feature_gc(&self, feature: &Feature) -> f64 {
+        gc_content(&self.feature_sequence(feature))
+    }
+
+    /// Protein encoded by a CDS feature, as GenBank viewers show it: the
+    /// [`Sequence::feature_sequence`] read from the `/codon_start` offset (1, 2
+    /// or 3; default 1) up to, but not including, the first stop codon.
+    /// Returns `None` for features that are not CDSs.
+    pub fn translate_feature(&self, feature: &Feature, table: &CodonTable) -> Option<String> {
+        if feature.feature_type != FeatureType::Cds {
+            return None;
+        }
+        let offset = match feature.get_qualifier("codon_start").map(str::trim) {
+            Some("2") => 1,
+            Some("3") => 2,
+            _ => 0,
+        };
+        let bases = self.feature_sequence(feature);
+        let protein = translate(bases.get(offset..).unwrap_or(""), table);
+        Some(protein.split('*').next().unwrap_or("").to_string())
+    }
+
     pub fn add_feature(&mut self, feature: Feature) {
         self.features.push(feature);
+        self.feature_index = OnceLock::new();
+    }
+
+    /// Merge overlapping or nearby features on the same strand.
+    ///
+    /// Features whose spans overlap or are separated by at most `max_gap`
+    /// bases are collapsed into one feature covering their union. When
+    /// `same_type_only` is set only features of the same type are merged. The
+    /// longest feature of each group survives (keeping its name, type and
+    /// color) and gains any qualifiers it did not already have. Only simple
+    /// locations are merged; joins are left untouched.
+    ///
+    /// Returns the number of features removed.
+    pub fn merge_features(&mut self, same_type_only: bool, max_gap: usize) -> usize {
+        struct Group {
+            strand: Strand,
+            feature_type: Option<FeatureType>,
+            end: usize,
+            members: Vec<usize>,
+        }
+
+        let mut order: Vec<usize> = (0..self.features.len())
+            .filter(|&i| matches!(self.features[i].location, Location::Simple { .. }))
+            .collect();
+        order.sort_by_key(|&i| self.features[i].start());
+
+        let mut groups: Vec<Group> = Vec::new();
+        for i in order {
+            let f = &self.features[i];
+            let feature_type = same_type_only.then_some(f.feature_type);
+            let open = groups.iter_mut().find(|g| {
+                g.strand == f.strand
+                    && g.feature_type == feature_type
+                    && f.start() <= g.end.saturating_add(max_gap)
+            });
+            match open {
+                Some(group) => {
+                    group.end = group.end.max(f.end());
+                    group.members.push(i);
+                }
+                None => groups.push(Group {
+                    strand: f.strand,
+                    feature_type,
+                    end: f.end(),
+                    members: vec![i],
+                }),
+            }
+        }
+
+        let mut removed = vec![false; self.features.len()];
+        for group in groups.iter().filter(|g| g.members.len() > 1) {
+            let keep = *group
+                .members
+                .iter()
+                .max_by_key(|&&i| self.features[i].location.len())
+                .expect("group has members");
+            let start = group
+                .members
+                .iter()
+                .map(|&i| self.features[i].start())
+                .min()
+                .unwrap_or(0);
+
+            for &other in group.members.iter().filter(|&&i| i != keep) {
+                let extra: Vec<_> = self.features[other]
+                    .qualifiers
+                    .iter()
+                    .filter(|q| {
+                        !self.features[keep]
+                            .qualifiers
+                            .iter()
+                            .any(|k| k.key == q.key && k.value == q.value)
+                    })
+                    .cloned()
+                    .collect();
+                self.features[keep].qualifiers.extend(extra);
+                removed[other] = true;
+            }
+            self.features[keep].location = Location::simple(start, group.end);
+        }
+
+        let mut idx = 0;
+        self.features.retain(|_| {
+            let keep = !removed[idx];
+            idx += 1;
+            keep
+        });
+        self.feature_index = OnceLock::new();
+        removed.iter().filter(|&&r| r).count()
+    }
+
+    /// Remove exact duplicate features, as left by tools that annotate a
+    /// record twice.
+    ///
+    /// Features count as duplicates when their name, type, location, strand
+    /// and set of qualifiers (in any order) all match; IDs and colors are
+    /// ignored. The first of each set is kept. Returns the number removed.
+    pub fn dedup_features(&mut self) -> usize {
+        let mut seen = std::collections::HashSet::new();
+        let before = self.features.len();
+        self.features.retain(|f| {
+            let mut qualifiers: Vec<(String, String)> = f
+                .qualifiers
+                .iter()
+                .map(|q| (q.key.clone(), q.value.clone()))
+                .collect();
+            qualifiers.sort_unstable();
+            seen.insert((
+                f.name.clone(),
+                f.feature_type,
+                f.location.clone(),
+                f.strand,
+                qualifiers,
+            ))
+        });
+        self.feature_index = OnceLock::new();
+        before - self.features.len()
+    }
+
+    /// Rename features that have no distinctive name (empty, or just their
+    /// GenBank feature key such as "CDS") from `scheme`, where `{type}` is the
+    /// feature key and `{n}` numbers the renamed features of each type from 1
+    /// in position order. User-given names are kept. Returns the number of
+    /// features renamed.
+    pub fn relabel_features(&mut self, scheme: &str) -> usize {
+        let mut order: Vec<usize> = (0..self.features.len())
+            .filter(|&i| {
+                let f = &self.features[i];
+                let name = f.name.trim();
+                name.is_empty() || name.eq_ignore_ascii_case(f.feature_type.to_genbank_key())
+            })
+            .collect();
+        order.sort_by_key(|&i| (self.features[i].start(), self.features[i].end()));
+
+        let mut counts: HashMap<FeatureType, usize> = HashMap::new();
+        for &i in &order {
+            let feature = &mut self.features[i];
+            let n = counts.entry(feature.feature_type).or_insert(0);
+            *n += 1;
+            feature.name = scheme
+                .replace("{type}", feature.feature_type.to_genbank_key())
+                .replace("{n}", &n.to_string());
+        }
+        order.len()
+    }
+
+    /// Copy the region `start..end` out as a new linear sequence.
+    ///
+    /// On circular sequences `start > end` wraps through the origin. Features
+    /// overlapping the region are carried over with coordinates shifted into the
+    /// new sequence; features that straddle the region boundary are clipped to
+    /// it, and each carried feature gets a fresh ID.
+    pub fn extract_region(&self, start: usize, end: usize) -> Sequence {
+        let len = self.len();
+        let (start, end) = (start.min(len), end.min(len));
+
+        // Region pieces in original coordinates, with their offset in the new sequence
+        let pieces: Vec<(usize, usize, usize)> = if start <= end {
+            vec![(start, end, 0)]
+        } else if self.is_circular() {
+            vec![(start, len, 0), (0, end, len - start)]
+        } else {
+            Vec::new()
+        };
+
+        let mut extracted = Sequence::new(
+            format!("{} ({}..{})", self.name, start + 1, end),
+            self.subsequence(start, end),
+            Topology::Linear,
+        );
+
+        for feature in &self.features {
+            let mut ranges: Vec<(usize, usize)> = Vec::new();
+            for (f_start, f_end) in feature.location.ranges() {
+                for &(p_start, p_end, offset) in &pieces {
+                    let (s, e) = (f_start.max(p_start), f_end.min(p_end));
+                    if s < e {
+                        ranges.push((s - p_start + offset, e - p_start + offset));
+                    }
+                }
+            }
+            if ranges.is_empty() {
+                continue;
+            }
+            ranges.sort_unstable();
+
+            let mut location = if ranges.len() == 1 {
+                Location::simple(ranges[0].0, ranges[0].1)
+            } else {
+                Location::join(ranges)
+            };
+            if matches!(feature.location, Location::Complement { .. }) {
+                location = Location::Complement {
+                    inner: Box::new(location),
+                };
+            }
+
+            let mut carried = feature.clone();
+            carried.id = Uuid::new_v4();
+            carried.location = location;
+            extracted.features.push(carried);
+        }
+
+        extracted
+    }
+
+    /// Report pairs of CDS features that overlap on the same strand.
+    ///
+    /// Frames are compared from the start codon: the feature start on the
+    /// forward strand, the feature end on the reverse strand. Overlaps between
+    /// opposite strands are not reported.
+    pub fn feature_conflicts(&self) -> Vec<FeatureConflict> {
+        let cds: Vec<&Feature> = self
+            .features
+            .iter()
+            .filter(|f| f.feature_type == FeatureType::Cds)
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for (i, a) in cds.iter().enumerate() {
+            for b in &cds[i + 1..] {
+                if a.strand != b.strand {
+                    continue;
+                }
+                let overlap_start = a.start().max(b.start());
+                let overlap_end = a.end().min(b.end());
+                if overlap_start >= overlap_end {
+                    continue;
+                }
+
+                let in_frame = if a.strand == Strand::Reverse {
+                    a.end().abs_diff(b.end()) % 3 == 0
+                } else {
+                    a.start().abs_diff(b.start()) % 3 == 0
+                };
+
+                conflicts.push(FeatureConflict {
+                    first: a.id,
+                    second: b.id,
+                    overlap_start,
+                    overlap_end,
+                    in_frame,
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// Amino acids of every reverse-strand codon, keyed by genomic position,
+    /// for drawing a reverse translation track under the sequence.
+    ///
+    /// Covers all three reverse frames. Each entry is the lowest genomic
+    /// coordinate the codon spans (the codon reads 3'→5' on the forward
+    /// strand, ending there) and its amino acid; entries are sorted by position.
+    pub fn reverse_strand_translation_track(&self, table: &CodonTable) -> Vec<(usize, char)> {
+        let rc = self.reverse_complement_str();
+        let len = rc.len();
+        let mut track: Vec<(usize, char)> = (0..len.saturating_sub(2))
+            .map(|k| (len - k - 3, table.translate_codon(&rc[k..k + 3])))
+            .collect();
+        track.reverse();
+        track
+    }
+
+    /// Number of features of each type
+    pub fn feature_type_histogram(&self) -> HashMap<FeatureType, usize> {
+        let mut counts = HashMap::new();
+        for feature in &self.features {
+            *counts.entry(feature.feature_type).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Compute feature coverage for a sequence overview.
+    ///
+    /// All feature spans (including every part of a `Join`) are unioned before
+    /// counting, so overlapping features are only counted once. On circular
+    /// sequences a span with `start > end` wraps through the origin.
+    pub fn coverage_stats(&self) -> CoverageStats {
+        let len = self.len();
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        for feature in &self.features {
+            for (start, end) in feature.location.ranges() {
+                let (start, end) = (start.min(len), end.min(len));
+                if start < end {
+                    spans.push((start, end));
+                } else if start > end && self.is_circular() {
+                    spans.push((start, len));
+                    spans.push((0, end));
+                }
+            }
+        }
+        spans.sort_unstable();
+
+        let mut covered_bases = 0;
+        let mut current: Option<(usize, usize)> = None;
+        for (start, end) in spans {
+            match current {
+                Some((s, e)) if start <= e => current = Some((s, e.max(end))),
+                Some((s, e)) => {
+                    covered_bases += e - s;
+                    current = Some((start, end));
+                }
+                None => current = Some((start, end)),
+            }
+        }
+        if let Some((s, e)) = current {
+            covered_bases += e - s;
+        }
+
+        let (fraction, features_per_kb) = if len == 0 {
+            (0.0, 0.0)
+        } else {
+            (
+                covered_bases as f64 / len as f64,
+                self.features.len() as f64 * 1000.0 / len as f64,
+            )
+        };
+
+        CoverageStats {
+            covered_bases,
+            fraction,
+            features_per_kb,
+        }
     }
 }
 
@@ -139,6 +609,92 @@ mod tests {
         assert!(!seq.is_circular());
     }
 
+    #[test]
+    fn test_reverse_complement_cache() {
+        let mut seq = Sequence::new("rc", "AACCGGTTA", Topology::Linear);
+        assert_eq!(
+            seq.reverse_complement_str(),
+            crate::operations::reverse_complement("AACCGGTTA")
+        );
+
+        seq.replace_range(0, 2, "GG");
+        assert_eq!(seq.sequence, "GGCCGGTTA");
+        assert_eq!(seq.reverse_complement_str(), "TAACCGGCC");
+
+        seq.set_sequence("ATG");
+        assert_eq!(seq.reverse_complement_str(), "CAT");
+    }
+
+    #[test]
+    fn test_features_at_position_tracks_edits() {
+        let mut seq = Sequence::new("test", "A".repeat(100), Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Gene, 10, 30, Strand::Forward));
+        assert_eq!(seq.features_at_position(20).len(), 1);
+        assert!(seq.features_at_position(30).is_empty());
+
+        // Adding a feature rebuilds the cached index
+        seq.add_feature(Feature::new("b", FeatureType::Cds, 25, 50, Strand::Forward));
+        let names: Vec<_> = seq.features_at_position(27).iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(seq.features_in_range(40, 60).len(), 1);
+
+        // So does editing the features in place
+        seq.features_mut()[0].location = Location::simple(60, 70);
+        assert_eq!(seq.features_at_position(27).len(), 1);
+        assert_eq!(seq.features_at_position(65)[0].name, "a");
+    }
+
+    #[test]
+    fn test_dedup_features() {
+        let mut seq = Sequence::new("test", "A".repeat(100), Topology::Linear);
+        let mut a = Feature::new("lac", FeatureType::Promoter, 0, 30, Strand::Forward);
+        a.add_qualifier("label", "lac");
+        a.add_qualifier("note", "x");
+        let mut b = Feature::new("lac", FeatureType::Promoter, 0, 30, Strand::Forward);
+        b.add_qualifier("note", "x");
+        b.add_qualifier("label", "lac");
+        let first_id = a.id;
+        seq.add_feature(a);
+        seq.add_feature(b);
+        seq.add_feature(Feature::new("lac", FeatureType::Promoter, 0, 30, Strand::Reverse));
+
+        assert_eq!(seq.dedup_features(), 1);
+        assert_eq!(seq.features.len(), 2);
+        assert_eq!(seq.features[0].id, first_id);
+        assert_eq!(seq.dedup_features(), 0);
+    }
+
+    #[test]
+    fn test_feature_type_histogram() {
+        let mut seq = Sequence::new("test", "A".repeat(100), Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Cds, 0, 30, Strand::Forward));
+        seq.add_feature(Feature::new("b", FeatureType::Cds, 40, 70, Strand::Reverse));
+        seq.add_feature(Feature::new("p", FeatureType::Promoter, 70, 90, Strand::Forward));
+
+        let histogram = seq.feature_type_histogram();
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&FeatureType::Cds], 2);
+        assert_eq!(histogram[&FeatureType::Promoter], 1);
+    }
+
+    #[test]
+    fn test_reverse_strand_translation_track() {
+        // Reverse-strand ORF ATG AAA TAG written on the forward strand, with
+        // a flanking base on each side
+        let seq = Sequence::new("rev", "GCTATTTCATG", Topology::Linear);
+        let track = seq.reverse_strand_translation_track(&CodonTable::standard());
+
+        assert_eq!(track.len(), 9);
+        assert!(track.windows(2).all(|w| w[0].0 < w[1].0));
+        let aa_at = |pos: usize| track.iter().find(|(p, _)| *p == pos).unwrap().1;
+        assert_eq!(aa_at(7), 'M'); // CAT at 7..10
+        assert_eq!(aa_at(4), 'K'); // TTT at 4..7
+        assert_eq!(aa_at(1), '*'); // CTA at 1..4
+        assert!(Sequence::new("s", "AC", Topology::Linear)
+            .reverse_strand_translation_track(&CodonTable::standard())
+            .is_empty());
+    }
+
     #[test]
     fn test_circular_subsequence() {
         let seq = Sequence::new("circ", "AABBCCDD", Topology::Circular);
@@ -153,4 +709,210 @@ mod tests {
         let seq = Sequence::new("lin", "AABBCCDD", Topology::Linear);
         assert_eq!(seq.subsequence(6, 2), ""); // no wrap for linear
     }
+
+    #[test]
+    fn test_extract_region_shifts_features() {
+        let mut seq = Sequence::new("src", "A".repeat(1000), Topology::Linear);
+        seq.add_feature(Feature::new("inside", FeatureType::Cds, 300, 400, Strand::Forward));
+        seq.add_feature(Feature::new("straddle", FeatureType::Misc, 150, 250, Strand::Reverse));
+        seq.add_feature(Feature::new("outside", FeatureType::Misc, 800, 900, Strand::Forward));
+
+        let region = seq.extract_region(200, 500);
+        assert_eq!(region.len(), 300);
+        assert_eq!(region.features.len(), 2);
+
+        let inside = region.features.iter().find(|f| f.name == "inside").unwrap();
+        assert_eq!((inside.start(), inside.end()), (100, 200));
+        let straddle = region.features.iter().find(|f| f.name == "straddle").unwrap();
+        assert_eq!((straddle.start(), straddle.end()), (0, 50));
+    }
+
+    #[test]
+    fn test_extract_region_wrapping() {
+        let mut seq = Sequence::new("circ", "AACCGGTT", Topology::Circular);
+        seq.add_feature(Feature::new("tail", FeatureType::Misc, 6, 8, Strand::Forward));
+        seq.add_feature(Feature::new("head", FeatureType::Misc, 0, 1, Strand::Forward));
+
+        let region = seq.extract_region(6, 2);
+        assert_eq!(region.sequence, "TTAA");
+        let tail = region.features.iter().find(|f| f.name == "tail").unwrap();
+        assert_eq!((tail.start(), tail.end()), (0, 2));
+        let head = region.features.iter().find(|f| f.name == "head").unwrap();
+        assert_eq!((head.start(), head.end()), (2, 3));
+    }
+
+    #[test]
+    fn test_feature_conflicts_same_strand() {
+        let mut seq = Sequence::new("cds", "A".repeat(1000), Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Cds, 100, 400, Strand::Forward));
+        seq.add_feature(Feature::new("b", FeatureType::Cds, 302, 700, Strand::Forward));
+        seq.add_feature(Feature::new("c", FeatureType::Cds, 350, 600, Strand::Reverse));
+        seq.add_feature(Feature::new("promoter", FeatureType::Promoter, 300, 450, Strand::Forward));
+
+        let conflicts = seq.feature_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].first, seq.features[0].id);
+        assert_eq!(conflicts[0].second, seq.features[1].id);
+        assert_eq!((conflicts[0].overlap_start, conflicts[0].overlap_end), (302, 400));
+        assert!(!conflicts[0].in_frame);
+    }
+
+    #[test]
+    fn test_feature_conflicts_in_frame() {
+        let mut seq = Sequence::new("cds", "A".repeat(1000), Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Cds, 100, 400, Strand::Reverse));
+        seq.add_feature(Feature::new("b", FeatureType::Cds, 200, 700, Strand::Reverse));
+
+        let conflicts = seq.feature_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].in_frame);
+    }
+
+    #[test]
+    fn test_coverage_stats_counts_overlap_once() {
+        let mut seq = Sequence::new("cov", "A".repeat(1000), Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Cds, 100, 300, Strand::Forward));
+        seq.add_feature(Feature::new("b", FeatureType::Misc, 200, 400, Strand::Forward));
+
+        let stats = seq.coverage_stats();
+        assert_eq!(stats.covered_bases, 300);
+        assert!((stats.fraction - 0.3).abs() < 1e-9);
+        assert!((stats.features_per_kb - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_coverage_stats_join() {
+        let mut seq = Sequence::new("cov", "A".repeat(100), Topology::Linear);
+        let mut f = Feature::new("split", FeatureType::Cds, 0, 10, Strand::Forward);
+        f.location = Location::join(vec![(0, 10), (50, 60)]);
+        seq.add_feature(f);
+
+        assert_eq!(seq.coverage_stats().covered_bases, 20);
+    }
+
+    #[test]
+    fn test_merge_overlapping_features() {
+        let mut seq = Sequence::new("merge", "A".repeat(500), Topology::Linear);
+        let mut a = Feature::new("GFP", FeatureType::Cds, 100, 300, Strand::Forward);
+        a.add_qualifier("gene", "gfp");
+        let mut b = Feature::new("GFP", FeatureType::Cds, 250, 400, Strand::Forward);
+        b.add_qualifier("note", "auto");
+        seq.add_feature(a);
+        seq.add_feature(b);
+        seq.add_feature(Feature::new("lac", FeatureType::Promoter, 50, 120, Strand::Forward));
+
+        assert_eq!(seq.merge_features(true, 0), 1);
+        assert_eq!(seq.features.len(), 2);
+        let cds = seq.features.iter().find(|f| f.feature_type == FeatureType::Cds).unwrap();
+        assert_eq!((cds.start(), cds.end()), (100, 400));
+        assert_eq!(cds.get_qualifier("gene"), Some("gfp"));
+        assert_eq!(cds.get_qualifier("note"), Some("auto"));
+    }
+
+    #[test]
+    fn test_feature_gc() {
+        let seq = Sequence::new("t", "GCGCGCGCGCATATATATATGGCCATATGGCC", Topology::Linear);
+        let gc_rich = Feature::new("gc", FeatureType::Misc, 0, 10, Strand::Forward);
+        let at_rich = Feature::new("at", FeatureType::Misc, 10, 20, Strand::Reverse);
+        assert!(seq.feature_gc(&gc_rich) > seq.feature_gc(&at_rich));
+        assert_eq!(seq.feature_gc(&gc_rich), 1.0);
+        assert_eq!(seq.feature_gc(&at_rich), 0.0);
+
+        // The join skips the AT-rich intron between its exons
+        let mut spliced = Feature::new("exons", FeatureType::Cds, 20, 32, Strand::Forward);
+        spliced.location = Location::join(vec![(20, 24), (28, 32)]);
+        assert_eq!(seq.feature_sequence(&spliced), "GGCCGGCC");
+        assert_eq!(seq.feature_gc(&spliced), 1.0);
+        let span = Feature::new("span", FeatureType::Misc, 20, 32, Strand::Forward);
+        assert!(seq.feature_gc(&span) < 1.0);
+    }
+
+    #[test]
+    fn test_feature_sequence_two_exon_join() {
+        // Exons ATGAAA and GGGTAA split by a CCCCC intron
+        let seq = Sequence::new("gene", "TTATGAAACCCCCGGGTAATT", Topology::Linear);
+        let mut cds = Feature::new("cds", FeatureType::Cds, 2, 19, Strand::Forward);
+        cds.location = Location::join(vec![(2, 8), (13, 19)]);
+        assert_eq!(seq.feature_sequence(&cds), "ATGAAAGGGTAA");
+
+        // The same exons read from the other strand
+        cds.strand = Strand::Reverse;
+        assert_eq!(seq.feature_sequence(&cds), "TTACCCTTTCAT");
+        cds.strand = Strand::Forward;
+        cds.location = Location::Complement { inner: Box::new(cds.location.clone()) };
+        assert_eq!(seq.feature_sequence(&cds), "TTACCCTTTCAT");
+    }
+
+    #[test]
+    fn test_feature_sequence_wraps_origin() {
+        let seq = Sequence::new("plasmid", "GGGTAATTTTATGAAA", Topology::Circular);
+        let mut cds = Feature::new("cds", FeatureType::Cds, 10, 6, Strand::Forward);
+        assert_eq!(seq.feature_sequence(&cds), "ATGAAAGGGTAA");
+        cds.location = Location::join(vec![(10, 16), (0, 6)]);
+        assert_eq!(seq.feature_sequence(&cds), "ATGAAAGGGTAA");
+    }
+
+    #[test]
+    fn test_doubled_for_search() {
+        let seq = Sequence::new("p", "ACGTTGCA", Topology::Circular);
+        let doubled = seq.doubled_for_search(3);
+        assert_eq!(doubled, "ACGTTGCAACG");
+        // Positions past the end are the origin bases again
+        for p in seq.len()..doubled.len() {
+            assert_eq!(doubled.as_bytes()[p], seq.sequence.as_bytes()[p % seq.len()]);
+        }
+        assert_eq!(seq.doubled_for_search(0), seq.sequence);
+        assert_eq!(seq.doubled_for_search(100), "ACGTTGCA".repeat(2));
+    }
+
+    #[test]
+    fn test_translate_feature_codon_start() {
+        let table = CodonTable::standard();
+        // One leftover base, then ATG AAA TGG TAA GGG
+        let seq = Sequence::new("cds", "CATGAAATGGTAAGGG", Topology::Linear);
+        let mut cds = Feature::new("cds", FeatureType::Cds, 0, 16, Strand::Forward);
+        assert_eq!(seq.translate_feature(&cds, &table).as_deref(), Some("HEMVR"));
+        cds.add_qualifier("codon_start", "2");
+        assert_eq!(seq.translate_feature(&cds, &table).as_deref(), Some("MKW"));
+
+        let gene = Feature::new("gene", FeatureType::Gene, 0, 16, Strand::Forward);
+        assert_eq!(seq.translate_feature(&gene, &table), None);
+    }
+
+    #[test]
+    fn test_translate_feature_reverse_strand() {
+        let table = CodonTable::standard();
+        // Reverse complement of ATG AAA TGG TAA, with flanking bases
+        let seq = Sequence::new("cds", "GGTTACCATTTCATGG", Topology::Linear);
+        let cds = Feature::new("cds", FeatureType::Cds, 2, 14, Strand::Reverse);
+        assert_eq!(seq.translate_feature(&cds, &table).as_deref(), Some("MKW"));
+    }
+
+    #[test]
+    fn test_relabel_features() {
+        let mut seq = Sequence::new("test", "A".repeat(500), Topology::Linear);
+        seq.add_feature(Feature::new("CDS", FeatureType::Cds, 300, 400, Strand::Forward));
+        seq.add_feature(Feature::new("", FeatureType::Cds, 10, 100, Strand::Reverse));
+        seq.add_feature(Feature::new("gfp", FeatureType::Cds, 150, 250, Strand::Forward));
+        seq.add_feature(Feature::new("cds", FeatureType::Cds, 200, 290, Strand::Forward));
+        seq.add_feature(Feature::new("promoter", FeatureType::Promoter, 0, 10, Strand::Forward));
+
+        assert_eq!(seq.relabel_features("{type}_{n}"), 4);
+        let names: Vec<&str> = seq.features.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["CDS_3", "CDS_1", "gfp", "CDS_2", "promoter_1"]);
+        assert_eq!(seq.relabel_features("{type}_{n}"), 0);
+    }
+
+    #[test]
+    fn test_merge_respects_gap_and_strand() {
+        let mut seq = Sequence::new("merge", "A".repeat(500), Topology::Linear);
+        seq.add_feature(Feature::new("a", FeatureType::Misc, 0, 100, Strand::Forward));
+        seq.add_feature(Feature::new("b", FeatureType::Misc, 110, 200, Strand::Forward));
+        seq.add_feature(Feature::new("c", FeatureType::Misc, 150, 250, Strand::Reverse));
+
+        assert_eq!(seq.merge_features(true, 5), 0);
+        assert_eq!(seq.merge_features(true, 10), 1);
+        assert_eq!(seq.features.len(), 2);
+        assert_eq!((seq.features[0].start(), seq.features[0].end()), (0, 200));
+    }
 }
@@ -0,0 +1,119 @@
+//! Amino acid substitution matrices for protein alignment.
+
+/// Residue order of the NCBI matrix files: the 20 amino acids, then the
+/// ambiguity codes B (D/N), Z (E/Q) and X (any), then the stop `*`.
+const NCBI_ALPHABET: &[u8; 24] = b"ARNDCQEGHILKMFPSTWYVBZX*";
+
+#[rustfmt::skip]
+const BLOSUM62: [[i8; 24]; 24] = [
+    //A   R   N   D   C   Q   E   G   H   I   L   K   M   F   P   S   T   W   Y   V   B   Z   X   *
+    [ 4, -1, -2, -2,  0, -1, -1,  0, -2, -1, -1, -1, -1, -2, -1,  1,  0, -3, -2,  0, -2, -1,  0, -4], // A
+    [-1,  5,  0, -2, -3,  1,  0, -2,  0, -3, -2,  2, -1, -3, -2, -1, -1, -3, -2, -3, -1,  0, -1, -4], // R
+    [-2,  0,  6,  1, -3,  0,  0,  0,  1, -3, -3,  0, -2, -3, -2,  1,  0, -4, -2, -3,  3,  0, -1, -4], // N
+    [-2, -2,  1,  6, -3,  0,  2, -1, -1, -3, -4, -1, -3, -3, -1,  0, -1, -4, -3, -3,  4,  1, -1, -4], // D
+    [ 0, -3, -3, -3,  9, -3, -4, -3, -3, -1, -1, -3, -1, -2, -3, -1, -1, -2, -2, -1, -3, -3, -2, -4], // C
+    [-1,  1,  0,  0, -3,  5,  2, -2,  0, -3, -2,  1,  0, -3, -1,  0, -1, -2, -1, -2,  0,  3, -1, -4], // Q
+    [-1,  0,  0,  2, -4,  2,  5, -2,  0, -3, -3,  1, -2, -3, -1,  0, -1, -3, -2, -2,  1,  4, -1, -4], // E
+    [ 0, -2,  0, -1, -3, -2, -2,  6, -2, -4, -4, -2, -3, -3, -2,  0, -2, -2, -3, -3, -1, -2, -1, -4], // G
+    [-2,  0,  1, -1, -3,  0,  0, -2,  8, -3, -3, -1, -2, -1, -2, -1, -2, -2,  2, -3,  0,  0, -1, -4], // H
+    [-1, -3, -3, -3, -1, -3, -3, -4, -3,  4,  2, -3,  1,  0, -3, -2, -1, -3, -1,  3, -3, -3, -1, -4], // I
+    [-1, -2, -3, -4, -1, -2, -3, -4, -3,  2,  4, -2,  2,  0, -3, -2, -1, -2, -1,  1, -4, -3, -1, -4], // L
+    [-1,  2,  0, -1, -3,  1,  1, -2, -1, -3, -2,  5, -1, -3, -1,  0, -1, -3, -2, -2,  0,  1, -1, -4], // K
+    [-1, -1, -2, -3, -1,  0, -2, -3, -2,  1,  2, -1,  5,  0, -2, -1, -1, -1, -1,  1, -3, -1, -1, -4], // M
+    [-2, -3, -3, -3, -2, -3, -3, -3, -1,  0,  0, -3,  0,  6, -4, -2, -2,  1,  3, -1, -3, -3, -1, -4], // F
+    [-1, -2, -2, -1, -3, -1, -1, -2, -2, -3, -3, -1, -2, -4,  7, -1, -1, -4, -3, -2, -2, -1, -2, -4], // P
+    [ 1, -1,  1,  0, -1,  0,  0,  0, -1, -2, -2,  0, -1, -2, -1,  4,  1, -3, -2, -2,  0,  0,  0, -4], // S
+    [ 0, -1,  0, -1, -1, -1, -1, -2, -2, -1, -1, -1, -1, -2, -1,  1,  5, -2, -2,  0, -1, -1,  0, -4], // T
+    [-3, -3, -4, -4, -2, -2, -3, -2, -2, -3, -2, -3, -1,  1, -4, -3, -2, 11,  2, -3, -4, -3, -2, -4], // W
+    [-2, -2, -2, -3, -2, -1, -2, -3,  2, -1, -1, -2, -1,  3, -3, -2, -2,  2,  7, -1, -3, -2, -1, -4], // Y
+    [ 0, -3, -3, -3, -1, -2, -2, -3, -3,  3,  1, -2,  1, -1, -2, -2,  0, -3, -1,  4, -3, -2, -1, -4], // V
+    [-2, -1,  3,  4, -3,  0,  1, -1,  0, -3, -4,  0, -3, -3, -2,  0, -1, -4, -3, -3,  4,  1, -1, -4], // B
+    [-1,  0,  0,  1, -3,  3,  4, -2,  0, -3, -3,  1, -1, -3, -1,  0, -1, -3, -2, -2,  1,  4, -1, -4], // Z
+    [ 0, -1, -1, -1, -2, -1, -1, -1, -1, -1, -1, -1, -1, -1, -2,  0,  0, -2, -1, -1, -1, -1, -1, -4], // X
+    [-4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4,  1], // *
+];
+
+/// Pairwise residue scores for protein alignment.
+///
+/// Lookups are case-insensitive; residues outside the matrix alphabet are
+/// scored as its last ambiguity code (X for the built-in matrices).
+#[derive(Debug, Clone)]
+pub struct SubstitutionMatrix {
+    pub name: String,
+    alphabet: Vec<u8>,
+    scores: Vec<i32>,
+    /// Row in `alphabet` for each byte value
+    index: [u8; 256],
+}
+
+impl SubstitutionMatrix {
+    /// Build a matrix over `alphabet` from row-major `scores`
+    /// (`alphabet.len()` squared of them). Unknown residues score as the
+    /// `fallback` residue, which must be in `alphabet`.
+    pub fn new(name: impl Into<String>, alphabet: &[u8], scores: Vec<i32>, fallback: u8) -> Self {
+        assert_eq!(scores.len(), alphabet.len() * alphabet.len(), "matrix must be square");
+        assert!(alphabet.len() <= u8::MAX as usize, "alphabet too large");
+        let fallback = alphabet
+            .iter()
+            .position(|&r| r == fallback.to_ascii_uppercase())
+            .expect("fallback residue must be in the alphabet") as u8;
+
+        let mut index = [fallback; 256];
+        for (i, &residue) in alphabet.iter().enumerate() {
+            index[residue.to_ascii_uppercase() as usize] = i as u8;
+            index[residue.to_ascii_lowercase() as usize] = i as u8;
+        }
+        Self {
+            name: name.into(),
+            alphabet: alphabet.to_ascii_uppercase(),
+            scores,
+            index,
+        }
+    }
+
+    /// BLOSUM62, the default matrix of protein BLAST.
+    pub fn blosum62() -> Self {
+        let scores = BLOSUM62.iter().flatten().map(|&s| s as i32).collect();
+        Self::new("BLOSUM62", NCBI_ALPHABET, scores, b'X')
+    }
+
+    /// Residues the matrix has rows for, uppercase.
+    pub fn alphabet(&self) -> &[u8] {
+        &self.alphabet
+    }
+
+    /// Score for aligning residue `a` with residue `b`.
+    pub fn score(&self, a: u8, b: u8) -> i32 {
+        let row = self.index[a as usize] as usize;
+        let col = self.index[b as usize] as usize;
+        self.scores[row * self.alphabet.len() + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blosum62_values() {
+        let m = SubstitutionMatrix::blosum62();
+        assert_eq!(m.score(b'W', b'W'), 11);
+        assert_eq!(m.score(b'C', b'C'), 9);
+        assert_eq!(m.score(b'a', b'A'), 4);
+        assert_eq!(m.score(b'I', b'V'), 3);
+        assert_eq!(m.score(b'W', b'G'), -2);
+        assert_eq!(m.score(b'*', b'*'), 1);
+        // Unknown residues score as X
+        assert_eq!(m.score(b'J', b'A'), m.score(b'X', b'A'));
+    }
+
+    #[test]
+    fn test_blosum62_symmetric() {
+        let m = SubstitutionMatrix::blosum62();
+        for &a in m.alphabet() {
+            for &b in m.alphabet() {
+                assert_eq!(m.score(a, b), m.score(b, a), "{} vs {}", a as char, b as char);
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Adapter trimming for sequencing reads.
+
+use crate::search::find_pattern_mismatches;
+
+/// Shortest adapter fragment trimmed when only part of an adapter overhangs
+/// the 3' end of a read. Shorter fragments match by chance too often.
+pub const MIN_ADAPTER_OVERLAP: usize = 3;
+
+/// Shortest adapter suffix trimmed from the 5' end of a read. Losing the
+/// start of a read to a chance match costs more than a few bases of adapter
+/// left in, so this is stricter than [`MIN_ADAPTER_OVERLAP`]: an exact
+/// 8-base match occurs by chance in about 1 read in 65,000.
+pub const MIN_FIVE_PRIME_OVERLAP: usize = 8;
+
+/// Which end of a read an adapter was removed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ReadEnd {
+    FivePrime,
+    ThreePrime,
+}
+
+/// One adapter removed from a read.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TrimEvent {
+    /// Index into the `adapters` passed to [`trim_adapters`].
+    pub adapter: usize,
+    pub read_end: ReadEnd,
+    /// Start of the removed bases in the original read (0-based, inclusive).
+    pub start: usize,
+    /// End of the removed bases in the original read (0-based, exclusive).
+    pub end: usize,
+    /// Mismatches between the removed bases and the adapter.
+    pub mismatches: usize,
+}
+
+/// Remove adapters from the ends of `seq`, allowing up to `max_mismatches`
+/// substitutions per adapter.
+///
+/// For each adapter in turn:
+/// - 5' end: the longest adapter suffix (at least [`MIN_FIVE_PRIME_OVERLAP`]
+///   bases, or the whole of a shorter adapter) that the read starts with is
+///   removed.
+/// - 3' end: the first complete occurrence of the adapter on the forward
+///   strand is removed along with everything after it. Failing that, the
+///   longest adapter prefix that the read ends with is removed.
+///
+/// A partial adapter of `n` bases is allowed `max_mismatches * n / len`
+/// mismatches, so short overhangs must match (almost) exactly. Returns the
+/// trimmed read, uppercased, and the events in the order they were applied.
+pub fn trim_adapters(
+    seq: &str,
+    adapters: &[&str],
+    max_mismatches: usize,
+) -> (String, Vec<TrimEvent>) {
+    let read = seq.to_uppercase();
+    let bytes = read.as_bytes();
+    let mut events = Vec::new();
+    // The read still left after earlier trims, in original coordinates
+    let (mut start, mut end) = (0, read.len());
+
+    for (index, adapter) in adapters.iter().enumerate() {
+        let adapter_upper = adapter.to_uppercase();
+        let adapter = adapter_upper.as_bytes();
+        if adapter.is_empty() {
+            continue;
+        }
+        let allowed = |overlap: usize| max_mismatches * overlap / adapter.len();
+
+        // 5' end: read[start..start + n] against the last n adapter bases
+        let min_five_prime = MIN_FIVE_PRIME_OVERLAP.min(adapter.len());
+        let five_prime = (min_five_prime..=adapter.len().min(end - start))
+            .rev()
+            .find_map(|n| {
+                let mismatches =
+                    count_mismatches(&bytes[start..start + n], &adapter[adapter.len() - n..]);
+                (mismatches <= allowed(n)).then_some((n, mismatches))
+            });
+        if let Some((n, mismatches)) = five_prime {
+            events.push(TrimEvent {
+                adapter: index,
+                read_end: ReadEnd::FivePrime,
+                start,
+                end: start + n,
+                mismatches,
+            });
+            start += n;
+        }
+
+        // 3' end: a complete adapter anywhere, else a partial one at the end
+        let remaining = &read[start..end];
+        let complete = find_pattern_mismatches(remaining, &adapter_upper, max_mismatches, false)
+            .into_iter()
+            .find(|m| !m.is_complement)
+            .map(|m| (start + m.start, count_mismatches(m.matched.as_bytes(), adapter)));
+        let partial = || {
+            (MIN_ADAPTER_OVERLAP..adapter.len().min(end - start + 1))
+                .rev()
+                .find_map(|n| {
+                    let mismatches = count_mismatches(&bytes[end - n..end], &adapter[..n]);
+                    (mismatches <= allowed(n)).then_some((end - n, mismatches))
+                })
+        };
+        if let Some((cut, mismatches)) = complete.or_else(partial) {
+            events.push(TrimEvent {
+                adapter: index,
+                read_end: ReadEnd::ThreePrime,
+                start: cut,
+                end,
+                mismatches,
+            });
+            end = cut;
+        }
+    }
+
+    (read[start..end].to_string(), events)
+}
+
+fn count_mismatches(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).filter(|(x, y)| x != y).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADAPTER: &str = "AGATCGGAAGAGC";
+
+    #[test]
+    fn test_trim_three_prime_adapter() {
+        let insert = "CCTGACTTGGCATTCAGCCAT";
+        let read = format!("{}{}TTTT", insert, ADAPTER.replace("GAAG", "GTAG"));
+        let (trimmed, events) = trim_adapters(&read, &[ADAPTER], 1);
+        assert_eq!(trimmed, insert);
+        assert_eq!(
+            events,
+            vec![TrimEvent {
+                adapter: 0,
+                read_end: ReadEnd::ThreePrime,
+                start: insert.len(),
+                end: read.len(),
+                mismatches: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_trim_partial_adapters() {
+        // The read runs into the first 6 bases of the adapter
+        let (trimmed, events) = trim_adapters("CCTGACTTGGCAagatcg", &[ADAPTER], 1);
+        assert_eq!(trimmed, "CCTGACTTGGCA");
+        assert_eq!((events[0].start, events[0].end), (12, 18));
+
+        // and starts with the last 8
+        let (trimmed, events) = trim_adapters("GGAAGAGCCCTGACTTGGCA", &[ADAPTER], 1);
+        assert_eq!(trimmed, "CCTGACTTGGCA");
+        assert_eq!(events[0].read_end, ReadEnd::FivePrime);
+        assert_eq!((events[0].start, events[0].end), (0, 8));
+    }
+
+    #[test]
+    fn test_short_five_prime_match_kept() {
+        // A read that happens to start with the adapter's last 4 bases
+        let read = "GAGCCCTGACTTGGCATTCAGCCAT";
+        let (trimmed, events) = trim_adapters(read, &[ADAPTER], 0);
+        assert_eq!(trimmed, read);
+        assert!(events.is_empty());
+
+        // An adapter shorter than the minimum is trimmed when whole
+        let (trimmed, _) = trim_adapters("ACGTCCTGACTTGGCA", &["ACGT"], 0);
+        assert_eq!(trimmed, "CCTGACTTGGCA");
+    }
+
+    #[test]
+    fn test_read_without_adapter_unchanged() {
+        let read = "CCTGACTTGGCATTCAGCCATTT";
+        let (trimmed, events) = trim_adapters(read, &[ADAPTER, "CTGTCTCTTATACACATCT"], 2);
+        assert_eq!(trimmed, read);
+        assert!(events.is_empty());
+    }
+}
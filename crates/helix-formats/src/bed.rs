@@ -0,0 +1,156 @@
+use helix_core::feature::{Feature, FeatureType, Strand};
+
+use crate::{normalize_line_endings, ParseError};
+
+/// Parse BED rows into features
+///
+/// Reads the chrom, start and end columns (0-based half-open, as in
+/// `Location`) and the optional name and strand columns; other columns are
+/// ignored. Features are typed `misc_feature` and unnamed rows are named
+/// `chrom:start-end`. Blank, `#` comment, `track` and `browser` lines are
+/// skipped.
+pub fn parse_features(input: &str) -> Result<Vec<Feature>, ParseError> {
+    Ok(parse_features_with_lines(input)?
+        .into_iter()
+        .map(|(_, feature)| feature)
+        .collect())
+}
+
+/// [`parse_features`], pairing each feature with its 1-based line number
+pub fn parse_features_with_lines(input: &str) -> Result<Vec<(usize, Feature)>, ParseError> {
+    let mut features = Vec::new();
+
+    for (line_no, line) in normalize_line_endings(input).lines().enumerate() {
+        let line_no = line_no + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty()
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("track")
+            || trimmed.starts_with("browser")
+        {
+            continue;
+        }
+
+        let cols: Vec<&str> = line.split('\t').map(str::trim).collect();
+        if cols.len() < 3 {
+            return Err(ParseError::InvalidFormat(format!(
+                "Line {}: expected at least 3 tab-separated BED columns, found {}",
+                line_no,
+                cols.len()
+            )));
+        }
+        let coordinate = |col: &str| {
+            col.parse::<usize>().map_err(|_| {
+                ParseError::InvalidLocation(format!(
+                    "Line {}: invalid coordinate '{}'",
+                    line_no, col
+                ))
+            })
+        };
+        let (start, end) = (coordinate(cols[1])?, coordinate(cols[2])?);
+        if start >= end {
+            return Err(ParseError::InvalidLocation(format!(
+                "Line {}: start {} is not before end {}",
+                line_no, start, end
+            )));
+        }
+
+        let name = match cols.get(3) {
+            Some(name) if !name.is_empty() && *name != "." => name.to_string(),
+            _ => format!("{}:{}-{}", cols[0], start, end),
+        };
+        let strand = match cols.get(5) {
+            Some(&"+") => Strand::Forward,
+            Some(&"-") => Strand::Reverse,
+            _ => Strand::None,
+        };
+        features.push((
+            line_no,
+            Feature::new(name, FeatureType::Misc, start, end, strand),
+        ));
+    }
+
+    Ok(features)
+}
+
+/// Serialize features as BED6 rows on chromosome `chrom`
+///
+/// BED uses the same 0-based, half-open coordinates as `Location`. Join
+/// locations are written as their overall span. The score column is always 0.
+pub fn serialize_features(features: &[Feature], chrom: &str) -> String {
+    let mut out = String::new();
+    for feature in features {
+        let strand = match feature.strand {
+            Strand::Forward => '+',
+            Strand::Reverse => '-',
+            Strand::None => '.',
+        };
+        // BED names are whitespace-delimited on many parsers
+        let name = feature.display_name().replace(char::is_whitespace, "_");
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t0\t{}\n",
+            chrom,
+            feature.start(),
+            feature.end(),
+            name,
+            strand
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_features() {
+        let features = vec![
+            Feature::new("lac promoter", FeatureType::Promoter, 0, 31, Strand::Forward),
+            Feature::new("AmpR", FeatureType::Resistance, 100, 961, Strand::Reverse),
+        ];
+        assert_eq!(
+            serialize_features(&features, "pUC19"),
+            "pUC19\t0\t31\tlac_promoter\t0\t+\npUC19\t100\t961\tAmpR\t0\t-\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_features() {
+        let bed = "track name=parts
+# comment
+pTest\t0\t31\tlac_promoter\t0\t+
+pTest\t100\t961\tAmpR\t0\t-
+
+pTest\t5\t10
+";
+        let features = parse_features_with_lines(bed).unwrap();
+        assert_eq!(features.len(), 3);
+        let (line, promoter) = &features[0];
+        assert_eq!(*line, 3);
+        assert_eq!(promoter.name, "lac_promoter");
+        assert_eq!((promoter.start(), promoter.end()), (0, 31));
+        assert_eq!(promoter.strand, Strand::Forward);
+        assert_eq!(features[1].1.strand, Strand::Reverse);
+        assert_eq!(features[2].0, 6);
+        assert_eq!(features[2].1.name, "pTest:5-10");
+        assert_eq!(features[2].1.strand, Strand::None);
+    }
+
+    #[test]
+    fn test_parse_roundtrip_and_errors() {
+        let features = vec![
+            Feature::new("lac promoter", FeatureType::Promoter, 0, 31, Strand::Forward),
+            Feature::new("AmpR", FeatureType::Resistance, 100, 961, Strand::Reverse),
+        ];
+        let parsed = parse_features(&serialize_features(&features, "pTest")).unwrap();
+        assert_eq!(parsed[0].name, "lac_promoter");
+        assert_eq!((parsed[1].start(), parsed[1].end()), (100, 961));
+
+        assert!(matches!(parse_features("pTest\t5\n"), Err(ParseError::InvalidFormat(_))));
+        assert!(matches!(
+            parse_features("pTest\t10\t5\n"),
+            Err(ParseError::InvalidLocation(_))
+        ));
+    }
+}
@@ -10,6 +10,10 @@ pub fn detect_format(content: &str) -> FileFormat {
         FileFormat::Fasta
     } else if trimmed.starts_with("ID ") {
         FileFormat::Embl
+    } else if trimmed.starts_with('@')
+        && trimmed.lines().nth(2).is_some_and(|line| line.starts_with('+'))
+    {
+        FileFormat::Fastq
     } else {
         FileFormat::Unknown
     }
@@ -28,6 +32,8 @@ pub fn detect_format_from_extension(path: &str) -> FileFormat {
         FileFormat::Fasta
     } else if lower.ends_with(".embl") {
         FileFormat::Embl
+    } else if lower.ends_with(".fastq") || lower.ends_with(".fq") {
+        FileFormat::Fastq
     } else if lower.ends_with(".dna") {
         FileFormat::SnapGene
     } else {
@@ -55,6 +61,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_fastq() {
+        assert_eq!(
+            detect_format("\n@read1\nACGT\n+\nIIII\n"),
+            FileFormat::Fastq
+        );
+        assert_eq!(detect_format("@read1\nACGT\n"), FileFormat::Unknown);
+        assert_eq!(detect_format_from_extension("reads.fq"), FileFormat::Fastq);
+    }
+
     #[test]
     fn test_detect_from_extension() {
         assert_eq!(detect_format_from_extension("test.gb"), FileFormat::GenBank);
@@ -0,0 +1,331 @@
+use helix_core::sequence::{Sequence, SequenceMetadata, Topology};
+
+use crate::{genbank, normalize_line_endings, parse_lineage, ParseError};
+
+/// Parse an EMBL flat file into a Sequence.
+///
+/// Reads the ID line (name, topology, molecule type, division and length), AC,
+/// DE, KW, OS and OC lines, the FT feature table and the SQ sequence block. The
+/// feature table uses the GenBank location and qualifier syntax, so it is
+/// parsed with the same code.
+pub fn parse(input: &str) -> Result<Sequence, ParseError> {
+    let mut seq = Sequence::new("", "", Topology::Linear);
+    seq.metadata = SequenceMetadata::default();
+
+    let input = normalize_line_endings(input);
+    let mut declared_len = None;
+    let mut accessions: Vec<String> = Vec::new();
+    let mut description = String::new();
+    let mut keywords = String::new();
+    let mut lineage = String::new();
+    // FT lines with the two-letter code blanked, which leaves the GenBank
+    // feature table layout (key at column 5, location at column 21)
+    let mut feature_lines: Vec<String> = Vec::new();
+    let mut bases = String::new();
+    let mut in_sequence = false;
+
+    for line in input.lines() {
+        if line.starts_with("//") {
+            break;
+        }
+        if in_sequence {
+            // Sequence lines: "     aaacctgcac ggtgaaccca        60"
+            bases.extend(
+                line.chars()
+                    .filter(|c| c.is_ascii_alphabetic())
+                    .map(|c| c.to_ascii_uppercase()),
+            );
+            continue;
+        }
+
+        let code = line.get(..2).unwrap_or(line);
+        let value = line.get(5..).unwrap_or("").trim();
+        match code {
+            "ID" => declared_len = parse_id_line(value, &mut seq)?,
+            "AC" => accessions.extend(
+                value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|a| !a.is_empty())
+                    .map(str::to_string),
+            ),
+            "DE" => {
+                if !description.is_empty() {
+                    description.push(' ');
+                }
+                description.push_str(value);
+            }
+            "KW" => {
+                if !keywords.is_empty() {
+                    keywords.push(' ');
+                }
+                keywords.push_str(value);
+            }
+            "OS" => {
+                seq.metadata.organism = Some(value.to_string());
+                seq.metadata.source = Some(value.to_string());
+            }
+            "OC" => {
+                lineage.push(' ');
+                lineage.push_str(value);
+            }
+            "FT" => feature_lines.push(format!("  {}", &line[2..])),
+            "SQ" => in_sequence = true,
+            _ => {}
+        }
+    }
+
+    if !accessions.is_empty() {
+        seq.metadata.accession = Some(accessions.join(" "));
+    }
+    if !description.is_empty() {
+        let description = description.trim_end_matches('.').to_string();
+        seq.description = description.clone();
+        seq.metadata.definition = Some(description);
+    }
+    let keywords = keywords.trim_end_matches('.');
+    if !keywords.is_empty() {
+        seq.metadata.keywords = Some(keywords.to_string());
+    }
+
+    seq.metadata.taxonomy = parse_lineage(&lineage);
+
+    let lines: Vec<&str> = feature_lines.iter().map(String::as_str).collect();
+    let mut i = 0;
+    genbank::parse_features(&lines, &mut i, seq.features_mut());
+
+    if let Some(len) = declared_len {
+        if len != bases.len() {
+            return Err(ParseError::InvalidFormat(format!(
+                "ID line declares {} bp but the sequence has {}",
+                len,
+                bases.len()
+            )));
+        }
+    }
+    seq.set_sequence(bases);
+
+    Ok(seq)
+}
+
+/// Parse the ID line, e.g. `X56734; SV 1; linear; mRNA; STD; PLN; 1859 BP.`
+/// Returns the declared length, if any.
+fn parse_id_line(value: &str, seq: &mut Sequence) -> Result<Option<usize>, ParseError> {
+    let parts: Vec<&str> = value.split(';').map(str::trim).collect();
+    seq.name = parts[0].to_string();
+
+    let mut declared_len = None;
+    for part in &parts[1..] {
+        let lower = part.to_lowercase();
+        if lower == "circular" {
+            seq.topology = Topology::Circular;
+        } else if lower == "linear" {
+            seq.topology = Topology::Linear;
+        } else if lower.contains("dna") || lower.contains("rna") {
+            seq.metadata.molecule_type = Some(part.to_string());
+        } else if let Some(len) = lower.strip_suffix("bp.").or_else(|| lower.strip_suffix("bp")) {
+            let len = len.trim();
+            declared_len = Some(len.parse().map_err(|_| {
+                ParseError::InvalidFormat(format!("Invalid sequence length '{}' on ID line", len))
+            })?);
+        }
+    }
+    // Accession; SV version; topology; molecule; data class; division; length
+    if parts.len() == 7 {
+        seq.metadata.division = Some(parts[5].to_string());
+    }
+
+    Ok(declared_len)
+}
+
+/// Serialize a Sequence to EMBL format
+pub fn serialize(seq: &Sequence) -> String {
+    let mut out = String::new();
+
+    let mol_type = seq.metadata.molecule_type.as_deref().unwrap_or("DNA");
+    let division = seq.metadata.division.as_deref().unwrap_or("SYN");
+    out.push_str(&format!(
+        "ID   {}; SV 1; {}; {}; STD; {}; {} BP.\nXX\n",
+        seq.name,
+        seq.topology,
+        mol_type,
+        division,
+        seq.len()
+    ));
+
+    if let Some(acc) = &seq.metadata.accession {
+        let accessions: Vec<&str> = acc.split_whitespace().collect();
+        out.push_str(&format!("AC   {};\nXX\n", accessions.join("; ")));
+    }
+
+    if !seq.description.is_empty() {
+        out.push_str(&format!("DE   {}.\nXX\n", seq.description));
+    }
+
+    if let Some(kw) = &seq.metadata.keywords {
+        out.push_str(&format!("KW   {}.\nXX\n", kw.trim_end_matches('.')));
+    }
+
+    if let Some(org) = &seq.metadata.organism {
+        out.push_str(&format!("OS   {}\n", org));
+        if !seq.metadata.taxonomy.is_empty() {
+            let lineage = format!("{}.", seq.metadata.taxonomy.join("; "));
+            for line in genbank::wrap_qualifier(&lineage, 80 - 5, false) {
+                out.push_str(&format!("OC   {}\n", line));
+            }
+        }
+        out.push_str("XX\n");
+    }
+
+    if !seq.features().is_empty() {
+        out.push_str("FH   Key             Location/Qualifiers\nFH\n");
+        for feat in seq.features() {
+            let key = feat.feature_type.to_genbank_key();
+            let loc_str = genbank::serialize_location(&feat.location, &feat.strand);
+            out.push_str(&format!("FT   {:<16}{}\n", key, loc_str));
+            for line in genbank::qualifier_lines(feat, 80 - 21) {
+                out.push_str(&format!("FT                   {}\n", line));
+            }
+        }
+        out.push_str("XX\n");
+    }
+
+    let count = |base: char| {
+        seq.sequence()
+            .chars()
+            .filter(|c| c.eq_ignore_ascii_case(&base))
+            .count()
+    };
+    let (a, c, g, t) = (count('A'), count('C'), count('G'), count('T'));
+    out.push_str(&format!(
+        "SQ   Sequence {} BP; {} A; {} C; {} G; {} T; {} other;\n",
+        seq.len(),
+        a,
+        c,
+        g,
+        t,
+        seq.len() - a - c - g - t
+    ));
+
+    // 60 bases per line in groups of 10, with the running count right-aligned
+    // to column 80
+    let bases: Vec<char> = seq.sequence().to_lowercase().chars().collect();
+    for (chunk_idx, chunk) in bases.chunks(60).enumerate() {
+        let groups: Vec<String> = chunk.chunks(10).map(|g| g.iter().collect()).collect();
+        let pos = chunk_idx * 60 + chunk.len();
+        out.push_str(&format!("     {:<66}{:>9}\n", groups.join(" "), pos));
+    }
+
+    out.push_str("//\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_core::feature::{FeatureType, Location, Strand};
+
+    const MINI_EMBL: &str = "ID   pTest; SV 1; circular; DNA; STD; SYN; 100 BP.
+XX
+AC   AB000001; AB000002;
+XX
+DE   Test plasmid with a
+DE   split description.
+XX
+KW   cloning vector.
+XX
+OS   Escherichia coli
+OC   Bacteria; Pseudomonadota; Gammaproteobacteria; Enterobacterales;
+OC   Enterobacteriaceae; Escherichia.
+XX
+FH   Key             Location/Qualifiers
+FH
+FT   promoter        1..20
+FT                   /label=\"test promoter\"
+FT   CDS             complement(join(30..50,61..90))
+FT                   /gene=\"gfp\"
+FT                   /codon_start=1
+FT                   /note=\"a note long enough to wrap onto a second
+FT                   line of the feature table\"
+XX
+SQ   Sequence 100 BP; 25 A; 25 C; 25 G; 25 T; 0 other;
+     atcgatcgat cgatcgatcg atcgatcgat cgatcgatcg atcgatcgat cgatcgatcg        60
+     atcgatcgat cgatcgatcg atcgatcgat cgatcgatcg                              100
+//
+";
+
+    #[test]
+    fn test_parse_mini_embl() {
+        let seq = parse(MINI_EMBL).unwrap();
+        assert_eq!(seq.name, "pTest");
+        assert_eq!(seq.topology, Topology::Circular);
+        assert_eq!(seq.len(), 100);
+        assert_eq!(seq.description, "Test plasmid with a split description");
+        assert_eq!(seq.metadata.accession.as_deref(), Some("AB000001 AB000002"));
+        assert_eq!(seq.metadata.molecule_type.as_deref(), Some("DNA"));
+        assert_eq!(seq.metadata.division.as_deref(), Some("SYN"));
+        assert_eq!(seq.metadata.keywords.as_deref(), Some("cloning vector"));
+        assert_eq!(seq.metadata.organism.as_deref(), Some("Escherichia coli"));
+        assert_eq!(seq.metadata.taxonomy.len(), 6);
+        assert_eq!(seq.metadata.taxonomy[0], "Bacteria");
+        assert_eq!(seq.metadata.taxonomy[5], "Escherichia");
+        assert!(seq.sequence().starts_with("ATCGATCG"));
+    }
+
+    #[test]
+    fn test_parse_embl_features() {
+        let seq = parse(MINI_EMBL).unwrap();
+        assert_eq!(seq.features().len(), 2);
+
+        let promoter = &seq.features()[0];
+        assert_eq!(promoter.name, "test promoter");
+        assert_eq!(promoter.feature_type, FeatureType::Promoter);
+        assert_eq!((promoter.start(), promoter.end()), (0, 20));
+
+        let cds = &seq.features()[1];
+        assert_eq!(cds.name, "gfp");
+        assert_eq!(cds.strand, Strand::Reverse);
+        assert_eq!(cds.location, Location::join(vec![(29, 50), (60, 90)]));
+        assert_eq!(
+            cds.get_qualifier("note"),
+            Some("a note long enough to wrap onto a second line of the feature table")
+        );
+    }
+
+    #[test]
+    fn test_embl_roundtrip() {
+        let seq = parse(MINI_EMBL).unwrap();
+        let reparsed = parse(&serialize(&seq)).unwrap();
+
+        assert_eq!(reparsed.name, seq.name);
+        assert_eq!(reparsed.topology, seq.topology);
+        assert_eq!(reparsed.sequence(), seq.sequence());
+        assert_eq!(reparsed.description, seq.description);
+        assert_eq!(reparsed.metadata.accession, seq.metadata.accession);
+        assert_eq!(reparsed.metadata.taxonomy, seq.metadata.taxonomy);
+        assert_eq!(reparsed.features().len(), seq.features().len());
+        for (a, b) in reparsed.features().iter().zip(seq.features()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.location, b.location);
+            assert_eq!(a.strand, b.strand);
+            assert_eq!(a.get_qualifier("note"), b.get_qualifier("note"));
+        }
+    }
+
+    #[test]
+    fn test_serialize_sequence_block() {
+        let seq = parse(MINI_EMBL).unwrap();
+        let out = serialize(&seq);
+        assert!(out.starts_with("ID   pTest; SV 1; circular; DNA; STD; SYN; 100 BP.\n"));
+        assert!(out.contains("SQ   Sequence 100 BP; 25 A; 25 C; 25 G; 25 T; 0 other;\n"));
+        let last = out.lines().rev().nth(1).unwrap();
+        assert_eq!(last.len(), 80);
+        assert!(last.ends_with(" 100"));
+    }
+
+    #[test]
+    fn test_declared_length_mismatch() {
+        let bad = MINI_EMBL.replace("100 BP.", "120 BP.");
+        assert!(matches!(parse(&bad), Err(ParseError::InvalidFormat(_))));
+    }
+}
@@ -1,66 +1,123 @@
 use helix_core::sequence::{Sequence, Topology};
 
-use crate::ParseError;
+use crate::{normalize_line_endings, ParseError};
+
+/// Name, description and accession read from a FASTA header
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Header {
+    name: String,
+    description: Option<String>,
+    accession: Option<String>,
+}
+
+/// Split a header (without `>`) into name and description at the first
+/// whitespace, recognizing common ID conventions:
+///
+/// - UniProt `sp|P12345|GFP_AEQVI` / `tr|...`: accession `P12345`, name
+///   `GFP_AEQVI`
+/// - NCBI `gi|6|ref|NM_001.2|` (also `gb`, `emb`, `dbj`): accession and name
+///   `NM_001.2`
+/// - A bare accession such as `NM_001.2` or `U49845`: used as the accession,
+///   the name is unchanged
+fn parse_header(header: &str) -> Header {
+    let mut parts = header.splitn(2, |c: char| c.is_whitespace());
+    let id = parts.next().unwrap_or_default();
+    let description = parts
+        .next()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let fields: Vec<&str> = id.split('|').collect();
+    let (name, accession) = match fields.as_slice() {
+        ["sp" | "tr", accession, entry, ..] if !accession.is_empty() => {
+            let name = if entry.is_empty() { accession } else { entry };
+            (name.to_string(), Some(accession.to_string()))
+        }
+        [_, ..] if fields.len() > 1 => {
+            let accession = fields
+                .windows(2)
+                .find(|w| matches!(w[0], "ref" | "gb" | "emb" | "dbj") && !w[1].is_empty())
+                .map(|w| w[1].to_string());
+            match accession {
+                Some(acc) => (acc.clone(), Some(acc)),
+                None => (id.to_string(), None),
+            }
+        }
+        _ => (id.to_string(), is_accession(id).then(|| id.to_string())),
+    };
+
+    Header {
+        name,
+        description,
+        accession,
+    }
+}
+
+/// Whether `id` looks like an INSDC/RefSeq accession: capital letters, an
+/// optional `_`, digits and an optional `.version`.
+fn is_accession(id: &str) -> bool {
+    let (base, version) = match id.split_once('.') {
+        Some((base, version)) => (base, Some(version)),
+        None => (id, None),
+    };
+    let letters = base.chars().take_while(|c| c.is_ascii_uppercase()).count();
+    let rest = base[letters..].strip_prefix('_').unwrap_or(&base[letters..]);
+    (1..=6).contains(&letters)
+        && rest.len() >= 5
+        && rest.chars().all(|c| c.is_ascii_digit())
+        && version.is_none_or(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn build_sequence(header: &str, bases: String) -> Sequence {
+    let parsed = parse_header(header);
+    let mut seq = Sequence::new(parsed.name, "", Topology::Linear);
+    seq.set_sequence(bases);
+    seq.description = parsed.description.unwrap_or_default();
+    seq.metadata.accession = parsed.accession;
+    seq.metadata.fasta_header = Some(header.to_string());
+    seq
+}
 
 /// Parse a FASTA format string into one or more Sequences
+///
+/// The raw header is kept in `metadata.fasta_header`; see `parse_header` for
+/// the ID conventions mapped to the name and `metadata.accession`.
 pub fn parse(input: &str) -> Result<Vec<Sequence>, ParseError> {
     let mut sequences = Vec::new();
-    let mut current_name: Option<String> = None;
-    let mut current_desc: Option<String> = None;
+    let mut current_header: Option<String> = None;
     let mut current_seq = String::new();
 
-    for line in input.lines() {
+    for line in normalize_line_endings(input).lines() {
         let trimmed = line.trim();
 
         if trimmed.is_empty() {
             continue;
         }
 
-        if trimmed.starts_with('>') {
+        if let Some(header) = trimmed.strip_prefix('>') {
             // Save previous sequence if exists
-            if let Some(name) = current_name.take() {
+            if let Some(header) = current_header.take() {
                 if !current_seq.is_empty() {
-                    let mut seq = Sequence::new(
-                        name,
-                        std::mem::take(&mut current_seq),
-                        Topology::Linear,
-                    );
-                    if let Some(desc) = current_desc.take() {
-                        seq.description = desc;
-                    }
-                    sequences.push(seq);
+                    sequences.push(build_sequence(&header, std::mem::take(&mut current_seq)));
                 }
             }
 
-            // Parse header
-            let header = &trimmed[1..];
-            let parts: Vec<&str> = header.splitn(2, |c: char| c.is_whitespace()).collect();
-            current_name = Some(parts[0].to_string());
-            current_desc = parts.get(1).map(|s| s.to_string());
+            current_header = Some(header.trim().to_string());
             current_seq = String::new();
         } else if trimmed.starts_with(';') {
             // Comment line, skip
             continue;
         } else {
-            // Sequence line
-            current_seq.push_str(
-                &trimmed
-                    .chars()
-                    .filter(|c| c.is_ascii_alphabetic())
-                    .collect::<String>()
-                    .to_uppercase(),
-            );
+            // Sequence line, case kept so soft-masked (lowercase) regions
+            // survive a round trip
+            current_seq.extend(trimmed.chars().filter(|c| c.is_ascii_alphabetic()));
         }
     }
 
     // Don't forget the last sequence
-    if let Some(name) = current_name {
+    if let Some(header) = current_header {
         if !current_seq.is_empty() {
-            let mut seq = Sequence::new(name, current_seq, Topology::Linear);
-            if let Some(desc) = current_desc {
-                seq.description = desc;
-            }
-            sequences.push(seq);
+            sequences.push(build_sequence(&header, current_seq));
         }
     }
 
@@ -78,17 +135,27 @@ pub fn serialize(sequences: &[Sequence]) -> String {
     let mut out = String::new();
 
     for seq in sequences {
-        // Header
+        // Header: the original one if it still matches the name and
+        // description, so structured IDs survive a round trip
         out.push('>');
-        out.push_str(&seq.name);
-        if !seq.description.is_empty() {
-            out.push(' ');
-            out.push_str(&seq.description);
+        let original = seq.metadata.fasta_header.as_deref().filter(|header| {
+            let parsed = parse_header(header);
+            parsed.name == seq.name && parsed.description.unwrap_or_default() == seq.description
+        });
+        match original {
+            Some(header) => out.push_str(header),
+            None => {
+                out.push_str(&seq.name);
+                if !seq.description.is_empty() {
+                    out.push(' ');
+                    out.push_str(&seq.description);
+                }
+            }
         }
         out.push('\n');
 
         // Sequence in 80-character lines
-        for chunk in seq.sequence.as_bytes().chunks(80) {
+        for chunk in seq.sequence().as_bytes().chunks(80) {
             out.push_str(&String::from_utf8_lossy(chunk));
             out.push('\n');
         }
@@ -108,7 +175,7 @@ mod tests {
         assert_eq!(seqs.len(), 1);
         assert_eq!(seqs[0].name, "seq1");
         assert_eq!(seqs[0].description, "A test sequence");
-        assert_eq!(seqs[0].sequence, "ATCGATCGGGCCTTAA");
+        assert_eq!(seqs[0].sequence(), "ATCGATCGGGCCTTAA");
     }
 
     #[test]
@@ -116,9 +183,9 @@ mod tests {
         let input = ">seq1\nATCG\n>seq2\nGGCC\n>seq3\nTTAA\n";
         let seqs = parse(input).unwrap();
         assert_eq!(seqs.len(), 3);
-        assert_eq!(seqs[0].sequence, "ATCG");
-        assert_eq!(seqs[1].sequence, "GGCC");
-        assert_eq!(seqs[2].sequence, "TTAA");
+        assert_eq!(seqs[0].sequence(), "ATCG");
+        assert_eq!(seqs[1].sequence(), "GGCC");
+        assert_eq!(seqs[2].sequence(), "TTAA");
     }
 
     #[test]
@@ -127,7 +194,47 @@ mod tests {
         let seqs = parse(input).unwrap();
         let serialized = serialize(&seqs);
         let reparsed = parse(&serialized).unwrap();
-        assert_eq!(seqs[0].sequence, reparsed[0].sequence);
+        assert_eq!(seqs[0].sequence(), reparsed[0].sequence());
+    }
+
+    #[test]
+    fn test_structured_headers() {
+        let seqs = parse(">sp|P12345|GFP_AEQVI Green fluorescent protein\nMSKGEELFTG\n").unwrap();
+        assert_eq!(seqs[0].name, "GFP_AEQVI");
+        assert_eq!(seqs[0].description, "Green fluorescent protein");
+        assert_eq!(seqs[0].metadata.accession.as_deref(), Some("P12345"));
+        assert_eq!(
+            seqs[0].metadata.fasta_header.as_deref(),
+            Some("sp|P12345|GFP_AEQVI Green fluorescent protein")
+        );
+        // The structured header is written back unchanged
+        assert!(serialize(&seqs).starts_with(">sp|P12345|GFP_AEQVI Green"));
+
+        let seqs = parse(">gi|12345|ref|NM_000518.5| HBB mRNA\nACGT\n").unwrap();
+        assert_eq!(seqs[0].name, "NM_000518.5");
+        assert_eq!(seqs[0].metadata.accession.as_deref(), Some("NM_000518.5"));
+
+        let seqs = parse(">NM_000518.5 some description\nACGT\n").unwrap();
+        assert_eq!(seqs[0].name, "NM_000518.5");
+        assert_eq!(seqs[0].metadata.accession.as_deref(), Some("NM_000518.5"));
+
+        let seqs = parse(">seq1 plain\nACGT\n").unwrap();
+        assert_eq!(seqs[0].metadata.accession, None);
+    }
+
+    #[test]
+    fn test_renamed_sequence_writes_new_header() {
+        let mut seqs = parse(">sp|P12345|GFP_AEQVI Green\nMSKG\n").unwrap();
+        seqs[0].name = "my_gfp".to_string();
+        assert_eq!(serialize(&seqs), ">my_gfp Green\nMSKG\n");
+    }
+
+    #[test]
+    fn test_preserves_soft_masking() {
+        let input = ">masked\nACGTacgtNN\nggccAATT\n";
+        let seqs = parse(input).unwrap();
+        assert_eq!(seqs[0].sequence(), "ACGTacgtNNggccAATT");
+        assert_eq!(serialize(&seqs), ">masked\nACGTacgtNNggccAATT\n");
     }
 
     #[test]
@@ -135,4 +242,13 @@ mod tests {
         assert!(parse("").is_err());
         assert!(parse("> \n").is_err());
     }
+
+    #[test]
+    fn test_parse_cr_line_endings() {
+        let seqs = parse(">seq1 desc\rATCG\rGGCC\r>seq2\r\nTTAA\r\n").unwrap();
+        assert_eq!(seqs.len(), 2);
+        assert_eq!(seqs[0].description, "desc");
+        assert_eq!(seqs[0].sequence(), "ATCGGGCC");
+        assert_eq!(seqs[1].sequence(), "TTAA");
+    }
 }
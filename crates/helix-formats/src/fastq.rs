@@ -0,0 +1,136 @@
+use helix_core::sequence::{Sequence, Topology};
+
+use crate::{normalize_line_endings, ParseError};
+
+/// One FASTQ read: the header (without `@`), bases and per-base quality string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastqRecord {
+    pub name: String,
+    pub sequence: String,
+    pub quality: String,
+}
+
+/// Parse FASTQ content into records.
+///
+/// Each record is four lines: `@header`, bases, a `+` separator (optionally
+/// repeating the header) and a quality string of the same length as the
+/// bases. Blank lines between records are ignored.
+pub fn parse(input: &str) -> Result<Vec<FastqRecord>, ParseError> {
+    let input = normalize_line_endings(input);
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()));
+    let mut records = Vec::new();
+
+    while let Some((line_no, header)) = lines.by_ref().find(|(_, line)| !line.is_empty()) {
+        let name = header.strip_prefix('@').ok_or_else(|| {
+            ParseError::InvalidFormat(format!(
+                "Line {}: FASTQ record header must start with '@'",
+                line_no
+            ))
+        })?;
+        let (_, sequence) = lines.next().ok_or(ParseError::UnexpectedEnd)?;
+        let (sep_line, separator) = lines.next().ok_or(ParseError::UnexpectedEnd)?;
+        if !separator.starts_with('+') {
+            return Err(ParseError::InvalidFormat(format!(
+                "Line {}: expected '+' separator in record '{}'",
+                sep_line, name
+            )));
+        }
+        let (qual_line, quality) = lines.next().ok_or(ParseError::UnexpectedEnd)?;
+        if quality.len() != sequence.len() {
+            return Err(ParseError::InvalidFormat(format!(
+                "Line {}: record '{}' has {} bases but {} quality scores",
+                qual_line,
+                name,
+                sequence.len(),
+                quality.len()
+            )));
+        }
+
+        records.push(FastqRecord {
+            name: name.trim().to_string(),
+            sequence: sequence.to_string(),
+            quality: quality.to_string(),
+        });
+    }
+
+    if records.is_empty() {
+        return Err(ParseError::InvalidFormat(
+            "No FASTQ records found".to_string(),
+        ));
+    }
+    Ok(records)
+}
+
+/// Convert records to linear sequences, dropping the quality scores. The
+/// header is split into name and description at the first whitespace, as for
+/// FASTA.
+pub fn to_sequences(records: Vec<FastqRecord>) -> Vec<Sequence> {
+    records
+        .into_iter()
+        .map(|record| {
+            let (name, description) = record
+                .name
+                .split_once(char::is_whitespace)
+                .map(|(name, desc)| (name.to_string(), desc.trim().to_string()))
+                .unwrap_or((record.name, String::new()));
+            let mut seq = Sequence::new(name, record.sequence, Topology::Linear);
+            seq.description = description;
+            seq
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_READS: &str = "@read1 sample=A
+ACGTACGT
++
+IIIIHHHH
+
+@read2
+GGCCTTAA
++read2
+!!##$$%%
+";
+
+    #[test]
+    fn test_parse_two_records() {
+        let records = parse(TWO_READS).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "read1 sample=A");
+        assert_eq!(records[0].sequence, "ACGTACGT");
+        assert_eq!(records[0].quality, "IIIIHHHH");
+        assert_eq!(records[1].name, "read2");
+        assert_eq!(records[1].quality, "!!##$$%%");
+
+        let seqs = to_sequences(records);
+        assert_eq!(seqs[0].name, "read1");
+        assert_eq!(seqs[0].description, "sample=A");
+        assert_eq!(seqs[1].sequence(), "GGCCTTAA");
+    }
+
+    #[test]
+    fn test_mismatched_quality_length() {
+        let bad = "@read1\nACGTACGT\n+\nIIII\n";
+        let err = parse(bad).unwrap_err();
+        assert!(matches!(&err, ParseError::InvalidFormat(msg) if msg.contains("8 bases but 4")));
+    }
+
+    #[test]
+    fn test_malformed_records() {
+        assert!(matches!(
+            parse("@read1\nACGT\n-\nIIII\n"),
+            Err(ParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(parse("@read1\nACGT\n+\n"), Err(ParseError::UnexpectedEnd)));
+        assert!(matches!(
+            parse(">read1\nACGT\n+\nIIII\n"),
+            Err(ParseError::InvalidFormat(_))
+        ));
+    }
+}
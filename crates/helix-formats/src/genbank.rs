@@ -1,5 +1,5 @@
 use helix_core::{
-    feature::{Feature, FeatureType, Location, Qualifier, Strand},
+    feature::{Feature, FeatureType, JoinGap, Location, Qualifier, Strand},
     sequence::{Reference, Sequence, SequenceMetadata, Topology},
 };
 // nom imported for future use in more robust parsing
@@ -7,13 +7,14 @@ use helix_core::{
 use nom::IResult;
 use uuid::Uuid;
 
-use crate::ParseError;
+use crate::{normalize_line_endings, parse_lineage, ParseError};
 
 /// Parse a GenBank format string into a Sequence
 pub fn parse(input: &str) -> Result<Sequence, ParseError> {
     let mut seq = Sequence::new("", "", Topology::Linear);
     seq.metadata = SequenceMetadata::default();
 
+    let input = normalize_line_endings(input);
     let lines: Vec<&str> = input.lines().collect();
     let mut i = 0;
 
@@ -45,14 +46,20 @@ pub fn parse(input: &str) -> Result<Sequence, ParseError> {
                 seq.metadata.organism = Some(lines[i].trim_start()[8..].trim().to_string());
             }
             i += 1;
-            // Skip taxonomy lines
+            // Taxonomy lineage continues the ORGANISM entry
+            let mut lineage = String::new();
             while i < lines.len()
                 && !lines[i].starts_with(char::is_alphabetic)
                 && !lines[i].starts_with("FEATURES")
                 && !lines[i].starts_with("ORIGIN")
             {
+                if lines[i].starts_with("            ") {
+                    lineage.push(' ');
+                    lineage.push_str(lines[i].trim());
+                }
                 i += 1;
             }
+            seq.metadata.taxonomy = parse_lineage(&lineage);
             continue;
         } else if line.starts_with("COMMENT") {
             let mut comment = line[12..].trim().to_string();
@@ -77,11 +84,11 @@ pub fn parse(input: &str) -> Result<Sequence, ParseError> {
             continue;
         } else if line.starts_with("FEATURES") {
             i += 1;
-            parse_features(&lines, &mut i, &mut seq.features);
+            parse_features(&lines, &mut i, seq.features_mut());
             continue;
         } else if line.starts_with("ORIGIN") {
             i += 1;
-            seq.sequence = parse_origin(&lines, &mut i);
+            seq.set_sequence(parse_origin(&lines, &mut i));
             continue;
         }
 
@@ -137,7 +144,7 @@ fn parse_locus_line(line: &str, seq: &mut Sequence) {
 
 fn parse_reference(lines: &[&str], i: &mut usize) -> Reference {
     let line = lines[*i];
-    let num_str = line[9..].trim().split_whitespace().next().unwrap_or("0");
+    let num_str = line[9..].split_whitespace().next().unwrap_or("0");
     let number = num_str.parse().unwrap_or(0);
 
     let mut reference = Reference {
@@ -195,7 +202,7 @@ fn parse_reference(lines: &[&str], i: &mut usize) -> Reference {
     reference
 }
 
-fn parse_features(lines: &[&str], i: &mut usize, features: &mut Vec<Feature>) {
+pub(crate) fn parse_features(lines: &[&str], i: &mut usize, features: &mut Vec<Feature>) {
     while *i < lines.len() {
         let line = lines[*i];
 
@@ -253,8 +260,11 @@ fn parse_features(lines: &[&str], i: &mut usize, features: &mut Vec<Feature>) {
                         *i += 1;
                     }
 
-                    // Strip surrounding quotes
-                    let qval = qval.trim_matches('"').to_string();
+                    // Strip surrounding quotes; translations wrap without spaces
+                    let mut qval = qval.trim_matches('"').to_string();
+                    if qkey == "translation" {
+                        qval.retain(|c| c != ' ');
+                    }
                     qualifiers.push(Qualifier {
                         key: qkey,
                         value: qval,
@@ -273,31 +283,31 @@ fn parse_features(lines: &[&str], i: &mut usize, features: &mut Vec<Feature>) {
             let (location, strand) = parse_location(&location_str);
             let feature_type = FeatureType::from_genbank_key(key);
 
-            // Get name from qualifiers (prefer label, then gene, then product)
-            let name = qualifiers
-                .iter()
-                .find(|q| q.key == "label")
-                .or_else(|| qualifiers.iter().find(|q| q.key == "gene"))
-                .or_else(|| qualifiers.iter().find(|q| q.key == "product"))
-                .or_else(|| qualifiers.iter().find(|q| q.key == "note"))
-                .map(|q| q.value.clone())
-                .unwrap_or_else(|| key.to_string());
-
-            // Get color from qualifiers
-            let color = qualifiers
-                .iter()
-                .find(|q| q.key == "ApEinfo_fwdcolor" || q.key == "color")
+            // Get color from qualifiers. ApE draws reverse features in their
+            // revcolor, so that takes precedence on the reverse strand.
+            let qualifier = |key: &str| qualifiers.iter().find(|q| q.key == key);
+            let revcolor = match strand {
+                Strand::Reverse => qualifier("ApEinfo_revcolor"),
+                _ => None,
+            };
+            let color = revcolor
+                .or_else(|| qualifier("ApEinfo_fwdcolor"))
+                .or_else(|| qualifier("color"))
                 .map(|q| q.value.clone());
 
-            features.push(Feature {
+            let mut feature = Feature {
                 id: Uuid::new_v4(),
-                name,
+                name: String::new(),
                 feature_type,
                 location,
                 strand,
                 color,
                 qualifiers,
-            });
+            };
+            // Name from qualifiers (see Feature::qualifier_name), falling back
+            // to the raw key so unrecognized keys are not lost
+            feature.name = feature.qualifier_name().unwrap_or(key).to_string();
+            features.push(feature);
         } else {
             *i += 1;
         }
@@ -317,14 +327,20 @@ fn parse_location(loc_str: &str) -> (Location, Strand) {
     // join(...)
     if trimmed.starts_with("join(") && trimmed.ends_with(')') {
         let inner = &trimmed[5..trimmed.len() - 1];
-        let ranges: Vec<(usize, usize)> = inner
-            .split(',')
-            .filter_map(|part| parse_simple_range(part.trim()))
-            .collect();
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        let mut gaps = Vec::new();
+        for part in inner.split(',') {
+            let part = part.trim();
+            if let Some(gap) = parse_gap(part, ranges.len()) {
+                gaps.push(gap);
+            } else if let Some(range) = parse_simple_range(part) {
+                ranges.push(range);
+            }
+        }
         if ranges.is_empty() {
             return (Location::simple(0, 0), Strand::Forward);
         }
-        return (Location::Join { ranges }, Strand::Forward);
+        return (Location::Join { ranges, gaps }, Strand::Forward);
     }
 
     // order(...)
@@ -334,7 +350,7 @@ fn parse_location(loc_str: &str) -> (Location, Strand) {
             .split(',')
             .filter_map(|part| parse_simple_range(part.trim()))
             .collect();
-        return (Location::Join { ranges }, Strand::Forward);
+        return (Location::join(ranges), Strand::Forward);
     }
 
     // Simple range: start..end
@@ -351,6 +367,28 @@ fn parse_location(loc_str: &str) -> (Location, Strand) {
     (Location::simple(0, 0), Strand::Forward)
 }
 
+/// Parse a `gap()`, `gap(50)` or `gap(unk100)` join element.
+/// `after` is the number of ranges already seen in the join.
+fn parse_gap(s: &str, after: usize) -> Option<JoinGap> {
+    let inner = s.strip_prefix("gap(")?.strip_suffix(')')?.trim();
+    if inner.is_empty() {
+        return Some(JoinGap {
+            after,
+            length: None,
+            unknown: true,
+        });
+    }
+    let (digits, unknown) = match inner.strip_prefix("unk") {
+        Some(rest) => (rest, true),
+        None => (inner, false),
+    };
+    Some(JoinGap {
+        after,
+        length: digits.parse().ok(),
+        unknown,
+    })
+}
+
 fn parse_simple_range(s: &str) -> Option<(usize, usize)> {
     // Handle formats like: 100..200, <100..>200, 100..200
     let cleaned = s.replace(['<', '>'], "");
@@ -387,8 +425,34 @@ fn parse_origin(lines: &[&str], i: &mut usize) -> String {
     seq
 }
 
+/// Layout options for [`serialize_with_options`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenBankWriteOptions {
+    /// Bases per space-separated group in the ORIGIN block
+    pub origin_group: usize,
+    /// Bases per line in the ORIGIN block
+    pub origin_per_line: usize,
+    /// Maximum line width for feature qualifiers (including the 21-column indent)
+    pub qualifier_width: usize,
+}
+
+impl Default for GenBankWriteOptions {
+    fn default() -> Self {
+        Self {
+            origin_group: 10,
+            origin_per_line: 60,
+            qualifier_width: 79,
+        }
+    }
+}
+
 /// Serialize a Sequence back to GenBank format
 pub fn serialize(seq: &Sequence) -> String {
+    serialize_with_options(seq, &GenBankWriteOptions::default())
+}
+
+/// Serialize a Sequence to GenBank format with a custom ORIGIN and qualifier layout
+pub fn serialize_with_options(seq: &Sequence, options: &GenBankWriteOptions) -> String {
     let mut out = String::new();
 
     // LOCUS line
@@ -435,6 +499,12 @@ pub fn serialize(seq: &Sequence) -> String {
         out.push_str(&format!("SOURCE      {}\n", src));
         if let Some(org) = &seq.metadata.organism {
             out.push_str(&format!("  ORGANISM  {}\n", org));
+            if !seq.metadata.taxonomy.is_empty() {
+                let lineage = format!("{}.", seq.metadata.taxonomy.join("; "));
+                for line in wrap_qualifier(&lineage, 80 - 12, false) {
+                    out.push_str(&format!("            {}\n", line));
+                }
+            }
         }
     }
 
@@ -461,37 +531,29 @@ pub fn serialize(seq: &Sequence) -> String {
     }
 
     // FEATURES
-    if !seq.features.is_empty() {
+    if !seq.features().is_empty() {
         out.push_str("FEATURES             Location/Qualifiers\n");
 
-        for feat in &seq.features {
+        for feat in seq.features() {
             let key = feat.feature_type.to_genbank_key();
             let loc_str = serialize_location(&feat.location, &feat.strand);
             out.push_str(&format!("     {:<16}{}\n", key, loc_str));
 
-            for q in &feat.qualifiers {
-                if q.value.is_empty() {
-                    out.push_str(&format!("                     /{}\n", q.key));
-                } else if q.key == "codon_start"
-                    || q.key == "transl_table"
-                    || q.value.parse::<f64>().is_ok()
-                {
-                    out.push_str(&format!("                     /{}={}\n", q.key, q.value));
-                } else {
-                    out.push_str(&format!("                     /{}=\"{}\"\n", q.key, q.value));
-                }
+            for line in qualifier_lines(feat, options.qualifier_width.saturating_sub(21)) {
+                out.push_str(&format!("                     {}\n", line));
             }
         }
     }
 
     // ORIGIN
     out.push_str("ORIGIN\n");
-    let bases: Vec<char> = seq.sequence.to_lowercase().chars().collect();
-    for (chunk_idx, chunk) in bases.chunks(60).enumerate() {
-        let pos = chunk_idx * 60 + 1;
+    let per_line = options.origin_per_line.max(1);
+    let bases: Vec<char> = seq.sequence().to_lowercase().chars().collect();
+    for (chunk_idx, chunk) in bases.chunks(per_line).enumerate() {
+        let pos = chunk_idx * per_line + 1;
         out.push_str(&format!("{:>9}", pos));
 
-        for (_sub_idx, sub_chunk) in chunk.chunks(10).enumerate() {
+        for sub_chunk in chunk.chunks(options.origin_group.max(1)) {
             out.push(' ');
             let s: String = sub_chunk.iter().collect();
             out.push_str(&s);
@@ -503,16 +565,92 @@ pub fn serialize(seq: &Sequence) -> String {
     out
 }
 
-fn serialize_location(loc: &Location, strand: &Strand) -> String {
+/// Qualifier lines for a feature, without the 21-column indent, wrapped to
+/// `width`. Shared with the EMBL writer, which uses the same qualifier syntax.
+pub(crate) fn qualifier_lines(feat: &Feature, width: usize) -> Vec<String> {
+    // Carry the display name as /label so it survives a round trip,
+    // unless it is only the feature key
+    let key = feat.feature_type.to_genbank_key();
+    let name = feat.display_name();
+    let label = (name != key && feat.get_qualifier("label") != Some(name.as_str()))
+        .then_some(name.as_str());
+    let mut qualifiers: Vec<(&str, &str)> = Vec::new();
+    if label.is_some() && feat.get_qualifier("label").is_none() {
+        qualifiers.push(("label", &name));
+    }
+    for q in &feat.qualifiers {
+        match label {
+            Some(label) if q.key == "label" => qualifiers.push(("label", label)),
+            _ => qualifiers.push((&q.key, &q.value)),
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (qkey, value) in qualifiers {
+        let text = if value.is_empty() {
+            format!("/{}", qkey)
+        } else if qkey == "codon_start"
+            || qkey == "transl_table"
+            || value.parse::<f64>().is_ok()
+        {
+            format!("/{}={}", qkey, value)
+        } else {
+            format!("/{}=\"{}\"", qkey, value)
+        };
+        lines.extend(wrap_qualifier(&text, width, qkey == "translation"));
+    }
+    lines
+}
+
+/// Split a qualifier into lines of at most `width` characters, breaking at
+/// spaces. Words longer than `width` are broken mid-word only when
+/// `break_words` is set, since the parser rejoins continuation lines with a
+/// space for every qualifier except `/translation`; otherwise they are kept
+/// whole on an over-long line.
+pub(crate) fn wrap_qualifier(text: &str, width: usize, break_words: bool) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split(' ') {
+        let needed = if current.is_empty() { word.len() } else { current.len() + 1 + word.len() };
+        if needed <= width {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        let mut rest = word;
+        while break_words && rest.len() > width {
+            let split = (1..=width).rev().find(|&k| rest.is_char_boundary(k)).unwrap_or(rest.len());
+            lines.push(rest[..split].to_string());
+            rest = &rest[split..];
+        }
+        current.push_str(rest);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+pub(crate) fn serialize_location(loc: &Location, strand: &Strand) -> String {
     let loc_str = match loc {
         Location::Simple { start, end } => {
             format!("{}..{}", start + 1, end) // back to 1-based
         }
-        Location::Join { ranges } => {
-            let parts: Vec<String> = ranges
-                .iter()
-                .map(|(s, e)| format!("{}..{}", s + 1, e))
-                .collect();
+        Location::Join { ranges, gaps } => {
+            let mut parts: Vec<String> = Vec::with_capacity(ranges.len() + gaps.len());
+            for (idx, (s, e)) in ranges.iter().enumerate() {
+                parts.extend(gaps.iter().filter(|g| g.after == idx).map(serialize_gap));
+                parts.push(format!("{}..{}", s + 1, e));
+            }
+            parts.extend(gaps.iter().filter(|g| g.after >= ranges.len()).map(serialize_gap));
             format!("join({})", parts.join(","))
         }
         Location::Complement { inner } => {
@@ -526,6 +664,14 @@ fn serialize_location(loc: &Location, strand: &Strand) -> String {
     }
 }
 
+fn serialize_gap(gap: &JoinGap) -> String {
+    match (gap.length, gap.unknown) {
+        (None, _) => "gap()".to_string(),
+        (Some(len), true) => format!("gap(unk{})", len),
+        (Some(len), false) => format!("gap({})", len),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -555,21 +701,50 @@ ORIGIN
         assert_eq!(seq.name, "pTest");
         assert_eq!(seq.topology, Topology::Circular);
         assert_eq!(seq.len(), 100);
-        assert_eq!(seq.features.len(), 2);
+        assert_eq!(seq.features().len(), 2);
+    }
+
+    #[test]
+    fn test_taxonomy_lineage_roundtrip() {
+        let record = MINI_GENBANK.replace(
+            "  ORGANISM  synthetic construct\n",
+            "  ORGANISM  Escherichia coli str. K-12 substr. MG1655\n            \
+             Bacteria; Pseudomonadota; Gammaproteobacteria; Enterobacterales;\n            \
+             Enterobacteriaceae; Escherichia.\n",
+        );
+        let seq = parse(&record).unwrap();
+        assert_eq!(
+            seq.metadata.taxonomy,
+            vec![
+                "Bacteria",
+                "Pseudomonadota",
+                "Gammaproteobacteria",
+                "Enterobacterales",
+                "Enterobacteriaceae",
+                "Escherichia",
+            ]
+        );
+        assert_eq!(seq.features().len(), 2);
+
+        let serialized = serialize(&seq);
+        assert!(serialized.contains("\n            Bacteria; Pseudomonadota;"));
+        assert_eq!(parse(&serialized).unwrap().metadata.taxonomy, seq.metadata.taxonomy);
+
+        assert!(parse(MINI_GENBANK).unwrap().metadata.taxonomy.is_empty());
     }
 
     #[test]
     fn test_parse_features() {
         let seq = parse(MINI_GENBANK).unwrap();
 
-        let promoter = &seq.features[0];
+        let promoter = &seq.features()[0];
         assert_eq!(promoter.name, "test promoter");
         assert_eq!(promoter.feature_type, FeatureType::Promoter);
         assert_eq!(promoter.start(), 0);
         assert_eq!(promoter.end(), 20);
         assert_eq!(promoter.strand, Strand::Forward);
 
-        let cds = &seq.features[1];
+        let cds = &seq.features()[1];
         assert_eq!(cds.name, "GFP");
         assert_eq!(cds.feature_type, FeatureType::Cds);
         assert_eq!(cds.start(), 29);
@@ -580,7 +755,7 @@ ORIGIN
     #[test]
     fn test_parse_origin() {
         let seq = parse(MINI_GENBANK).unwrap();
-        assert!(seq.sequence.starts_with("ATCGATCG"));
+        assert!(seq.sequence().starts_with("ATCGATCG"));
         assert_eq!(seq.len(), 100);
     }
 
@@ -592,8 +767,8 @@ ORIGIN
 
         assert_eq!(reparsed.name, seq.name);
         assert_eq!(reparsed.topology, seq.topology);
-        assert_eq!(reparsed.sequence, seq.sequence);
-        assert_eq!(reparsed.features.len(), seq.features.len());
+        assert_eq!(reparsed.sequence(), seq.sequence());
+        assert_eq!(reparsed.features().len(), seq.features().len());
     }
 
     #[test]
@@ -616,7 +791,7 @@ ORIGIN
     fn test_parse_location_join() {
         let (loc, strand) = parse_location("join(100..200,300..400)");
         assert_eq!(strand, Strand::Forward);
-        if let Location::Join { ranges } = loc {
+        if let Location::Join { ranges, .. } = loc {
             assert_eq!(ranges.len(), 2);
             assert_eq!(ranges[0], (99, 200));
             assert_eq!(ranges[1], (299, 400));
@@ -624,4 +799,171 @@ ORIGIN
             panic!("Expected Join location");
         }
     }
+
+    #[test]
+    fn test_parse_location_join_with_gap() {
+        let (loc, _) = parse_location("join(1..100,gap(50),151..200)");
+        match &loc {
+            Location::Join { ranges, gaps } => {
+                assert_eq!(ranges, &vec![(0, 100), (150, 200)]);
+                assert_eq!(
+                    gaps,
+                    &vec![JoinGap {
+                        after: 1,
+                        length: Some(50),
+                        unknown: false,
+                    }]
+                );
+            }
+            _ => panic!("Expected Join location"),
+        }
+        assert_eq!(
+            serialize_location(&loc, &Strand::Forward),
+            "join(1..100,gap(50),151..200)"
+        );
+
+        let (loc, _) = parse_location("join(1..10,gap(unk100),gap(),21..30)");
+        assert_eq!(
+            serialize_location(&loc, &Strand::Forward),
+            "join(1..10,gap(unk100),gap(),21..30)"
+        );
+    }
+
+    #[test]
+    fn test_parse_cr_line_endings() {
+        let cr_only = MINI_GENBANK.replace('\n', "\r");
+        let seq = parse(&cr_only).unwrap();
+        assert_eq!(seq.name, "pTest");
+        assert_eq!(seq.len(), 100);
+        assert_eq!(seq.features().len(), 2);
+
+        let crlf = MINI_GENBANK.replace('\n', "\r\n");
+        let seq = parse(&crlf).unwrap();
+        assert_eq!(seq.len(), 100);
+        assert_eq!(seq.features()[1].name, "GFP");
+    }
+
+    #[test]
+    fn test_ape_fwd_rev_colors() {
+        let input = MINI_GENBANK.replace(
+            "                     /label=\"GFP\"\n",
+            "                     /label=\"GFP\"\n                     /ApEinfo_fwdcolor=\"#00ff00\"\n                     /ApEinfo_revcolor=\"#ff00ff\"\n",
+        );
+        let seq = parse(&input).unwrap();
+        let gfp = &seq.features()[1];
+        assert_eq!(gfp.strand, Strand::Reverse);
+        assert_eq!(gfp.color.as_deref(), Some("#ff00ff"));
+        assert_eq!(gfp.effective_color(), "#ff00ff");
+
+        let serialized = serialize(&seq);
+        assert!(serialized.contains("/ApEinfo_fwdcolor=\"#00ff00\""));
+        assert!(serialized.contains("/ApEinfo_revcolor=\"#ff00ff\""));
+        let mut reparsed = parse(&serialized).unwrap();
+        assert_eq!(reparsed.features()[1].effective_color(), "#ff00ff");
+
+        // A color set after import wins over the ApE qualifiers.
+        reparsed.features_mut()[1].color = Some("#123456".to_string());
+        assert_eq!(reparsed.features()[1].effective_color(), "#123456");
+    }
+
+    #[test]
+    fn test_label_emitted_for_gene_name() {
+        let input = MINI_GENBANK.replace("/label=\"GFP\"", "/gene=\"gfp\"");
+        let seq = parse(&input).unwrap();
+        let gfp = &seq.features()[1];
+        assert_eq!(gfp.display_name(), "gfp");
+        assert!(gfp.get_qualifier("label").is_none());
+
+        let serialized = serialize(&seq);
+        assert!(serialized.contains("/label=\"gfp\""));
+        let reparsed = parse(&serialized).unwrap();
+        assert_eq!(reparsed.features()[1].display_name(), "gfp");
+        assert_eq!(reparsed.features()[1].get_qualifier("label"), Some("gfp"));
+
+        // A renamed feature overwrites its stale /label
+        let mut renamed = reparsed.clone();
+        renamed.features_mut()[1].name = "sfGFP".to_string();
+        let reparsed = parse(&serialize(&renamed)).unwrap();
+        assert_eq!(reparsed.features()[1].display_name(), "sfGFP");
+        assert_eq!(
+            reparsed.features()[1]
+                .qualifiers
+                .iter()
+                .filter(|q| q.key == "label")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_dedup_duplicate_promoters() {
+        let input = MINI_GENBANK.replace(
+            "     CDS ",
+            "     promoter        1..20\n                     /label=\"test promoter\"\n     CDS ",
+        );
+        let mut seq = parse(&input).unwrap();
+        assert_eq!(seq.features().len(), 3);
+        assert_eq!(seq.dedup_features(), 1);
+        assert_eq!(seq.features().len(), 2);
+        assert_eq!(seq.features()[0].name, "test promoter");
+        assert_eq!(seq.features()[1].name, "GFP");
+    }
+
+    #[test]
+    fn test_serialize_with_options() {
+        let mut seq = parse(MINI_GENBANK).unwrap();
+        seq.features_mut()[1].add_qualifier(
+            "note",
+            "a deliberately long note that has to be wrapped across several qualifier lines",
+        );
+        let options = GenBankWriteOptions {
+            origin_group: 10,
+            origin_per_line: 80,
+            qualifier_width: 60,
+        };
+        let serialized = serialize_with_options(&seq, &options);
+
+        let origin: Vec<&str> = serialized
+            .lines()
+            .skip_while(|l| !l.starts_with("ORIGIN"))
+            .skip(1)
+            .take_while(|l| !l.starts_with("//"))
+            .collect();
+        assert_eq!(origin.len(), 2);
+        assert!(origin[1].trim_start().starts_with("81 "));
+        assert_eq!(origin[0].split_whitespace().skip(1).map(str::len).sum::<usize>(), 80);
+
+        let features = serialized
+            .lines()
+            .skip_while(|l| !l.starts_with("FEATURES"))
+            .take_while(|l| !l.starts_with("ORIGIN"));
+        assert!(features.clone().all(|l| l.len() <= 60));
+
+        let reparsed = parse(&serialized).unwrap();
+        assert_eq!(reparsed.sequence(), seq.sequence());
+        assert_eq!(
+            reparsed.features()[1].get_qualifier("note"),
+            seq.features()[1].get_qualifier("note")
+        );
+    }
+
+    #[test]
+    fn test_wrap_translation_roundtrip() {
+        let mut seq = parse(MINI_GENBANK).unwrap();
+        let protein = "MSKGEELFTGVVPILVELDGDVNGHKFSVSGEGEGDATYGKLTLKFICTTGKLPVPWPTLVTTF";
+        seq.features_mut()[1].add_qualifier("translation", protein);
+
+        let reparsed = parse(&serialize(&seq)).unwrap();
+        assert_eq!(reparsed.features()[1].get_qualifier("translation"), Some(protein));
+    }
+
+    #[test]
+    fn test_wrap_long_word_roundtrip() {
+        let mut seq = parse(MINI_GENBANK).unwrap();
+        let note = format!("target {} end", "ACGT".repeat(30));
+        seq.features_mut()[1].add_qualifier("note", &note);
+
+        let reparsed = parse(&serialize(&seq)).unwrap();
+        assert_eq!(reparsed.features()[1].get_qualifier("note"), Some(note.as_str()));
+    }
 }
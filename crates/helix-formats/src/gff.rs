@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+
+use helix_core::feature::{Feature, FeatureType, Location, Strand};
+
+use crate::{normalize_line_endings, ParseError};
+
+/// Parse GFF3 rows into features
+///
+/// Columns map as follows: type via `FeatureType::from_genbank_key`, 1-based
+/// inclusive start/end to a 0-based half-open location, strand `+`/`-`/`.` to
+/// `Strand`, and attributes to qualifiers. `Name` (or `ID` when there is no
+/// name) becomes the feature name; rows sharing an `ID` are merged into one
+/// join location. A CDS phase other than 0 is kept as `codon_start`, taken
+/// from the row read first in transcription order (the last row of a
+/// reverse-strand feature). Comment
+/// and directive lines are skipped, and parsing stops at `##FASTA`.
+pub fn parse_features(input: &str) -> Result<Vec<Feature>, ParseError> {
+    Ok(parse_features_with_lines(input)?
+        .into_iter()
+        .map(|(_, feature)| feature)
+        .collect())
+}
+
+/// [`parse_features`], pairing each feature with the 1-based line number of
+/// its first row
+pub fn parse_features_with_lines(input: &str) -> Result<Vec<(usize, Feature)>, ParseError> {
+    let mut features: Vec<(usize, Feature)> = Vec::new();
+    // Index into `features` of each ID seen so far
+    let mut by_id: HashMap<String, usize> = HashMap::new();
+    // Phase of the 5'-most row of each feature, by index into `features`
+    let mut phases: HashMap<usize, usize> = HashMap::new();
+
+    for (line_no, line) in normalize_line_endings(input).lines().enumerate() {
+        let line_no = line_no + 1;
+        if line.starts_with("##FASTA") {
+            break;
+        }
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let cols: Vec<&str> = line.split('\t').collect();
+        if cols.len() != 9 {
+            return Err(ParseError::InvalidFormat(format!(
+                "Line {}: expected 9 tab-separated GFF3 columns, found {}",
+                line_no,
+                cols.len()
+            )));
+        }
+
+        let coordinate = |col: &str| {
+            col.trim().parse::<usize>().ok().filter(|&v| v > 0).ok_or_else(|| {
+                ParseError::InvalidLocation(format!(
+                    "Line {}: invalid coordinate '{}'",
+                    line_no, col
+                ))
+            })
+        };
+        let (start, end) = (coordinate(cols[3])?, coordinate(cols[4])?);
+        if start > end {
+            return Err(ParseError::InvalidLocation(format!(
+                "Line {}: start {} is after end {}",
+                line_no, start, end
+            )));
+        }
+        let range = (start - 1, end);
+        let phase = cols[7].trim().parse::<usize>().ok();
+
+        let mut id = None;
+        let mut name = None;
+        let mut qualifiers = Vec::new();
+        for attribute in cols[8].trim().split(';').filter(|a| !a.is_empty()) {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let (key, value) = (unescape(key.trim()), unescape(value));
+            match key.as_str() {
+                "ID" => id = Some(value),
+                "Name" => name = Some(value),
+                _ => qualifiers.push((key, value)),
+            }
+        }
+
+        if let Some(&index) = id.as_ref().and_then(|id| by_id.get(id)) {
+            let (_, feature) = &mut features[index];
+            let mut ranges = feature.location.ranges();
+            ranges.push(range);
+            feature.location = Location::join(ranges);
+            if let (Strand::Reverse, Some(phase)) = (feature.strand, phase) {
+                phases.insert(index, phase);
+            }
+            continue;
+        }
+
+        let strand = match cols[6].trim() {
+            "+" => Strand::Forward,
+            "-" => Strand::Reverse,
+            _ => Strand::None,
+        };
+        let feature_type = FeatureType::from_genbank_key(cols[2].trim());
+        let label = name.or_else(|| id.clone()).unwrap_or_default();
+        let mut feature = Feature::new(label, feature_type, range.0, range.1, strand);
+        for (key, value) in qualifiers {
+            feature.add_qualifier(key, value);
+        }
+        if let Some(phase) = phase {
+            phases.insert(features.len(), phase);
+        }
+
+        if let Some(id) = id {
+            by_id.insert(id, features.len());
+        }
+        features.push((line_no, feature));
+    }
+
+    for (index, phase) in phases {
+        let (_, feature) = &mut features[index];
+        if matches!(phase, 1..=2)
+            && feature.feature_type == FeatureType::Cds
+            && feature.get_qualifier("codon_start").is_none()
+        {
+            feature.add_qualifier("codon_start", (phase + 1).to_string());
+        }
+    }
+
+    Ok(features)
+}
+
+/// Serialize features as GFF3 rows on sequence `seqid`
+///
+/// Coordinates are converted to GFF's 1-based inclusive form. Each feature's
+/// display name is written as both `ID` and `Name` (IDs are suffixed with `.2`, `.3`,
+/// ... when names repeat), and its remaining qualifiers become attributes.
+/// Join locations are written as one row per segment sharing the same ID, each
+/// CDS segment with its own phase.
+pub fn serialize_features(features: &[Feature], seqid: &str) -> String {
+    let mut out = String::from("##gff-version 3\n");
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    for feature in features {
+        let name = feature.display_name();
+        let count = seen.entry(name.clone()).or_insert(0);
+        *count += 1;
+        let id = if *count == 1 {
+            name.clone()
+        } else {
+            format!("{}.{}", name, count)
+        };
+
+        let mut attributes = vec![
+            format!("ID={}", escape(&id)),
+            format!("Name={}", escape(&name)),
+        ];
+        for q in &feature.qualifiers {
+            if matches!(q.key.as_str(), "ID" | "Name") {
+                continue;
+            }
+            attributes.push(format!("{}={}", escape(&q.key), escape(&q.value)));
+        }
+        let attributes = attributes.join(";");
+
+        let strand = match feature.strand {
+            Strand::Forward => '+',
+            Strand::Reverse => '-',
+            Strand::None => '.',
+        };
+        // Phase of the first segment in transcription order
+        let first_phase = (feature.feature_type == FeatureType::Cds).then(|| {
+            let codon_start = feature
+                .get_qualifier("codon_start")
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(1);
+            codon_start.saturating_sub(1).min(2)
+        });
+
+        let ranges = feature.location.ranges();
+        for (k, &(start, end)) in ranges.iter().enumerate() {
+            // Later segments start part way through a codon, depending on the
+            // bases transcribed before them
+            let phase = match first_phase {
+                Some(first_phase) => {
+                    let upstream = match feature.strand {
+                        Strand::Reverse => &ranges[k + 1..],
+                        _ => &ranges[..k],
+                    };
+                    let upstream_len: usize = upstream.iter().map(|(s, e)| e - s).sum();
+                    ((first_phase + 3 - upstream_len % 3) % 3).to_string()
+                }
+                None => ".".to_string(),
+            };
+            out.push_str(&format!(
+                "{}\tHelix\t{}\t{}\t{}\t.\t{}\t{}\t{}\n",
+                escape(seqid),
+                feature.feature_type.to_genbank_key(),
+                start + 1,
+                end,
+                strand,
+                phase,
+                attributes
+            ));
+        }
+    }
+
+    out
+}
+
+/// Percent-encode the characters GFF3 reserves in columns and attributes
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            ';' | '=' | '&' | ',' | '%' | '\t' | '\n' | '\r' => {
+                out.push_str(&format!("%{:02X}", c as u32))
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Decode `%XX` escapes, leaving malformed ones as they are
+fn unescape(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = value.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_features() {
+        let mut cds = Feature::new("GFP", FeatureType::Cds, 29, 90, Strand::Reverse);
+        cds.add_qualifier("note", "green; bright");
+        let promoter = Feature::new("GFP", FeatureType::Promoter, 0, 20, Strand::Forward);
+
+        let gff = serialize_features(&[cds, promoter], "pTest");
+        let lines: Vec<&str> = gff.lines().collect();
+        assert_eq!(lines[0], "##gff-version 3");
+        assert_eq!(
+            lines[1],
+            "pTest\tHelix\tCDS\t30\t90\t.\t-\t0\tID=GFP;Name=GFP;note=green%3B bright"
+        );
+        assert_eq!(
+            lines[2],
+            "pTest\tHelix\tpromoter\t1\t20\t.\t+\t.\tID=GFP.2;Name=GFP"
+        );
+    }
+
+    #[test]
+    fn test_parse_features() {
+        let gff = "##gff-version 3
+# produced by a pipeline
+##sequence-region pTest 1 200
+pTest\tprokka\tgene\t5\t40\t.\t+\t.\tID=gene1;locus_tag=TST_001
+pTest\tprokka\tCDS\t5\t40\t.\t+\t1\tID=cds1;Name=abc%3Bd;product=hypothetical protein
+
+pTest\tprokka\texon\t50\t60\t.\t.\t.\tID=ex;Name=exon
+##FASTA
+>pTest
+ACGT
+";
+        let features = parse_features(gff).unwrap();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[0].name, "gene1");
+        assert_eq!(features[0].feature_type, FeatureType::Gene);
+        assert_eq!((features[0].start(), features[0].end()), (4, 40));
+        assert_eq!(features[0].get_qualifier("locus_tag"), Some("TST_001"));
+        assert_eq!(features[1].name, "abc;d");
+        assert_eq!(features[1].get_qualifier("codon_start"), Some("2"));
+        assert_eq!(features[1].get_qualifier("product"), Some("hypothetical protein"));
+        assert_eq!(features[2].strand, Strand::None);
+    }
+
+    #[test]
+    fn test_reverse_cds_roundtrip() {
+        let mut cds = Feature::new("GFP", FeatureType::Cds, 29, 50, Strand::Reverse);
+        cds.location = Location::join(vec![(29, 50), (60, 90)]);
+        cds.add_qualifier("note", "green; bright=yes");
+        cds.add_qualifier("codon_start", "3");
+
+        let gff = serialize_features(std::slice::from_ref(&cds), "pTest");
+        let features = parse_features(&gff).unwrap();
+        assert_eq!(features.len(), 1);
+        let parsed = &features[0];
+        assert_eq!(parsed.name, "GFP");
+        assert_eq!(parsed.feature_type, FeatureType::Cds);
+        assert_eq!(parsed.strand, Strand::Reverse);
+        assert_eq!(parsed.location, cds.location);
+        assert_eq!(parsed.get_qualifier("note"), Some("green; bright=yes"));
+        assert_eq!(parsed.get_qualifier("codon_start"), Some("3"));
+        assert_eq!(parsed.qualifiers.len(), 2);
+    }
+
+    #[test]
+    fn test_multi_exon_cds_phases() {
+        let mut cds = Feature::new("gene", FeatureType::Cds, 0, 10, Strand::Forward);
+        cds.location = Location::join(vec![(0, 10), (20, 31), (40, 52)]);
+        let phases = |gff: &str| -> Vec<String> {
+            gff.lines().skip(1).map(|l| l.split('\t').nth(7).unwrap().to_string()).collect()
+        };
+
+        // 10 bases leave one spare base, so exon 2 needs 2 to finish the codon
+        let gff = serialize_features(std::slice::from_ref(&cds), "pTest");
+        assert_eq!(phases(&gff), ["0", "2", "0"]);
+        assert!(parse_features(&gff).unwrap()[0].get_qualifier("codon_start").is_none());
+
+        // Reverse strand: the last segment is read first
+        cds.strand = Strand::Reverse;
+        cds.add_qualifier("codon_start", "2");
+        let gff = serialize_features(std::slice::from_ref(&cds), "pTest");
+        assert_eq!(phases(&gff), ["2", "1", "1"]);
+
+        // Third-party rows carry only the phase column
+        let gff = gff.replace(";codon_start=2", "");
+        let parsed = parse_features(&gff).unwrap();
+        assert_eq!(parsed[0].get_qualifier("codon_start"), Some("2"));
+    }
+
+    #[test]
+    fn test_parse_invalid_rows() {
+        assert!(matches!(
+            parse_features("pTest\tx\tCDS\t5\t40\n"),
+            Err(ParseError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            parse_features("pTest\tx\tCDS\t40\t5\t.\t+\t0\tID=a\n"),
+            Err(ParseError::InvalidLocation(_))
+        ));
+    }
+}
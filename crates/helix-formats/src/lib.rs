@@ -1,6 +1,12 @@
+pub mod bed;
 pub mod detect;
+pub mod embl;
 pub mod fasta;
+pub mod fastq;
 pub mod genbank;
+pub mod gff;
+
+use std::borrow::Cow;
 
 use helix_core::Sequence;
 use thiserror::Error;
@@ -22,6 +28,7 @@ pub enum FileFormat {
     GenBank,
     Fasta,
     Embl,
+    Fastq,
     SnapGene,
     Unknown,
 }
@@ -31,8 +38,32 @@ pub fn parse_file(content: &str) -> Result<Vec<Sequence>, ParseError> {
     match detect::detect_format(content) {
         FileFormat::GenBank => genbank::parse(content).map(|s| vec![s]),
         FileFormat::Fasta => fasta::parse(content),
+        FileFormat::Embl => embl::parse(content).map(|s| vec![s]),
+        FileFormat::Fastq => fastq::parse(content).map(fastq::to_sequences),
         _ => Err(ParseError::InvalidFormat(
             "Unsupported or unrecognized file format".to_string(),
         )),
     }
 }
+
+/// Split a `; `-separated taxonomy lineage (GenBank ORGANISM continuation or
+/// EMBL OC lines, already joined) into clades, dropping the final period.
+pub(crate) fn parse_lineage(text: &str) -> Vec<String> {
+    text.trim()
+        .trim_end_matches('.')
+        .split(';')
+        .map(str::trim)
+        .filter(|clade| !clade.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Convert Windows (`\r\n`) and classic Mac (`\r`) line endings to `\n`.
+/// Borrows the input unchanged when it contains no carriage returns.
+pub(crate) fn normalize_line_endings(input: &str) -> Cow<'_, str> {
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n").replace('\r', "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
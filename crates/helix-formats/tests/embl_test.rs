@@ -0,0 +1,67 @@
+use helix_core::sequence::Topology;
+use helix_formats::{embl, genbank};
+
+const PUC19_EMBL: &str = include_str!("fixtures/pUC19.embl");
+const PUC19_GB: &str = include_str!("fixtures/pUC19.gb");
+
+#[test]
+fn test_parse_puc19_basic_fields() {
+    let seq = embl::parse(PUC19_EMBL).unwrap();
+    assert_eq!(seq.name, "pUC19");
+    assert_eq!(seq.topology, Topology::Circular);
+    assert_eq!(seq.len(), 2686);
+}
+
+#[test]
+fn test_parse_puc19_features() {
+    let seq = embl::parse(PUC19_EMBL).unwrap();
+    assert_eq!(seq.features().len(), 7);
+
+    let ampr = seq
+        .features()
+        .iter()
+        .find(|f| f.name == "AmpR")
+        .expect("AmpR feature not found");
+    assert_eq!(ampr.strand, helix_core::feature::Strand::Reverse);
+    assert_eq!(ampr.start(), 1628); // 0-based
+    assert_eq!(ampr.end(), 2489);
+}
+
+#[test]
+fn test_parse_puc19_metadata() {
+    let seq = embl::parse(PUC19_EMBL).unwrap();
+    assert_eq!(seq.metadata.accession.as_deref(), Some("L09137"));
+    assert_eq!(seq.metadata.organism.as_deref(), Some("synthetic construct"));
+    assert_eq!(seq.description, "Cloning vector pUC19");
+}
+
+#[test]
+fn test_matches_genbank_record() {
+    let from_embl = embl::parse(PUC19_EMBL).unwrap();
+    let from_gb = genbank::parse(PUC19_GB).unwrap();
+    assert_eq!(from_embl.sequence(), from_gb.sequence());
+    for (a, b) in from_embl.features().iter().zip(from_gb.features()) {
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.location, b.location);
+        assert_eq!(a.strand, b.strand);
+    }
+}
+
+#[test]
+fn test_puc19_roundtrip() {
+    let seq = embl::parse(PUC19_EMBL).unwrap();
+    let serialized = embl::serialize(&seq);
+    let reparsed = embl::parse(&serialized).unwrap();
+
+    assert_eq!(reparsed.name, seq.name);
+    assert_eq!(reparsed.topology, seq.topology);
+    assert_eq!(reparsed.sequence(), seq.sequence());
+    assert_eq!(reparsed.features().len(), seq.features().len());
+}
+
+#[test]
+fn test_parse_file_detects_embl() {
+    let seqs = helix_formats::parse_file(PUC19_EMBL).unwrap();
+    assert_eq!(seqs.len(), 1);
+    assert_eq!(seqs[0].name, "pUC19");
+}
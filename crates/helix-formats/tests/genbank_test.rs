@@ -16,11 +16,11 @@ fn test_parse_puc19_features() {
     let seq = genbank::parse(PUC19_GB).unwrap();
 
     // Should have 7 features: source, lac promoter, AmpR, pMB1 ori, MCS, lac UV5, lacZ-alpha
-    assert_eq!(seq.features.len(), 7);
+    assert_eq!(seq.features().len(), 7);
 
     // Check AmpR feature
     let ampr = seq
-        .features
+        .features()
         .iter()
         .find(|f| f.name == "AmpR")
         .expect("AmpR feature not found");
@@ -34,7 +34,7 @@ fn test_parse_puc19_metadata() {
     let seq = genbank::parse(PUC19_GB).unwrap();
     assert_eq!(seq.metadata.accession.as_deref(), Some("L09137"));
     assert_eq!(seq.metadata.organism.as_deref(), Some("synthetic construct"));
-    assert!(seq.metadata.references.len() >= 1);
+    assert!(!seq.metadata.references.is_empty());
 }
 
 #[test]
@@ -45,10 +45,10 @@ fn test_puc19_roundtrip() {
 
     assert_eq!(reparsed.name, seq.name);
     assert_eq!(reparsed.topology, seq.topology);
-    assert_eq!(reparsed.sequence, seq.sequence);
-    assert_eq!(reparsed.features.len(), seq.features.len());
+    assert_eq!(reparsed.sequence(), seq.sequence());
+    assert_eq!(reparsed.features().len(), seq.features().len());
 
     // Verify sequence content is preserved exactly
     assert_eq!(reparsed.len(), seq.len());
-    assert_eq!(reparsed.sequence, seq.sequence);
+    assert_eq!(reparsed.sequence(), seq.sequence());
 }
@@ -1,6 +1,25 @@
+use helix_core::operations::{delete_bases, insert_bases, replace_bases};
+use helix_core::{Feature, Sequence, Topology};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use uuid::Uuid;
 
+#[derive(Debug, Error)]
+pub enum DeltaError {
+    #[error("Position {position} (length {length}) is outside the sequence (length {sequence_length})")]
+    OutOfBounds {
+        position: usize,
+        length: usize,
+        sequence_length: usize,
+    },
+    #[error("No feature with id {0}")]
+    UnknownFeature(Uuid),
+    #[error("Invalid feature JSON: {0}")]
+    InvalidFeature(#[from] serde_json::Error),
+    #[error("Unknown metadata key '{0}'")]
+    UnknownMetadataKey(String),
+}
+
 /// A sequence operation in a delta
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "op", rename_all = "snake_case")]
@@ -51,6 +70,267 @@ impl Delta {
             && self.annotation_ops.is_empty()
             && self.metadata_ops.is_empty()
     }
+
+    /// Compute the delta that turns `old` into `new`.
+    ///
+    /// Bases are compared as a single edit spanning the first to last
+    /// differing position; features are matched by id, with changed features
+    /// recorded as the top-level fields that differ.
+    pub fn diff(old: &Sequence, new: &Sequence) -> Self {
+        let mut delta = Self::new();
+        delta.sequence_ops.extend(diff_bases(old.sequence(), new.sequence()));
+
+        for feature in old.features() {
+            if !new.features().iter().any(|f| f.id == feature.id) {
+                delta.annotation_ops.push(AnnotationOp::Remove {
+                    feature_id: feature.id,
+                });
+            }
+        }
+        for feature in new.features() {
+            match old.features().iter().find(|f| f.id == feature.id) {
+                None => delta.annotation_ops.push(AnnotationOp::Add {
+                    feature_json: serde_json::to_string(feature)
+                        .expect("features serialize to JSON"),
+                }),
+                Some(before) => {
+                    if let Some(changes_json) = feature_changes(before, feature) {
+                        delta.annotation_ops.push(AnnotationOp::Modify {
+                            feature_id: feature.id,
+                            changes_json,
+                        });
+                    }
+                }
+            }
+        }
+
+        for key in METADATA_KEYS {
+            let value = get_metadata(new, key);
+            if get_metadata(old, key) != value {
+                delta.metadata_ops.push(MetadataOp {
+                    key: key.to_string(),
+                    value: value.unwrap_or_default(),
+                });
+            }
+        }
+        delta
+    }
+
+    /// The delta that undoes this one, given the sequence it will be applied to.
+    pub fn invert(&self, before: &Sequence) -> Result<Self, DeltaError> {
+        let mut after = before.clone();
+        self.apply(&mut after)?;
+        Ok(Self::diff(&after, before))
+    }
+
+    /// Apply the delta to `seq`: sequence ops, then annotation ops, then
+    /// metadata ops, each group in order.
+    ///
+    /// Sequence ops do not move feature coordinates; annotation ops in the same
+    /// delta are expected to do that. On error `seq` is left unchanged.
+    pub fn apply(&self, seq: &mut Sequence) -> Result<(), DeltaError> {
+        let mut bases = seq.sequence().to_string();
+        for op in &self.sequence_ops {
+            bases = match op {
+                SequenceOp::Insert { position, bases: new } => {
+                    check_bounds(&bases, *position, 0)?;
+                    insert_bases(&bases, *position, new)
+                }
+                SequenceOp::Delete { position, length } => {
+                    check_bounds(&bases, *position, *length)?;
+                    delete_bases(&bases, *position, *length)
+                }
+                SequenceOp::Replace {
+                    position,
+                    length,
+                    bases: new,
+                } => {
+                    check_bounds(&bases, *position, *length)?;
+                    replace_bases(&bases, *position, *length, new)
+                }
+            };
+        }
+
+        let mut features = seq.features().to_vec();
+        for op in &self.annotation_ops {
+            match op {
+                AnnotationOp::Add { feature_json } => {
+                    features.push(serde_json::from_str(feature_json)?);
+                }
+                AnnotationOp::Remove { feature_id } => {
+                    let pos = feature_position(&features, *feature_id)?;
+                    features.remove(pos);
+                }
+                AnnotationOp::Modify {
+                    feature_id,
+                    changes_json,
+                } => {
+                    let pos = feature_position(&features, *feature_id)?;
+                    features[pos] = modify_feature(&features[pos], changes_json)?;
+                }
+            }
+        }
+
+        let mut updated = seq.clone();
+        updated.set_sequence(bases);
+        *updated.features_mut() = features;
+        for op in &self.metadata_ops {
+            set_metadata(&mut updated, &op.key, &op.value)?;
+        }
+        *seq = updated;
+        Ok(())
+    }
+}
+
+/// Trim the common prefix and suffix and describe the remaining region as one
+/// insert, delete or replace.
+fn diff_bases(old: &str, new: &str) -> Option<SequenceOp> {
+    let (old_b, new_b) = (old.as_bytes(), new.as_bytes());
+    let prefix = old_b.iter().zip(new_b).take_while(|(a, b)| a == b).count();
+    let max_suffix = old_b.len().min(new_b.len()) - prefix;
+    let suffix = old_b
+        .iter()
+        .rev()
+        .zip(new_b.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let length = old.len() - prefix - suffix;
+    let bases = new[prefix..new.len() - suffix].to_string();
+    match (length, bases.is_empty()) {
+        (0, true) => None,
+        (0, false) => Some(SequenceOp::Insert {
+            position: prefix,
+            bases,
+        }),
+        (_, true) => Some(SequenceOp::Delete {
+            position: prefix,
+            length,
+        }),
+        _ => Some(SequenceOp::Replace {
+            position: prefix,
+            length,
+            bases,
+        }),
+    }
+}
+
+/// JSON object of the top-level fields of `after` that differ from `before`,
+/// or `None` if the features are identical.
+fn feature_changes(before: &Feature, after: &Feature) -> Option<String> {
+    let before = serde_json::to_value(before).expect("features serialize to JSON");
+    let after = serde_json::to_value(after).expect("features serialize to JSON");
+    let (serde_json::Value::Object(before), serde_json::Value::Object(after)) = (before, after)
+    else {
+        return None;
+    };
+    let changes: serde_json::Map<String, serde_json::Value> = after
+        .into_iter()
+        .filter(|(key, value)| before.get(key) != Some(value))
+        .collect();
+    (!changes.is_empty()).then(|| serde_json::Value::Object(changes).to_string())
+}
+
+fn check_bounds(bases: &str, position: usize, length: usize) -> Result<(), DeltaError> {
+    if position.checked_add(length).is_none_or(|end| end > bases.len()) {
+        return Err(DeltaError::OutOfBounds {
+            position,
+            length,
+            sequence_length: bases.len(),
+        });
+    }
+    Ok(())
+}
+
+fn feature_position(features: &[Feature], id: Uuid) -> Result<usize, DeltaError> {
+    features
+        .iter()
+        .position(|f| f.id == id)
+        .ok_or(DeltaError::UnknownFeature(id))
+}
+
+/// Overlay the top-level fields of the JSON object `changes_json` onto the
+/// feature's JSON form.
+fn modify_feature(feature: &Feature, changes_json: &str) -> Result<Feature, DeltaError> {
+    let mut value = serde_json::to_value(feature)?;
+    let changes: serde_json::Map<String, serde_json::Value> = serde_json::from_str(changes_json)?;
+    if let serde_json::Value::Object(fields) = &mut value {
+        fields.extend(changes);
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Keys accepted by [`MetadataOp`]: the sequence name, description and
+/// topology, and the text fields of its metadata. Empty values clear optional
+/// fields; `taxonomy` is written as `; `-separated clades.
+pub const METADATA_KEYS: &[&str] = &[
+    "name",
+    "description",
+    "topology",
+    "accession",
+    "organism",
+    "molecule_type",
+    "division",
+    "date",
+    "definition",
+    "keywords",
+    "source",
+    "taxonomy",
+];
+
+/// Current value of a [`METADATA_KEYS`] entry (`None` for unknown keys).
+pub fn get_metadata(seq: &Sequence, key: &str) -> Option<String> {
+    let meta = &seq.metadata;
+    let optional = |field: &Option<String>| Some(field.clone().unwrap_or_default());
+    match key {
+        "name" => Some(seq.name.clone()),
+        "description" => Some(seq.description.clone()),
+        "topology" => Some(seq.topology.to_string()),
+        "accession" => optional(&meta.accession),
+        "organism" => optional(&meta.organism),
+        "molecule_type" => optional(&meta.molecule_type),
+        "division" => optional(&meta.division),
+        "date" => optional(&meta.date),
+        "definition" => optional(&meta.definition),
+        "keywords" => optional(&meta.keywords),
+        "source" => optional(&meta.source),
+        "taxonomy" => Some(meta.taxonomy.join("; ")),
+        _ => None,
+    }
+}
+
+fn set_metadata(seq: &mut Sequence, key: &str, value: &str) -> Result<(), DeltaError> {
+    let optional = (!value.is_empty()).then(|| value.to_string());
+    let meta = &mut seq.metadata;
+    match key {
+        "name" => seq.name = value.to_string(),
+        "description" => seq.description = value.to_string(),
+        "topology" => {
+            seq.topology = match value {
+                "circular" => Topology::Circular,
+                _ => Topology::Linear,
+            }
+        }
+        "accession" => meta.accession = optional,
+        "organism" => meta.organism = optional,
+        "molecule_type" => meta.molecule_type = optional,
+        "division" => meta.division = optional,
+        "date" => meta.date = optional,
+        "definition" => meta.definition = optional,
+        "keywords" => meta.keywords = optional,
+        "source" => meta.source = optional,
+        "taxonomy" => {
+            meta.taxonomy = value
+                .split(';')
+                .map(str::trim)
+                .filter(|clade| !clade.is_empty())
+                .map(str::to_string)
+                .collect()
+        }
+        _ => return Err(DeltaError::UnknownMetadataKey(key.to_string())),
+    }
+    Ok(())
 }
 
 impl Default for Delta {
@@ -71,3 +351,147 @@ pub struct Version {
     pub description: String,
     pub delta: Delta,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use helix_core::{FeatureType, Location, Strand};
+
+    fn feature_json(feature: &Feature) -> String {
+        serde_json::to_string(feature).unwrap()
+    }
+
+    #[test]
+    fn test_apply_sequence_ops_in_order() {
+        let mut seq = Sequence::new("p", "AAAACCCCGGGG", Topology::Linear);
+        let mut delta = Delta::new();
+        delta.sequence_ops = vec![
+            SequenceOp::Insert { position: 4, bases: "TT".to_string() },
+            SequenceOp::Delete { position: 0, length: 2 },
+            SequenceOp::Replace { position: 8, length: 4, bases: "A".to_string() },
+        ];
+        delta.apply(&mut seq).unwrap();
+        // AAAATTCCCCGGGG -> AATTCCCCGGGG -> AATTCCCCA
+        assert_eq!(seq.sequence(), "AATTCCCCA");
+    }
+
+    #[test]
+    fn test_apply_annotation_and_metadata_ops() {
+        let mut seq = Sequence::new("p", "A".repeat(100), Topology::Linear);
+        let keep = Feature::new("keep", FeatureType::Cds, 0, 30, Strand::Forward);
+        let drop = Feature::new("drop", FeatureType::Misc, 40, 50, Strand::Forward);
+        let (keep_id, drop_id) = (keep.id, drop.id);
+        seq.add_feature(keep);
+        seq.add_feature(drop);
+
+        let added = Feature::new("new", FeatureType::Promoter, 60, 80, Strand::Reverse);
+        let mut delta = Delta::new();
+        delta.annotation_ops = vec![
+            AnnotationOp::Add { feature_json: feature_json(&added) },
+            AnnotationOp::Remove { feature_id: drop_id },
+            AnnotationOp::Modify {
+                feature_id: keep_id,
+                changes_json: r##"{"name": "lacZ", "color": "#ff0000"}"##.to_string(),
+            },
+        ];
+        delta.metadata_ops = vec![
+            MetadataOp { key: "name".to_string(), value: "pNew".to_string() },
+            MetadataOp { key: "topology".to_string(), value: "circular".to_string() },
+            MetadataOp { key: "organism".to_string(), value: "E. coli".to_string() },
+        ];
+        delta.apply(&mut seq).unwrap();
+
+        let names: Vec<&str> = seq.features().iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["lacZ", "new"]);
+        assert_eq!(seq.features()[0].id, keep_id);
+        assert_eq!(seq.features()[0].color.as_deref(), Some("#ff0000"));
+        assert_eq!(seq.features()[0].end(), 30);
+        assert_eq!(seq.features()[1].strand, Strand::Reverse);
+        assert_eq!(seq.name, "pNew");
+        assert!(seq.is_circular());
+        assert_eq!(seq.metadata.organism.as_deref(), Some("E. coli"));
+    }
+
+    #[test]
+    fn test_diff_then_apply_reproduces_new() {
+        let mut old = Sequence::new("p", "ATGAAACCCGGGTTTTAA", Topology::Linear);
+        let promoter = Feature::new("Plac", FeatureType::Promoter, 0, 6, Strand::Forward);
+        let terminator = Feature::new("term", FeatureType::Terminator, 12, 18, Strand::Forward);
+        old.add_feature(promoter);
+        old.add_feature(terminator);
+
+        let mut new = old.clone();
+        new.set_sequence("ATGAAACCCTATATAGGGTTTTAA".to_string());
+        new.features_mut()[0].name = "Ptac".to_string();
+        new.features_mut()[1].location = Location::simple(18, 24);
+
+        let delta = Delta::diff(&old, &new);
+        assert!(matches!(
+            delta.sequence_ops.as_slice(),
+            [SequenceOp::Insert { position: 9, bases }] if bases == "TATATA"
+        ));
+        assert_eq!(delta.annotation_ops.len(), 2);
+        assert!(delta.metadata_ops.is_empty());
+
+        let mut applied = old.clone();
+        delta.apply(&mut applied).unwrap();
+        assert_eq!(applied.sequence(), new.sequence());
+        assert_eq!(
+            serde_json::to_value(applied.features()).unwrap(),
+            serde_json::to_value(new.features()).unwrap()
+        );
+        assert!(Delta::diff(&applied, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_features_and_metadata() {
+        let mut old = Sequence::new("p", "ACGTACGT", Topology::Linear);
+        old.add_feature(Feature::new("gone", FeatureType::Misc, 0, 4, Strand::Forward));
+        let mut new = Sequence::new("p", "ACCTACGA", Topology::Circular);
+        new.id = old.id;
+        new.add_feature(Feature::new("added", FeatureType::Cds, 2, 8, Strand::Reverse));
+        new.metadata.organism = Some("E. coli".to_string());
+
+        let delta = Delta::diff(&old, &new);
+        assert!(matches!(
+            delta.sequence_ops.as_slice(),
+            [SequenceOp::Replace { position: 2, length: 6, bases }] if bases == "CTACGA"
+        ));
+        let keys: Vec<&str> = delta.metadata_ops.iter().map(|op| op.key.as_str()).collect();
+        assert_eq!(keys, vec!["topology", "organism"]);
+
+        let mut applied = old.clone();
+        delta.apply(&mut applied).unwrap();
+        assert!(Delta::diff(&applied, &new).is_empty());
+    }
+
+    #[test]
+    fn test_apply_errors_leave_sequence_unchanged() {
+        let mut seq = Sequence::new("p", "ACGT", Topology::Linear);
+        seq.add_feature(Feature::new("f", FeatureType::Misc, 0, 2, Strand::Forward));
+
+        let mut delta = Delta::new();
+        delta.sequence_ops = vec![
+            SequenceOp::Insert { position: 0, bases: "GG".to_string() },
+            SequenceOp::Delete { position: 5, length: 2 },
+        ];
+        assert!(matches!(
+            delta.apply(&mut seq),
+            Err(DeltaError::OutOfBounds { position: 5, length: 2, sequence_length: 6 })
+        ));
+        assert_eq!(seq.sequence(), "ACGT");
+
+        let mut delta = Delta::new();
+        delta.sequence_ops = vec![SequenceOp::Insert { position: 5, bases: "A".to_string() }];
+        assert!(matches!(delta.apply(&mut seq), Err(DeltaError::OutOfBounds { .. })));
+
+        let mut delta = Delta::new();
+        delta.annotation_ops = vec![AnnotationOp::Remove { feature_id: Uuid::new_v4() }];
+        assert!(matches!(delta.apply(&mut seq), Err(DeltaError::UnknownFeature(_))));
+
+        let mut delta = Delta::new();
+        delta.metadata_ops = vec![MetadataOp { key: "color".to_string(), value: "x".to_string() }];
+        assert!(matches!(delta.apply(&mut seq), Err(DeltaError::UnknownMetadataKey(_))));
+        assert_eq!(seq.features().len(), 1);
+    }
+}
@@ -0,0 +1,123 @@
+use helix_core::Sequence;
+
+use crate::delta::{Delta, DeltaError};
+
+/// Undo/redo history for one sequence.
+///
+/// Holds the current sequence plus two stacks: the inverses of applied deltas
+/// (undo) and the deltas that were undone (redo). Applying a new delta clears
+/// the redo stack.
+#[derive(Debug, Clone)]
+pub struct EditHistory {
+    sequence: Sequence,
+    undo_stack: Vec<Delta>,
+    redo_stack: Vec<Delta>,
+}
+
+impl EditHistory {
+    pub fn new(sequence: Sequence) -> Self {
+        Self {
+            sequence,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    /// The sequence with all applied (and not undone) deltas.
+    pub fn sequence(&self) -> &Sequence {
+        &self.sequence
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Apply `delta` to the current sequence. On error nothing changes.
+    pub fn apply(&mut self, delta: Delta) -> Result<(), DeltaError> {
+        let inverse = delta.invert(&self.sequence)?;
+        delta.apply(&mut self.sequence)?;
+        self.undo_stack.push(inverse);
+        self.redo_stack.clear();
+        Ok(())
+    }
+
+    /// Undo the most recent delta. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> Result<bool, DeltaError> {
+        Self::step(&mut self.sequence, &mut self.undo_stack, &mut self.redo_stack)
+    }
+
+    /// Reapply the most recently undone delta. Returns false if there is
+    /// nothing to redo.
+    pub fn redo(&mut self) -> Result<bool, DeltaError> {
+        Self::step(&mut self.sequence, &mut self.redo_stack, &mut self.undo_stack)
+    }
+
+    /// Pop a delta from `from`, apply it and push its inverse onto `to`.
+    fn step(
+        sequence: &mut Sequence,
+        from: &mut Vec<Delta>,
+        to: &mut Vec<Delta>,
+    ) -> Result<bool, DeltaError> {
+        let Some(delta) = from.pop() else {
+            return Ok(false);
+        };
+        let inverse = delta.invert(sequence)?;
+        delta.apply(sequence)?;
+        to.push(inverse);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delta::SequenceOp;
+    use helix_core::Topology;
+
+    fn insert(position: usize, bases: &str) -> Delta {
+        let mut delta = Delta::new();
+        delta.sequence_ops.push(SequenceOp::Insert {
+            position,
+            bases: bases.to_string(),
+        });
+        delta
+    }
+
+    #[test]
+    fn test_insert_undo_redo() {
+        let mut history = EditHistory::new(Sequence::new("p", "AAAATTTT", Topology::Linear));
+        assert!(!history.can_undo());
+
+        history.apply(insert(4, "GGG")).unwrap();
+        assert_eq!(history.sequence().sequence(), "AAAAGGGTTTT");
+
+        assert!(history.undo().unwrap());
+        assert_eq!(history.sequence().sequence(), "AAAATTTT");
+        assert!(!history.undo().unwrap());
+
+        assert!(history.redo().unwrap());
+        assert_eq!(history.sequence().sequence(), "AAAAGGGTTTT");
+        assert!(!history.redo().unwrap());
+    }
+
+    #[test]
+    fn test_apply_clears_redo_and_rejects_bad_delta() {
+        let mut history = EditHistory::new(Sequence::new("p", "ACGT", Topology::Linear));
+        history.apply(insert(0, "TT")).unwrap();
+        history.undo().unwrap();
+        assert!(history.can_redo());
+
+        history.apply(insert(4, "CC")).unwrap();
+        assert!(!history.can_redo());
+        assert_eq!(history.sequence().sequence(), "ACGTCC");
+
+        assert!(history.apply(insert(10, "A")).is_err());
+        assert_eq!(history.sequence().sequence(), "ACGTCC");
+        history.undo().unwrap();
+        assert_eq!(history.sequence().sequence(), "ACGT");
+    }
+}
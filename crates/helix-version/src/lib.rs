@@ -2,5 +2,8 @@
 //! Full implementation in Step 16 (Month 4).
 
 pub mod delta;
+pub mod history;
+pub mod notes;
 
 pub use delta::*;
+pub use history::EditHistory;
@@ -0,0 +1,104 @@
+use rusqlite::{params, Connection, Result as SqlResult};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A free-text note attached to a sequence, kept alongside its versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    /// Monotonically increasing note ID (insertion order)
+    pub id: i64,
+    pub sequence_id: Uuid,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub author: String,
+    pub text: String,
+}
+
+/// Create the notes table if it does not exist.
+pub fn init_notes(conn: &Connection) -> SqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS notes (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            sequence_id TEXT NOT NULL,
+            timestamp   TEXT NOT NULL,
+            author      TEXT NOT NULL,
+            text        TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_notes_sequence ON notes(sequence_id);",
+    )
+}
+
+/// Attach a note to a sequence, timestamped now. Returns the new note's ID.
+pub fn add_note(conn: &Connection, sequence_id: Uuid, author: &str, text: &str) -> SqlResult<i64> {
+    conn.execute(
+        "INSERT INTO notes (sequence_id, timestamp, author, text) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            sequence_id.to_string(),
+            // Fixed-width UTC so the text column sorts chronologically
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            author,
+            text
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// All notes for a sequence, oldest first.
+pub fn get_notes(conn: &Connection, sequence_id: Uuid) -> SqlResult<Vec<Note>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, timestamp, author, text FROM notes
+         WHERE sequence_id = ?1
+         ORDER BY timestamp, id",
+    )?;
+    let rows = stmt.query_map(params![sequence_id.to_string()], |row| {
+        let timestamp: String = row.get(1)?;
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&timestamp)
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    1,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })?
+            .with_timezone(&chrono::Utc);
+        Ok(Note {
+            id: row.get(0)?,
+            sequence_id,
+            timestamp,
+            author: row.get(2)?,
+            text: row.get(3)?,
+        })
+    })?;
+    rows.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_notes(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_notes_in_chronological_order() {
+        let conn = test_db();
+        let seq_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+
+        add_note(&conn, seq_id, "ada", "Verified by Sanger").unwrap();
+        add_note(&conn, other_id, "ada", "Unrelated").unwrap();
+        add_note(&conn, seq_id, "lin", "Swapped promoter to J23100").unwrap();
+
+        let notes = get_notes(&conn, seq_id).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].text, "Verified by Sanger");
+        assert_eq!(notes[0].author, "ada");
+        assert_eq!(notes[1].text, "Swapped promoter to J23100");
+        assert!(notes[0].timestamp <= notes[1].timestamp);
+        assert!(notes.iter().all(|n| n.sequence_id == seq_id));
+
+        assert!(get_notes(&conn, Uuid::new_v4()).unwrap().is_empty());
+    }
+}
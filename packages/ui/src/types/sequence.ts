@@ -21,6 +21,27 @@ export interface FeatureDto {
   qualifiers: QualifierDto[];
 }
 
+/** One step of a batch feature edit (see `edit_features`) */
+export type FeatureEditOp =
+  | { op: 'delete'; id: string }
+  | { op: 'recolor'; id: string; color: string }
+  | { op: 'rename'; id: string; name: string };
+
+/** An edit recorded by the undo/redo history (see `history_apply`) */
+export interface SequenceDelta {
+  sequence_ops?: (
+    | { op: 'insert'; position: number; bases: string }
+    | { op: 'delete'; position: number; length: number }
+    | { op: 'replace'; position: number; length: number; bases: string }
+  )[];
+  annotation_ops?: (
+    | { op: 'add'; feature_json: string }
+    | { op: 'remove'; feature_id: string }
+    | { op: 'modify'; feature_id: string; changes_json: string }
+  )[];
+  metadata_ops?: { key: string; value: string }[];
+}
+
 export interface QualifierDto {
   key: string;
   value: string;
@@ -31,6 +52,12 @@ export interface MatchDto {
   end: number;
   matched: string;
   isComplement: boolean;
+  isWrapped: boolean;
+}
+
+export interface SequenceSearchResult {
+  id: string;
+  matches: MatchDto[];
 }
 
 export interface OrfDto {
@@ -41,6 +68,70 @@ export interface OrfDto {
   protein: string;
 }
 
+export interface FrameTranslationDto {
+  label: string;
+  frame: number;
+  protein: string;
+}
+
+export interface CodonTableDto {
+  id: number;
+  name: string;
+}
+
+export interface ValidationDto {
+  isValid: boolean;
+  expectedType: 'dna' | 'rna' | 'protein';
+  inferredType: 'dna' | 'rna' | 'protein';
+  invalidPositions: number[];
+  cleaned: string;
+  removedCount: number;
+}
+
+export interface AlignmentResultDto {
+  score: number;
+  targetStart: number;
+  targetEnd: number;
+  queryStart: number;
+  queryEnd: number;
+  matches: number;
+  mismatches: number;
+  gaps: number;
+  alignmentLength: number;
+  percentIdentity: number;
+}
+
+export interface SequenceStatsDto {
+  length: number;
+  gcContent: number;
+  featureCount: number;
+  featureCoverage: number;
+  featureTypeCounts: Record<string, number>;
+}
+
+export interface DotplotPointDto {
+  x: number;
+  y: number;
+  isReverse: boolean;
+}
+
+export interface DotplotDto {
+  lengthA: number;
+  lengthB: number;
+  wordSize: number;
+  points: DotplotPointDto[];
+  hasForward: boolean;
+  hasReverse: boolean;
+  truncated: boolean;
+}
+
+export interface AnnealingDto {
+  forwardTm: number;
+  reverseTm: number;
+  annealingTemp: number;
+  warning?: string;
+}
+
 export interface EditorTab {
   id: string;
   sequenceId: string;
@@ -62,6 +153,18 @@ export interface EnzymeDto {
   overhang: string;
 }
 
+export interface EnzymeSummaryDto {
+  name: string;
+  recognitionSite: string;
+  cutPositions: number[];
+  classification: 'unique' | 'multiple' | 'none';
+}
+
+export interface RestrictionMapDto {
+  enzymes: EnzymeSummaryDto[];
+  cuts: EnzymeDto[];
+}
+
 export interface ComponentDto {
   id: number;
   name: string;
@@ -84,3 +187,28 @@ export interface AnnotationHitDto {
   alignmentScore: number;
   color: string;
 }
+
+export interface BackboneGuessDto {
+  name: string;
+  /** Fraction (0 to 1) of the family's marker components found */
+  confidence: number;
+  matchedComponents: string[];
+}
+
+export interface ScoringParamsDto {
+  matchScore: number;
+  mismatchScore: number;
+  gapOpen: number;
+  gapExtend: number;
+}
+
+export interface AnnotationConfigDto {
+  minIdentity: number;
+  minCoverage: number;
+  scoring: ScoringParamsDto;
+  bandWidth?: number;
+  minScore: number;
+  mode: 'dna' | 'protein' | 'both';
+  overlapStrategy: 'keep_best_score' | 'keep_longest' | 'keep_all';
+  overlapThreshold: number;
+}